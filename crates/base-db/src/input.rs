@@ -38,6 +38,10 @@ impl SourceRoot {
         }
     }
 
+    pub fn root(&self) -> &VfsPath {
+        &self.root
+    }
+
     pub fn path_for_file(&self, file: &FileId) -> Option<&VfsPath> {
         self.file_set.path_for_file(file)
     }
@@ -0,0 +1,721 @@
+//! Formatter for SourcePawn files, working on the tree-sitter tree of a file.
+//!
+//! The formatter only rewrites the whitespace between the tokens of the tree: the indentation
+//! of the lines, the spacing between the tokens of a line, the blank lines and the placement of
+//! the opening braces. The tokens themselves are never modified, and neither are the lines it is
+//! asked to protect, e.g. the preprocessor directives and the inactive `#if` regions.
+
+use std::ops::Range;
+
+use fxhash::{FxHashMap, FxHashSet};
+use lsp_types::{Position, TextEdit};
+use syntax::TSKind;
+use tree_sitter::Node;
+
+/// Maximum number of consecutive blank lines kept between two tokens.
+const MAX_BLANK_LINES: usize = 1;
+
+/// Placement of the opening brace of a body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BraceStyle {
+    /// The brace ends the line of the declaration or statement, e.g. `void OnPluginStart() {`.
+    SameLine,
+
+    /// The brace is on its own line, at the indentation of the declaration or statement.
+    #[default]
+    NextLine,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattingConfig {
+    pub brace_style: BraceStyle,
+
+    /// Number of columns of one level of indentation.
+    pub indent_width: u32,
+
+    /// Whether to indent with spaces instead of tabs.
+    pub insert_spaces: bool,
+
+    /// Whether to surround the binary and assignment operators with spaces.
+    pub operator_spacing: bool,
+
+    /// Whether to align the values of the variants of enums, and the names of the members of
+    /// methodmaps, on consecutive lines.
+    pub align_bodies: bool,
+}
+
+impl Default for FormattingConfig {
+    fn default() -> Self {
+        Self {
+            brace_style: BraceStyle::default(),
+            indent_width: 4,
+            insert_spaces: true,
+            operator_spacing: true,
+            align_bodies: false,
+        }
+    }
+}
+
+/// Lines of the source whose text differs from the text seen by the user.
+///
+/// The tree is parsed from the preprocessed text, where the directives and the inactive regions
+/// are blanked and the macros are expanded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtectedLines {
+    /// Lines that must be left byte-identical.
+    pub verbatim: FxHashSet<u32>,
+
+    /// Lines whose indentation can be changed, but not the rest of their text.
+    pub indent_only: FxHashSet<u32>,
+}
+
+/// Format a file.
+///
+/// Returns the edits to apply to `source`, or [`None`] if the tree contains syntax errors.
+/// If a range is provided, only the edits that start on the lines of the range are returned.
+///
+/// Like the columns of the tree, the columns of the edits are UTF-8 offsets in their line. They
+/// must be converted to the encoding negotiated with the client before being sent.
+pub fn format(
+    tree: &tree_sitter::Tree,
+    source: &str,
+    protected: &ProtectedLines,
+    config: &FormattingConfig,
+    range: Option<lsp_types::Range>,
+) -> Option<Vec<TextEdit>> {
+    let root = tree.root_node();
+    if root.has_error() {
+        return None;
+    }
+    let mut formatter = Formatter::new(root, source, protected, config);
+    formatter.compute_gaps();
+    if config.align_bodies {
+        formatter.align_bodies(root);
+    }
+
+    let mut edits = formatter.edits();
+    if let Some(range) = range {
+        edits.retain(|edit| {
+            range.start.line <= edit.range.start.line && edit.range.start.line <= range.end.line
+        });
+    }
+
+    Some(edits)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    node: Node<'a>,
+    kind: TSKind,
+    text: &'a str,
+}
+
+impl Token<'_> {
+    fn is_comment(&self) -> bool {
+        self.kind == TSKind::comment
+    }
+
+    fn is_preproc(&self) -> bool {
+        is_preproc(self.kind)
+    }
+
+    fn start_line(&self) -> u32 {
+        self.node.start_position().row as u32
+    }
+}
+
+/// The whitespace before a token, with its replacements.
+#[derive(Debug)]
+struct Gap<'a> {
+    text: &'a str,
+
+    /// Position of the start of the whitespace, with a UTF-8 column.
+    start: Position,
+
+    /// Replacements of byte ranges of the whitespace, ordered and disjoint.
+    pieces: Vec<(Range<usize>, String)>,
+}
+
+impl Gap<'_> {
+    fn new_text(&self) -> String {
+        let mut res = String::new();
+        let mut last = 0;
+        for (range, text) in &self.pieces {
+            res.push_str(&self.text[last..range.start]);
+            res.push_str(text);
+            last = range.end;
+        }
+        res.push_str(&self.text[last..]);
+
+        res
+    }
+
+    /// Position of a byte offset of the whitespace.
+    fn position(&self, offset: usize) -> Position {
+        let mut pos = self.start;
+        for c in self.text[..offset].bytes() {
+            if c == b'\n' {
+                pos.line += 1;
+                pos.character = 0;
+            } else {
+                pos.character += 1;
+            }
+        }
+
+        pos
+    }
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    protected: &'a ProtectedLines,
+    config: &'a FormattingConfig,
+    tokens: Vec<Token<'a>>,
+
+    /// The gap before each token, the first one starting at the beginning of the file.
+    gaps: Vec<Gap<'a>>,
+
+    /// Lines of the preprocessor directives of the tree.
+    preproc_lines: FxHashSet<u32>,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(
+        root: Node<'a>,
+        source: &'a str,
+        protected: &'a ProtectedLines,
+        config: &'a FormattingConfig,
+    ) -> Self {
+        let mut tokens = Vec::new();
+        collect_tokens(root, source, &mut tokens);
+        let mut preproc_lines = FxHashSet::default();
+        for token in tokens.iter().filter(|token| token.is_preproc()) {
+            let start = token.node.start_position();
+            let end = token.node.end_position();
+            // Directives include their trailing newline.
+            let end_line = if end.column == 0 && end.row > start.row {
+                end.row - 1
+            } else {
+                end.row
+            };
+            preproc_lines.extend(start.row as u32..=end_line as u32);
+        }
+
+        Self {
+            source,
+            protected,
+            config,
+            tokens,
+            gaps: Vec::new(),
+            preproc_lines,
+        }
+    }
+
+    /// Whether the tokens and the whitespace of a line can be changed.
+    fn is_normal(&self, line: u32) -> bool {
+        !self.protected.verbatim.contains(&line)
+            && !self.protected.indent_only.contains(&line)
+            && !self.preproc_lines.contains(&line)
+    }
+
+    /// Whether the indentation of a line can be changed.
+    fn can_indent(&self, line: u32) -> bool {
+        !self.protected.verbatim.contains(&line) && !self.preproc_lines.contains(&line)
+    }
+
+    fn compute_gaps(&mut self) {
+        let mut offset = 0;
+        let mut start = Position::new(0, 0);
+        for idx in 0..self.tokens.len() {
+            let token = self.tokens[idx];
+            let text = &self.source[offset..token.node.start_byte()];
+            let mut gap = Gap {
+                text,
+                start,
+                pieces: Vec::new(),
+            };
+            // Leave the gap alone if the tree is out of sync with the source.
+            if text.chars().all(char::is_whitespace) {
+                gap.pieces = self.gap_pieces(idx, &gap);
+            }
+            self.gaps.push(gap);
+            offset = token.node.end_byte();
+            let end = token.node.end_position();
+            start = Position::new(end.row as u32, end.column as u32);
+        }
+    }
+
+    fn gap_pieces(&self, idx: usize, gap: &Gap) -> Vec<(Range<usize>, String)> {
+        let token = self.tokens[idx];
+        let text = gap.text;
+        let line = token.start_line();
+        let Some(prev) = idx.checked_sub(1).map(|idx| self.tokens[idx]) else {
+            // Indentation of the first line of the file.
+            let start = text.rfind('\n').map_or(0, |nl| nl + 1);
+            if self.can_indent(line) {
+                return vec![(start..text.len(), self.indent(idx))];
+            }
+            return Vec::new();
+        };
+        let prev_line = gap.start.line;
+        let newlines = text.matches('\n').count();
+
+        match self.brace_on_same_line(prev, idx) {
+            Some(true) if newlines > 0 => {
+                if (prev_line..=line).all(|line| self.is_normal(line)) {
+                    return vec![(0..text.len(), " ".to_string())];
+                }
+            }
+            Some(false) if newlines == 0 => {
+                if self.is_normal(line) {
+                    return vec![(0..text.len(), format!("\n{}", self.indent(idx)))];
+                }
+                return Vec::new();
+            }
+            _ => (),
+        }
+
+        // Directives end with their newline.
+        let after_newline = prev.text.ends_with('\n');
+        if newlines == 0 && !after_newline {
+            if !self.is_normal(line) {
+                return Vec::new();
+            }
+            return self
+                .inline_spacing(prev, idx, text)
+                .map(|spacing| vec![(0..text.len(), spacing)])
+                .unwrap_or_default();
+        }
+
+        let mut res = Vec::new();
+        let (blank_start, first_blank_line) = if after_newline {
+            (0, prev_line)
+        } else {
+            let first_nl = text.find('\n').unwrap_or_default();
+            if first_nl > 0 && self.is_normal(prev_line) {
+                // Trailing whitespace.
+                res.push((0..first_nl, String::new()));
+            }
+            (first_nl + 1, prev_line + 1)
+        };
+        let indent_start = text.rfind('\n').map_or(0, |nl| nl + 1);
+        if (first_blank_line..line).all(|line| self.is_normal(line)) {
+            let blank_lines = text[blank_start..indent_start]
+                .matches('\n')
+                .count()
+                .min(MAX_BLANK_LINES);
+            res.push((blank_start..indent_start, "\n".repeat(blank_lines)));
+        }
+        if self.can_indent(line) {
+            res.push((indent_start..text.len(), self.indent(idx)));
+        }
+
+        res
+    }
+
+    /// Whether the token is an opening brace, or an `else` following a closing brace, that
+    /// should be on the same line as the previous token according to the brace style.
+    ///
+    /// Returns [`None`] if the brace style does not apply to the token.
+    fn brace_on_same_line(&self, prev: Token, idx: usize) -> Option<bool> {
+        let token = self.tokens[idx];
+        let same_line = self.config.brace_style == BraceStyle::SameLine;
+        if prev.is_comment() || prev.is_preproc() {
+            return None;
+        }
+        if token.text == "else" && prev.text == "}" {
+            return Some(same_line);
+        }
+        if !is_body_brace(token) || matches!(prev.text, "{" | "}" | ";" | ":" | "," | "=") {
+            return None;
+        }
+        if !same_line {
+            // Keep the braces of a body on a single line together, e.g. `public get() { ... }`.
+            let close = token.node.parent().and_then(|parent| {
+                parent
+                    .children(&mut parent.walk())
+                    .find(|child| TSKind::from(child) == TSKind::anon_RBRACE)
+            })?;
+            if close.start_position().row == token.node.start_position().row {
+                return None;
+            }
+        }
+
+        Some(same_line)
+    }
+
+    /// Compute the whitespace between two tokens of the same line.
+    ///
+    /// Returns [`None`] if the whitespace should be kept as is.
+    fn inline_spacing(&self, prev: Token, idx: usize, text: &str) -> Option<String> {
+        let token = self.tokens[idx];
+        if prev.is_comment() || token.is_comment() {
+            // Keep the alignment of trailing comments.
+            return None;
+        }
+        if is_operator(prev) || is_operator(token) {
+            // Never glue two operators together, e.g. `a - -b`.
+            let glued = prev.text.ends_with(is_operator_char)
+                && token.text.starts_with(is_operator_char);
+            let spacing = if self.config.operator_spacing || glued {
+                " "
+            } else {
+                ""
+            };
+            return Some(spacing.to_string());
+        }
+        let spacing = match (prev.text, token.text) {
+            (_, "," | ";") => "",
+            ("(" | "[", _) | (_, ")" | "]") => "",
+            ("," | ";", _) => " ",
+            ("if" | "for" | "while" | "switch", "(") => " ",
+            (_, "{") if is_body_brace(token) => " ",
+            _ if text.is_empty() => return None,
+            _ => " ",
+        };
+
+        Some(spacing.to_string())
+    }
+
+    /// Compute the indentation of a token that starts a line.
+    fn indent(&self, idx: usize) -> String {
+        let token = self.tokens[idx];
+        let mut depth = 0;
+        let mut node = token.node;
+        while let Some(parent) = node.parent() {
+            if brace_encloses(parent, token) {
+                depth += 1;
+            }
+            if TSKind::from(parent) == TSKind::switch_case && self.after_case_colon(parent, token)
+            {
+                depth += 1;
+            }
+            node = parent;
+        }
+        if self.is_continuation(idx) {
+            depth += 1;
+        }
+
+        if self.config.insert_spaces {
+            " ".repeat((depth * self.config.indent_width) as usize)
+        } else {
+            "\t".repeat(depth as usize)
+        }
+    }
+
+    /// Whether the token is in the statements of a `case`, after its colon.
+    fn after_case_colon(&self, case: Node, token: Token) -> bool {
+        case.children(&mut case.walk())
+            .find(|child| child.utf8_text(self.source.as_bytes()).ok() == Some(":"))
+            .is_some_and(|colon| colon.end_byte() <= token.node.start_byte())
+    }
+
+    /// Whether a token that starts a line continues the statement or declaration of the
+    /// previous line, e.g. the arguments of a call split on several lines.
+    fn is_continuation(&self, idx: usize) -> bool {
+        let token = self.tokens[idx];
+        if token.is_comment() || matches!(token.text, "{" | "}" | ")" | "]" | "else") {
+            return false;
+        }
+        let Some(prev) = self.tokens[..idx]
+            .iter()
+            .rev()
+            .find(|token| !token.is_comment())
+        else {
+            return false;
+        };
+        if prev.is_preproc() {
+            return false;
+        }
+        match prev.text {
+            ";" | "{" | "}" => false,
+            ":" => prev
+                .node
+                .parent()
+                .map_or(true, |parent| TSKind::from(parent) != TSKind::switch_case),
+            // Items of enums and array literals.
+            "," => innermost_bracket(token) != Some(TSKind::anon_LBRACE),
+            _ => true,
+        }
+    }
+
+    /// Align the values of the variants of enums and the names of the members of methodmaps.
+    fn align_bodies(&mut self, root: Node) {
+        let token_idx = self
+            .tokens
+            .iter()
+            .enumerate()
+            .map(|(idx, token)| (token.node.start_byte(), idx))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut cursor = root.walk();
+        'outer: loop {
+            let node = cursor.node();
+            let aligned = match TSKind::from(node) {
+                TSKind::r#enum => descendants(node, TSKind::enum_entry)
+                    .into_iter()
+                    .filter_map(|entry| {
+                        entry
+                            .children(&mut entry.walk())
+                            .find(|child| TSKind::from(child) == TSKind::anon_EQ)
+                    })
+                    .collect(),
+                TSKind::methodmap => node
+                    .children(&mut node.walk())
+                    .filter(|member| is_methodmap_member(TSKind::from(member)))
+                    .filter_map(|member| member.child_by_field_name("name"))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            if !aligned.is_empty() {
+                groups.push(
+                    aligned
+                        .iter()
+                        .filter_map(|node| token_idx.get(&node.start_byte()).copied())
+                        .collect(),
+                );
+            }
+            if cursor.goto_first_child() || cursor.goto_next_sibling() {
+                continue;
+            }
+            loop {
+                if !cursor.goto_parent() {
+                    break 'outer;
+                }
+                if cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+
+        let columns = self.output_columns();
+        for group in groups {
+            for run in self.alignment_runs(&group) {
+                let Some(target) = run.iter().map(|idx| columns[*idx]).max() else {
+                    continue;
+                };
+                for idx in run {
+                    let padding = " ".repeat(target - columns[idx]);
+                    let gap = &mut self.gaps[idx];
+                    let text = format!("{}{}", gap.new_text(), padding);
+                    gap.pieces = vec![(0..gap.text.len(), text)];
+                }
+            }
+        }
+    }
+
+    /// Split the aligned tokens of a body in runs of tokens on consecutive lines.
+    fn alignment_runs(&self, group: &[usize]) -> Vec<Vec<usize>> {
+        let mut res: Vec<Vec<usize>> = Vec::new();
+        let mut prev_line = None;
+        for &idx in group {
+            let line = self.tokens[idx].start_line();
+            // Only the tokens in the middle of a line can be aligned.
+            if !self.is_normal(line) || self.gaps[idx].text.contains('\n') {
+                prev_line = None;
+                continue;
+            }
+            match (res.last_mut(), prev_line) {
+                (Some(run), Some(prev_line)) if line == prev_line + 1 => run.push(idx),
+                _ => res.push(vec![idx]),
+            }
+            prev_line = Some(line);
+        }
+
+        res
+    }
+
+    /// Compute the column of each token in the formatted text.
+    fn output_columns(&self) -> Vec<usize> {
+        let mut res = Vec::with_capacity(self.tokens.len());
+        let mut column = 0;
+        for (token, gap) in self.tokens.iter().zip(&self.gaps) {
+            advance_column(&mut column, &gap.new_text());
+            res.push(column);
+            advance_column(&mut column, token.text);
+        }
+
+        res
+    }
+
+    fn edits(&self) -> Vec<TextEdit> {
+        let mut res = Vec::new();
+        for gap in &self.gaps {
+            for (range, text) in &gap.pieces {
+                if gap.text[range.clone()] == *text {
+                    continue;
+                }
+                res.push(TextEdit::new(
+                    lsp_types::Range::new(gap.position(range.start), gap.position(range.end)),
+                    text.clone(),
+                ));
+            }
+        }
+
+        res
+    }
+}
+
+/// Collect the leaves of the tree, keeping the comments, the literals and the preprocessor
+/// directives whole.
+fn collect_tokens<'a>(node: Node<'a>, source: &'a str, acc: &mut Vec<Token<'a>>) {
+    let kind = TSKind::from(node);
+    if node.child_count() == 0
+        || is_preproc(kind)
+        || matches!(
+            kind,
+            TSKind::comment | TSKind::string_literal | TSKind::char_literal
+        )
+    {
+        // Automatic semicolons are empty.
+        if node.start_byte() < node.end_byte() {
+            acc.push(Token {
+                node,
+                kind,
+                text: &source[node.start_byte()..node.end_byte()],
+            });
+        }
+        return;
+    }
+    for child in node.children(&mut node.walk()) {
+        collect_tokens(child, source, acc);
+    }
+}
+
+fn is_preproc(kind: TSKind) -> bool {
+    matches!(
+        kind,
+        TSKind::preproc_include
+            | TSKind::preproc_tryinclude
+            | TSKind::preproc_macro
+            | TSKind::preproc_define
+            | TSKind::preproc_undefine
+            | TSKind::preproc_if
+            | TSKind::preproc_elseif
+            | TSKind::preproc_else
+            | TSKind::preproc_endif
+            | TSKind::preproc_endinput
+            | TSKind::preproc_assert
+            | TSKind::preproc_pragma
+            | TSKind::preproc_error
+            | TSKind::preproc_warning
+    )
+}
+
+fn is_methodmap_member(kind: TSKind) -> bool {
+    matches!(
+        kind,
+        TSKind::methodmap_native
+            | TSKind::methodmap_native_constructor
+            | TSKind::methodmap_native_destructor
+            | TSKind::methodmap_method
+            | TSKind::methodmap_method_constructor
+            | TSKind::methodmap_method_destructor
+            | TSKind::methodmap_property
+    )
+}
+
+/// Whether the token is the opening brace of a body, as opposed to an array literal.
+fn is_body_brace(token: Token) -> bool {
+    token.kind == TSKind::anon_LBRACE
+        && token.node.parent().is_some_and(|parent| {
+            !matches!(
+                TSKind::from(parent),
+                TSKind::array_literal | TSKind::struct_constructor
+            )
+        })
+}
+
+/// Whether the token is a binary or an assignment operator.
+fn is_operator(token: Token) -> bool {
+    let Some(parent) = token.node.parent() else {
+        return false;
+    };
+    match TSKind::from(parent) {
+        TSKind::binary_expression
+        | TSKind::case_binary_expression
+        | TSKind::assignment_expression => {
+            parent.child_by_field_name("operator") == Some(token.node)
+        }
+        TSKind::ternary_expression => matches!(token.text, "?" | ":"),
+        TSKind::variable_declaration
+        | TSKind::old_variable_declaration
+        | TSKind::enum_entry
+        | TSKind::struct_field_value
+        | TSKind::named_arg => token.text == "=",
+        _ => false,
+    }
+}
+
+fn is_operator_char(c: char) -> bool {
+    "+-*/%=<>!&|^~?:".contains(c)
+}
+
+/// Whether the token is between the braces of the node.
+fn brace_encloses(node: Node, token: Token) -> bool {
+    let mut open = None;
+    let mut close = None;
+    for child in node.children(&mut node.walk()) {
+        match TSKind::from(child) {
+            TSKind::anon_LBRACE if open.is_none() => open = Some(child),
+            TSKind::anon_RBRACE => close = Some(child),
+            _ => (),
+        }
+    }
+    let start = token.node.start_byte();
+
+    open.is_some_and(|open| open.end_byte() <= start)
+        && close.map_or(true, |close| start < close.start_byte())
+}
+
+/// Get the kind of the innermost brace, parenthesis or bracket that encloses the token.
+fn innermost_bracket(token: Token) -> Option<TSKind> {
+    let start = token.node.start_byte();
+    let mut node = token.node;
+    while let Some(parent) = node.parent() {
+        let mut open = None;
+        for child in parent.children(&mut parent.walk()) {
+            let kind = TSKind::from(child);
+            match kind {
+                TSKind::anon_LBRACE | TSKind::anon_LPAREN | TSKind::anon_LBRACK
+                    if child.end_byte() <= start =>
+                {
+                    open = Some(kind)
+                }
+                TSKind::anon_RBRACE | TSKind::anon_RPAREN | TSKind::anon_RBRACK
+                    if child.end_byte() <= start =>
+                {
+                    open = None
+                }
+                _ => (),
+            }
+        }
+        if open.is_some() {
+            return open;
+        }
+        node = parent;
+    }
+
+    None
+}
+
+fn descendants(node: Node, kind: TSKind) -> Vec<Node> {
+    let mut res = Vec::new();
+    for child in node.children(&mut node.walk()) {
+        if TSKind::from(child) == kind {
+            res.push(child);
+        } else {
+            res.extend(descendants(child, kind));
+        }
+    }
+
+    res
+}
+
+fn advance_column(column: &mut usize, text: &str) {
+    match text.rfind('\n') {
+        Some(nl) => *column = text[nl + 1..].chars().count(),
+        None => *column += text.chars().count(),
+    }
+}
@@ -0,0 +1,147 @@
+use formatter::{format, BraceStyle, FormattingConfig, ProtectedLines};
+use insta::assert_snapshot;
+use lsp_types::TextEdit;
+
+fn format_text(input: &str, protected: &ProtectedLines, config: &FormattingConfig) -> String {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_sourcepawn::language())
+        .unwrap();
+    let tree = parser.parse(input, None).unwrap();
+    let edits = format(&tree, input, protected, config, None).unwrap();
+
+    apply_edits(input, edits)
+}
+
+/// Apply the edits of the formatter, whose columns are UTF-8 offsets in their line.
+fn apply_edits(input: &str, mut edits: Vec<TextEdit>) -> String {
+    let line_starts = std::iter::once(0)
+        .chain(input.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect::<Vec<_>>();
+    let offset = |pos: lsp_types::Position| line_starts[pos.line as usize] + pos.character as usize;
+    edits.sort_by_key(|edit| edit.range.start);
+
+    let mut res = input.to_string();
+    for edit in edits.into_iter().rev() {
+        res.replace_range(offset(edit.range.start)..offset(edit.range.end), &edit.new_text);
+    }
+
+    res
+}
+
+#[test]
+fn indent_1() {
+    let input = r#"void OnPluginStart() {
+int a=1;
+  if (a==1) {
+a  =  2;
+  }
+}"#;
+
+    assert_snapshot!(format_text(
+        input,
+        &ProtectedLines::default(),
+        &FormattingConfig::default()
+    ));
+}
+
+#[test]
+fn same_line_braces_1() {
+    let input = r#"void OnPluginStart()
+{
+    if (true)
+    {
+        return;
+    }
+    else
+    {
+        return;
+    }
+}"#;
+    let config = FormattingConfig {
+        brace_style: BraceStyle::SameLine,
+        ..Default::default()
+    };
+
+    assert_snapshot!(format_text(input, &ProtectedLines::default(), &config));
+}
+
+#[test]
+fn preprocessor_1() {
+    let input = r#"#define FOO   1
+#pragma semicolon 1
+  int a;"#;
+
+    assert_snapshot!(format_text(
+        input,
+        &ProtectedLines::default(),
+        &FormattingConfig::default()
+    ));
+}
+
+#[test]
+fn protected_lines_1() {
+    // Lines 3 to 5 are an inactive region, blanked by the preprocessor.
+    let input = r#"void Foo()
+{
+    int a;
+
+
+
+  int c;
+}"#;
+    let protected = ProtectedLines {
+        verbatim: [3, 4, 5].into_iter().collect(),
+        ..Default::default()
+    };
+
+    assert_snapshot!(format_text(
+        input,
+        &protected,
+        &FormattingConfig::default()
+    ));
+}
+
+#[test]
+fn align_bodies_1() {
+    let input = r#"enum Foo
+{
+    Foo_A = 1,
+    Foo_Long = 2,
+    Foo_C
+}"#;
+    let config = FormattingConfig {
+        align_bodies: true,
+        ..Default::default()
+    };
+
+    assert_snapshot!(format_text(input, &ProtectedLines::default(), &config));
+}
+
+#[test]
+fn operator_spacing_1() {
+    let input = r#"int a = b + c * 2;"#;
+    let config = FormattingConfig {
+        operator_spacing: false,
+        ..Default::default()
+    };
+
+    assert_snapshot!(format_text(input, &ProtectedLines::default(), &config));
+}
+
+#[test]
+fn non_ascii_1() {
+    let input = r#"void OnPluginStart()
+{
+    char s[8] = "é";   
+    // déjà vu   
+    PrintToServer("%s",s);
+    int a=1; /* ü */ int b=2;   
+}"#;
+
+    assert_snapshot!(format_text(
+        input,
+        &ProtectedLines::default(),
+        &FormattingConfig::default()
+    ));
+}
@@ -1,6 +1,6 @@
 use crate::{
     db::DefMap,
-    hir::{Expr, ExprId, Ident, IdentId},
+    hir::{type_ref::TypeRef, Expr, ExprId, Ident, IdentId},
     src::HasSource,
     BlockId, DefDatabase, DefWithBodyId, InFile, Lookup, NodePtr,
 };
@@ -156,6 +156,14 @@ impl Body {
             .map(move |&block| (block, db.block_def_map(block)))
     }
 
+    /// Returns the declared type of a binding, if the expression is one.
+    pub fn binding_type_ref(&self, expr: ExprId) -> Option<&TypeRef> {
+        match &self[expr] {
+            Expr::Binding { type_ref, .. } => type_ref.as_ref(),
+            _ => None,
+        }
+    }
+
     fn new(
         db: &dyn DefDatabase,
         owner: DefWithBodyId,
@@ -362,10 +362,10 @@ impl ExprCollector<'_> {
             }
             TSKind::unary_expression | TSKind::update_expression => {
                 // For our needs, unary and update expressions are the same
-                let expr = expr.child_by_field_name("argument")?;
+                let operand = expr.child_by_field_name("argument")?;
                 let op = expr.child_by_field_name("operator").map(TSKind::from);
                 let unary = Expr::UnaryOp {
-                    operand: self.collect_expr(expr),
+                    operand: self.collect_expr(operand),
                     op,
                 };
                 Some(self.alloc_expr(unary, NodePtr::from(&expr)))
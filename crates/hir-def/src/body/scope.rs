@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use la_arena::{Arena, ArenaMap, Idx};
 use vfs::FileId;
 
@@ -69,9 +69,48 @@ impl ExprScopes {
         &self.scope_entries[entry]
     }
 
+    /// Returns the names declared directly in `scope`, without walking up its parents.
+    pub fn entries(&self, scope: ScopeId) -> impl Iterator<Item = (&Name, ExprId)> + '_ {
+        self.scopes[scope]
+            .entries
+            .iter()
+            .map(|(name, entry)| (name, self.scope_entries[*entry]))
+    }
+
     pub fn first_scope(&self) -> Option<ScopeId> {
         self.scopes.iter().next().map(|(id, _)| id)
     }
+
+    /// Returns the bindings (locals and parameters) of the body which are referenced by at least
+    /// one identifier of the body.
+    pub fn used_bindings(&self, body: &Body) -> FxHashSet<ExprId> {
+        let mut res = FxHashSet::default();
+        if let (Some(body_expr), Some(root)) = (body.body_expr, self.first_scope()) {
+            self.collect_used_bindings(body, body_expr, root, &mut res);
+        }
+        res
+    }
+
+    fn collect_used_bindings(
+        &self,
+        body: &Body,
+        expr: ExprId,
+        scope: ScopeId,
+        acc: &mut FxHashSet<ExprId>,
+    ) {
+        // Only the statements have a scope, the sub-expressions inherit the one of their parent.
+        let scope = self.scope_for(expr).unwrap_or(scope);
+        match &body[expr] {
+            Expr::Ident(name) => {
+                if let Some(entry) = self.resolve_name_in_scope(scope, name) {
+                    acc.insert(*self.entry(*entry));
+                }
+            }
+            // The name of a named argument refers to a parameter of the callee.
+            Expr::NamedArg { value, .. } => self.collect_used_bindings(body, *value, scope, acc),
+            it => it.walk_child_exprs(|child| self.collect_used_bindings(body, child, scope, acc)),
+        }
+    }
 }
 
 impl ExprScopes {
@@ -3,17 +3,18 @@ use std::sync::Arc;
 use fxhash::FxHashMap;
 use itertools::Itertools;
 use la_arena::{Arena, ArenaMap, Idx};
-use smol_str::ToSmolStr;
+use smol_str::{SmolStr, ToSmolStr};
 use syntax::TSKind;
 
 use crate::{
     hir::type_ref::TypeRef,
-    item_tree::{EnumStructItemId, MethodmapItemId, Name, Param, SpecialMethod},
+    item_tree::{EnumStructItemId, FunctionKind, MethodmapItemId, Name, Param, SpecialMethod},
     resolver::{global_resolver, ValueNs},
     src::{HasChildSource, HasSource},
-    DefDatabase, DefDiagnostic, EnumStructId, FuncenumId, FunctagId, FunctagLoc, FunctionId,
-    FunctionLoc, InFile, Intern, ItemTreeId, LocalFieldId, Lookup, MacroId, MethodmapId, NodePtr,
-    PropertyId, PropertyLoc, TypedefId, TypedefLoc, TypesetId,
+    DefDatabase, DefDiagnostic, EnumId, EnumStructId, FuncenumId, FunctagId, FunctagLoc,
+    FunctionId, FunctionLoc, InFile, Intern, ItemTreeId, LocalFieldId, Lookup, MacroId,
+    MethodmapId, NodePtr, PropertyId, PropertyLoc, TypedefId, TypedefLoc, TypesetId, VariantId,
+    VariantLoc,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -38,7 +39,11 @@ impl From<&Param> for ParamData {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FunctionData {
     pub name: Name,
+    pub kind: FunctionKind,
     pub type_ref: Option<TypeRef>,
+    /// The note of the `#pragma deprecated` directive preceding the function, if it is
+    /// deprecated. The note is empty if the directive does not have one.
+    pub deprecation: Option<SmolStr>,
     params: Vec<ParamData>,
 }
 
@@ -55,7 +60,9 @@ impl FunctionData {
 
         let function_data = FunctionData {
             name: function.name.clone(),
+            kind: function.kind,
             type_ref: function.ret_type.clone(),
+            deprecation: function.deprecation.clone(),
             params,
         };
 
@@ -70,6 +77,10 @@ impl FunctionData {
         self.type_ref.clone()
     }
 
+    pub fn params(&self) -> &[ParamData] {
+        &self.params
+    }
+
     pub fn number_of_mandatory_parameters(&self) -> usize {
         self.params
             .iter()
@@ -138,6 +149,9 @@ impl PropertyItem {
 pub struct PropertyData {
     pub id: PropertyId,
     pub getters_setters: Vec<PropertyItem>,
+    /// The note of the `#pragma deprecated` directive preceding the property, if it is
+    /// deprecated. The note is empty if the directive does not have one.
+    pub deprecation: Option<SmolStr>,
 }
 
 impl MethodmapData {
@@ -218,6 +232,7 @@ impl MethodmapData {
                             }
                         })
                         .collect(),
+                    deprecation: property.deprecation.clone(),
                 });
                 let property_id = items.alloc(property_data);
                 items_map.insert(property.name.clone(), property_id);
@@ -295,6 +310,7 @@ impl MethodmapData {
 pub struct TypedefData {
     pub name: Option<Name>,
     pub type_ref: TypeRef,
+    pub params: Vec<ParamData>,
 }
 
 impl TypedefData {
@@ -305,6 +321,11 @@ impl TypedefData {
         let typedef_data = TypedefData {
             name: typedef.name.clone(),
             type_ref: typedef.type_ref.clone(),
+            params: typedef
+                .params
+                .clone()
+                .map(|param_idx| ParamData::from(&item_tree[param_idx]))
+                .collect_vec(),
         };
 
         Arc::new(typedef_data)
@@ -355,6 +376,7 @@ impl TypesetData {
 pub struct FunctagData {
     pub name: Option<Name>,
     pub type_ref: Option<TypeRef>,
+    pub params: Vec<ParamData>,
 }
 
 impl FunctagData {
@@ -365,6 +387,11 @@ impl FunctagData {
         let functag_data = FunctagData {
             name: functag.name.clone(),
             type_ref: functag.type_ref.clone(),
+            params: functag
+                .params
+                .clone()
+                .map(|param_idx| ParamData::from(&item_tree[param_idx]))
+                .collect_vec(),
         };
 
         Arc::new(functag_data)
@@ -411,6 +438,43 @@ impl FuncenumData {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumData {
+    pub name: Name,
+    pub variants: Arc<Arena<VariantId>>,
+}
+
+impl EnumData {
+    pub(crate) fn enum_data_query(db: &dyn DefDatabase, id: EnumId) -> Arc<Self> {
+        let loc = id.lookup(db);
+        let item_tree = loc.id.tree_id().item_tree(db);
+        let enum_ = &item_tree[loc.id.value];
+        let mut variants = Arena::new();
+        enum_.variants.clone().for_each(|variant_idx| {
+            // Variants are declared in the same container as their enum.
+            let variant_id = VariantLoc {
+                container: loc.container,
+                id: ItemTreeId {
+                    tree: loc.id.tree_id(),
+                    value: variant_idx,
+                },
+            }
+            .intern(db);
+            let _ = variants.alloc(variant_id);
+        });
+        let enum_data = Self {
+            name: enum_.name.clone(),
+            variants: variants.into(),
+        };
+
+        Arc::new(enum_data)
+    }
+
+    pub fn name(&self) -> Name {
+        self.name.clone()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EnumStructData {
     pub name: Name,
@@ -553,6 +617,7 @@ impl HasChildSource<Idx<TypedefData>> for TypesetId {
                 let typedef = TypedefData {
                     name: None,
                     type_ref,
+                    params: Vec::new(),
                 };
                 map.insert(typedefs.alloc(typedef), NodePtr::from(&child));
             }
@@ -585,6 +650,7 @@ impl HasChildSource<Idx<FunctagData>> for FuncenumId {
             let functag = FunctagData {
                 name: None,
                 type_ref,
+                params: Vec::new(),
             };
             map.insert(functags.alloc(functag), NodePtr::from(&child));
         }
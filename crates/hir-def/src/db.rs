@@ -14,11 +14,12 @@ use crate::{
     ast_id_map::AstIdMap,
     body::{scope::ExprScopes, Body, BodySourceMap},
     data::{
-        EnumStructData, FuncenumData, FunctagData, FunctionData, MacroData, MethodmapData,
+        EnumData, EnumStructData, FuncenumData, FunctagData, FunctionData, MacroData, MethodmapData,
         TypedefData, TypesetData,
     },
     infer,
     item_tree::{ItemTree, Name},
+    references::FileReferences,
     BlockId, BlockLoc, DefDiagnostic, DefWithBodyId, EnumId, EnumLoc, EnumStructId, EnumStructLoc,
     FileDefId, FileItem, FuncenumId, FuncenumLoc, FunctagId, FunctagLoc, FunctionId, FunctionLoc,
     GlobalId, GlobalLoc, InferenceResult, Intern, ItemTreeId, Lookup, MacroId, MacroLoc,
@@ -89,6 +90,10 @@ pub trait DefDatabase: InternDatabase + PreprocDatabase {
     #[salsa::invoke(ExprScopes::expr_scopes_query)]
     fn expr_scopes(&self, def: DefWithBodyId, file_id: FileId) -> Arc<ExprScopes>;
 
+    /// Names referenced in the file, used to find the unused declarations.
+    #[salsa::invoke(FileReferences::file_references_query)]
+    fn file_references(&self, file_id: FileId) -> Arc<FileReferences>;
+
     // region: data
     #[salsa::invoke(FunctionData::function_data_query)]
     fn function_data(&self, id: FunctionId) -> Arc<FunctionData>;
@@ -96,6 +101,9 @@ pub trait DefDatabase: InternDatabase + PreprocDatabase {
     #[salsa::invoke(MacroData::macro_data_query)]
     fn macro_data(&self, id: MacroId) -> Arc<MacroData>;
 
+    #[salsa::invoke(EnumData::enum_data_query)]
+    fn enum_data(&self, id: EnumId) -> Arc<EnumData>;
+
     #[salsa::invoke(EnumStructData::enum_struct_data_query)]
     fn enum_struct_data(&self, id: EnumStructId) -> Arc<EnumStructData>;
 
@@ -401,6 +409,11 @@ impl DefMap {
         self.get_from_str(name).and_then(|v| v.first().copied())
     }
 
+    /// Iterate over all the names declared in this map and their definitions.
+    pub fn entries(&self) -> impl Iterator<Item = (&Name, &SmallVec<[FileDefId; 1]>)> + '_ {
+        self.values.iter()
+    }
+
     pub fn get_macro(&self, idx: &u32) -> Option<MacroId> {
         self.macros.get(idx).copied()
     }
@@ -109,6 +109,93 @@ pub enum Expr {
     Literal(Literal),
 }
 
+impl Expr {
+    /// Calls `f` on each of the direct child expressions of this expression.
+    pub fn walk_child_exprs(&self, mut f: impl FnMut(ExprId)) {
+        match self {
+            Expr::Missing | Expr::Ident(_) => (),
+            Expr::Block { statements, .. } => statements.iter().copied().for_each(f),
+            Expr::CommaExpr(exprs) | Expr::Decl(exprs) => exprs.iter().copied().for_each(f),
+            Expr::NamedArg { name, value } => {
+                f(*name);
+                f(*value);
+            }
+            Expr::New { args, .. } => args.iter().copied().for_each(f),
+            Expr::ViewAs { operand, .. } | Expr::UnaryOp { operand, .. } => f(*operand),
+            Expr::FieldAccess { target, .. } => f(*target),
+            Expr::ScopeAccess { scope, .. } => f(*scope),
+            Expr::ArrayIndexedAccess { array, index } => {
+                f(*array);
+                f(*index);
+            }
+            Expr::BinaryOp { lhs, rhs, .. } => {
+                f(*lhs);
+                f(*rhs);
+            }
+            Expr::TernaryOp {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                f(*condition);
+                f(*then_branch);
+                f(*else_branch);
+            }
+            Expr::Loop {
+                initialization,
+                condition,
+                iteration,
+                body,
+            } => {
+                initialization.iter().copied().for_each(&mut f);
+                f(*condition);
+                if let Some(iteration) = iteration {
+                    f(*iteration);
+                }
+                f(*body);
+            }
+            Expr::Switch { condition, cases } => {
+                f(*condition);
+                for case in cases.iter() {
+                    case.values().iter().copied().for_each(&mut f);
+                    f(case.body());
+                }
+            }
+            Expr::Condition {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                f(*condition);
+                f(*then_branch);
+                if let Some(else_branch) = else_branch {
+                    f(*else_branch);
+                }
+            }
+            Expr::Control { operand, .. } => {
+                if let Some(operand) = operand {
+                    f(*operand);
+                }
+            }
+            Expr::Call { callee, args } => {
+                f(*callee);
+                args.iter().copied().for_each(f);
+            }
+            Expr::MethodCall { target, args, .. } => {
+                f(*target);
+                args.iter().copied().for_each(f);
+            }
+            Expr::Binding { initializer, .. } => {
+                if let Some(initializer) = initializer {
+                    f(*initializer);
+                }
+            }
+            Expr::Literal(Literal::Array(items)) => items.iter().copied().for_each(f),
+            Expr::Literal(_) => (),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SwitchCase {
     values: Box<[ExprId]>,
@@ -1,8 +1,11 @@
 use std::sync::Arc;
 
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
+use preprocessor::BUILTIN_MACROS;
 use smallvec::smallvec;
+use smol_str::SmolStr;
 use stdx::impl_from;
+use syntax::TSKind;
 
 use crate::{
     body::Body,
@@ -13,6 +16,17 @@ use crate::{
     DefDatabase, DefWithBodyId, ExprId, FieldId, FunctionId, InFile, Lookup, PropertyId,
 };
 
+/// Constants defined by the compiler, which are not declared in any file. The macros it defines
+/// are in [`BUILTIN_MACROS`].
+const BUILTIN_CONSTANTS: &[&str] = &[
+    "cellbits", "cellmax", "cellmin", "charbits", "charmax", "charmin", "ucharmax", "debug",
+];
+
+/// Whether the name is a constant or a macro defined by the compiler.
+fn is_builtin(name: &str) -> bool {
+    BUILTIN_CONSTANTS.contains(&name) || BUILTIN_MACROS.contains(&name)
+}
+
 pub(crate) fn infer_query(db: &dyn DefDatabase, def: DefWithBodyId) -> Arc<InferenceResult> {
     let body = db.body(def);
     let resolver = def.resolver(db);
@@ -57,6 +71,32 @@ pub enum InferenceDiagnostic {
         expected: usize,
         actual: usize,
     },
+    UnresolvedIdent {
+        expr: ExprId,
+        name: Name,
+    },
+    MismatchedAssignmentType {
+        expr: ExprId,
+        expected: TypeRef,
+        actual: TypeRef,
+    },
+    MismatchedArgType {
+        expr: ExprId,
+        name: Name,
+        index: usize,
+        expected: TypeRef,
+        actual: TypeRef,
+    },
+    MismatchedReturnType {
+        expr: ExprId,
+        expected: TypeRef,
+        actual: TypeRef,
+    },
+    Deprecated {
+        expr: ExprId,
+        name: Name,
+        note: SmolStr,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -80,8 +120,12 @@ pub struct InferenceResult {
     attribute_resolutions: FxHashMap<ExprId, AttributeId>,
     /// For each method call expr, records the function it resolves to.
     method_resolutions: FxHashMap<ExprId, FunctionId>,
+    /// For each call expr, records the function it resolves to.
+    call_resolutions: FxHashMap<ExprId, FunctionId>,
     /// For each named argument, records the local it resolves to.
     named_arg_resolutions: FxHashMap<ExprId, (DefWithBodyId, ExprId)>,
+    /// For each expression whose type could be inferred, records that type.
+    type_of_expr: FxHashMap<ExprId, TypeRef>,
 
     pub diagnostics: Vec<InferenceDiagnostic>,
 }
@@ -95,9 +139,26 @@ impl InferenceResult {
         self.method_resolutions.get(&expr).copied()
     }
 
+    pub fn call_resolution(&self, expr: ExprId) -> Option<FunctionId> {
+        self.call_resolutions.get(&expr).copied()
+    }
+
+    /// Iterate over the calls that could be resolved, including the method and constructor
+    /// calls, with the function they resolve to.
+    pub fn calls(&self) -> impl Iterator<Item = (ExprId, FunctionId)> + '_ {
+        self.call_resolutions
+            .iter()
+            .chain(self.method_resolutions.iter())
+            .map(|(expr, function)| (*expr, *function))
+    }
+
     pub fn named_arg_resolution(&self, expr: ExprId) -> Option<(DefWithBodyId, ExprId)> {
         self.named_arg_resolutions.get(&expr).copied()
     }
+
+    pub fn type_of_expr(&self, expr: ExprId) -> Option<&TypeRef> {
+        self.type_of_expr.get(&expr)
+    }
 }
 
 /// The inference context contains all information needed during type inference.
@@ -109,6 +170,7 @@ pub(crate) struct InferenceContext<'a> {
     pub(crate) result: InferenceResult,
     pub(crate) resolver: Resolver,
     call_stack: Vec<Callee>,
+    return_ty: Option<TypeRef>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -131,6 +193,7 @@ impl<'a> InferenceContext<'a> {
             body,
             resolver,
             call_stack: Vec::new(),
+            return_ty: None,
         }
     }
 
@@ -150,14 +213,17 @@ impl<'a> InferenceContext<'a> {
         self.call_stack.last_mut()
     }
 
-    fn current_call_data(&self) -> Option<Arc<FunctionData>> {
+    fn current_call_fn(&self) -> Option<FunctionId> {
         let current_call = self.current_call()?;
         let id = current_call.id?;
         let ValueNs::FunctionId(fn_ids) = id else {
             return None;
         };
-        let fn_id = fn_ids.first()?.value;
-        self.db.function_data(fn_id).into()
+        fn_ids.first()?.value.into()
+    }
+
+    fn current_call_data(&self) -> Option<Arc<FunctionData>> {
+        self.db.function_data(self.current_call_fn()?).into()
     }
 
     /// Returns the min and max number of parameters for the current call.
@@ -180,6 +246,12 @@ impl<'a> InferenceContext<'a> {
 
 impl InferenceContext<'_> {
     pub(crate) fn infer_expr(&mut self, expr: &ExprId) -> Option<TypeRef> {
+        let ty = self.infer_expr_inner(expr)?;
+        self.result.type_of_expr.insert(*expr, ty.clone());
+        Some(ty)
+    }
+
+    fn infer_expr_inner(&mut self, expr: &ExprId) -> Option<TypeRef> {
         match &self.body[*expr] {
             Expr::Block { id: _, statements } => {
                 let g = self
@@ -264,17 +336,53 @@ impl InferenceContext<'_> {
                 self.infer_expr(value)
             }
             Expr::New { name, args } => {
-                for arg in args.iter() {
-                    self.infer_expr(arg);
+                let arg_tys = args.iter().map(|arg| self.infer_expr(arg)).collect::<Vec<_>>();
+                let ty = self.infer_constructor(expr, name);
+                if let Some(constructor) = self.result.method_resolution(*expr) {
+                    self.check_args(constructor, args, &arg_tys);
                 }
-                self.infer_constructor(expr, name)
+                ty
             }
             Expr::FieldAccess { target, name } => self.infer_field_access(expr, target, name),
-            Expr::UnaryOp { operand, .. } => self.infer_expr(operand),
-            Expr::BinaryOp { lhs, rhs, .. } => {
-                let _ = self.infer_expr(lhs);
-                // Assume the type of the left-hand side is the same as the right-hand side.
-                self.infer_expr(rhs)
+            Expr::UnaryOp { operand, op } => {
+                let ty = self.infer_expr(operand);
+                match op {
+                    Some(TSKind::anon_BANG) => Some(TypeRef::Bool),
+                    _ => ty,
+                }
+            }
+            Expr::BinaryOp { lhs, rhs, op } => {
+                let lhs_ty = self.infer_expr(lhs);
+                let rhs_ty = self.infer_expr(rhs);
+                match op {
+                    Some(TSKind::anon_EQ) => {
+                        if let (Some(expected), Some(actual)) = (&lhs_ty, &rhs_ty) {
+                            if !self.is_coercible(expected, actual) {
+                                self.result.diagnostics.push(
+                                    InferenceDiagnostic::MismatchedAssignmentType {
+                                        expr: *rhs,
+                                        expected: expected.clone(),
+                                        actual: actual.clone(),
+                                    },
+                                );
+                            }
+                        }
+                        lhs_ty
+                    }
+                    Some(
+                        TSKind::anon_EQ_EQ
+                        | TSKind::anon_BANG_EQ
+                        | TSKind::anon_LT
+                        | TSKind::anon_LT_EQ
+                        | TSKind::anon_GT
+                        | TSKind::anon_GT_EQ
+                        | TSKind::anon_AMP_AMP
+                        | TSKind::anon_PIPE_PIPE,
+                    ) => Some(TypeRef::Bool),
+                    // Assume the type of the left-hand side is the same as the right-hand side,
+                    // unless one of them is a float.
+                    _ => widen_float(lhs_ty, rhs_ty),
+                }
             }
             Expr::TernaryOp {
                 condition,
@@ -282,9 +390,11 @@ impl InferenceContext<'_> {
                 else_branch,
             } => {
                 self.infer_expr(condition);
-                let _ = self.infer_expr(then_branch);
-                // Assume the type of the then branch is the same as the else branch.
-                self.infer_expr(else_branch)
+                let then_ty = self.infer_expr(then_branch);
+                // Assume the type of the then branch is the same as the else branch,
+                // unless one of them is a float.
+                let else_ty = self.infer_expr(else_branch);
+                widen_float(then_ty, else_ty)
             }
             Expr::ScopeAccess { scope, field } => self.infer_field_access(expr, scope, field),
             Expr::ArrayIndexedAccess { array, index } => {
@@ -313,15 +423,40 @@ impl InferenceContext<'_> {
                 };
                 Some(ty)
             }
-            Expr::Control { operand, .. } => {
-                if let Some(operand) = operand {
-                    self.infer_expr(operand);
+            Expr::Control { keyword, operand } => {
+                let Some(operand) = operand else {
+                    return None;
+                };
+                let ty = self.infer_expr(operand);
+                if *keyword != TSKind::anon_return_ {
+                    return None;
+                }
+                if let (Some(expected), Some(actual)) = (self.return_ty.clone(), ty) {
+                    if !self.is_coercible(&expected, &actual) {
+                        self.result
+                            .diagnostics
+                            .push(InferenceDiagnostic::MismatchedReturnType {
+                                expr: *operand,
+                                expected,
+                                actual,
+                            });
+                    }
                 }
                 None
             }
             Expr::Ident(name) => {
-                let name: String = name.clone().into();
-                let res = self.resolver.resolve_ident(&name)?; // TODO: Should we emit a diagnostic here?
+                let name_str: String = name.clone().into();
+                let Some(res) = self.resolver.resolve_ident(&name_str) else {
+                    if !is_builtin(&name_str) {
+                        self.result
+                            .diagnostics
+                            .push(InferenceDiagnostic::UnresolvedIdent {
+                                expr: *expr,
+                                name: name.clone(),
+                            });
+                    }
+                    return None;
+                };
                 match &res {
                     ValueNs::GlobalId(it) => {
                         let item_tree = self.db.file_item_tree(it.file_id);
@@ -347,6 +482,18 @@ impl InferenceContext<'_> {
                         TypeRef::Name(item_tree[it.value.lookup(self.db).id].name.clone()).into()
                     }
                     ValueNs::FunctionId(it) => {
+                        // A function can have several declarations, e.g. a forward and its
+                        // implementation, it is deprecated if any of them is.
+                        if let Some(function) = it
+                            .iter()
+                            .find(|it| self.db.function_data(it.value).deprecation.is_some())
+                        {
+                            self.check_deprecated(*expr, function.value);
+                        }
+                        if self.current_call().map(|call| call.expr) != Some(*expr) {
+                            // This is a reference to a function, e.g. a callback, not a call.
+                            return None;
+                        }
                         if let Some(current_call) = self.current_call_mut() {
                             if current_call.id.is_none() {
                                 current_call.id = Some(res.clone());
@@ -389,8 +536,9 @@ impl InferenceContext<'_> {
             } => {
                 self.push_call(*target);
                 let ty = self.infer_method_call(expr, target, method_name);
-                for arg in args.iter() {
-                    self.infer_expr(arg);
+                let arg_tys = args.iter().map(|arg| self.infer_expr(arg)).collect::<Vec<_>>();
+                if let Some(method) = self.result.method_resolution(*expr) {
+                    self.check_args(method, args, &arg_tys);
                 }
                 self.pop_call();
                 ty
@@ -398,8 +546,10 @@ impl InferenceContext<'_> {
             Expr::Call { callee, args } => {
                 self.push_call(*callee);
                 let ty = self.infer_expr(callee);
-                for arg in args.iter() {
-                    self.infer_expr(arg);
+                let arg_tys = args.iter().map(|arg| self.infer_expr(arg)).collect::<Vec<_>>();
+                if let Some(function) = self.current_call_fn() {
+                    self.result.call_resolutions.insert(*expr, function);
+                    self.check_args(function, args, &arg_tys);
                 }
                 if let Some((min, max)) = self.current_call_params_numbers() {
                     if args.len() < min || args.len() > max.unwrap_or(usize::MAX) {
@@ -432,7 +582,18 @@ impl InferenceContext<'_> {
                 ..
             } => {
                 if let Some(initializer) = initializer {
-                    self.infer_expr(initializer);
+                    let ty = self.infer_expr(initializer);
+                    if let (Some(expected), Some(actual)) = (type_ref, ty) {
+                        if !self.is_coercible(expected, &actual) {
+                            self.result.diagnostics.push(
+                                InferenceDiagnostic::MismatchedAssignmentType {
+                                    expr: *initializer,
+                                    expected: expected.clone(),
+                                    actual,
+                                },
+                            );
+                        }
+                    }
                 }
                 type_ref.as_ref().cloned()
             }
@@ -440,7 +601,8 @@ impl InferenceContext<'_> {
         }
     }
 
-    pub(crate) fn collect_fn(&mut self, _func: FunctionId) {
+    pub(crate) fn collect_fn(&mut self, func: FunctionId) {
+        self.return_ty = self.db.function_data(func).type_ref();
         if let Some(id) = self.body.body_expr {
             self.infer_expr(&id);
         }
@@ -463,6 +625,7 @@ impl InferenceContext<'_> {
                 let data = self.db.methodmap_data(it.value);
                 if let Some(constructor_id) = data.constructor() {
                     self.result.method_resolutions.insert(*expr, constructor_id);
+                    self.check_deprecated(*expr, constructor_id);
                     TypeRef::Name(name.clone()).into()
                 } else {
                     self.result
@@ -536,6 +699,15 @@ impl InferenceContext<'_> {
                             self.result
                                 .attribute_resolutions
                                 .insert(*receiver, property_data.id.into());
+                            if let Some(note) = &property_data.deprecation {
+                                self.result
+                                    .diagnostics
+                                    .push(InferenceDiagnostic::Deprecated {
+                                        expr: *receiver,
+                                        name: name.clone(),
+                                        note: note.clone(),
+                                    });
+                            }
                             let property = property_data.id.lookup(self.db);
                             let item_tree = property.id.item_tree(self.db);
                             return Some(item_tree[property.id.value].type_ref.clone());
@@ -599,6 +771,7 @@ impl InferenceContext<'_> {
                         }
                         EnumStructItemData::Method(method) => {
                             self.result.method_resolutions.insert(*receiver, *method);
+                            self.check_deprecated(*receiver, *method);
                             if let Some(current_call) = self.current_call_mut() {
                                 if current_call.id.is_none() {
                                     let res = InFile::new(it.file_id, *method);
@@ -631,6 +804,7 @@ impl InferenceContext<'_> {
                         | MethodmapItemData::Constructor(method)
                         | MethodmapItemData::Destructor(method) => {
                             self.result.method_resolutions.insert(*receiver, *method);
+                            self.check_deprecated(*receiver, *method);
                             let function = method.lookup(self.db);
                             let item_tree = function.id.item_tree(self.db);
                             return item_tree[function.id.value].ret_type.clone();
@@ -652,3 +826,145 @@ impl InferenceContext<'_> {
         None
     }
 }
+
+impl InferenceContext<'_> {
+    /// Report the use of `function` by `expr` if the function is deprecated.
+    fn check_deprecated(&mut self, expr: ExprId, function: FunctionId) {
+        let data = self.db.function_data(function);
+        if let Some(note) = &data.deprecation {
+            self.result
+                .diagnostics
+                .push(InferenceDiagnostic::Deprecated {
+                    expr,
+                    name: data.name.clone(),
+                    note: note.clone(),
+                });
+        }
+    }
+
+    /// Check the types of the positional arguments of a call against the parameters of the callee.
+    fn check_args(&mut self, callee: FunctionId, args: &[ExprId], arg_tys: &[Option<TypeRef>]) {
+        let data = self.db.function_data(callee);
+        for (index, ((arg, arg_ty), param)) in args
+            .iter()
+            .zip(arg_tys)
+            .zip(data.params())
+            .enumerate()
+        {
+            // Named arguments can be passed in any order, and the rest parameter accepts anything.
+            if matches!(self.body[*arg], Expr::NamedArg { .. }) || param.is_rest {
+                break;
+            }
+            let (Some(expected), Some(actual)) = (&param.type_ref, arg_ty) else {
+                continue;
+            };
+            if !self.is_coercible(expected, actual) {
+                self.result
+                    .diagnostics
+                    .push(InferenceDiagnostic::MismatchedArgType {
+                        expr: *arg,
+                        name: data.name(),
+                        index,
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    });
+            }
+        }
+    }
+
+    /// Whether a value of type `actual` can be used where a value of type `expected` is expected,
+    /// following the tag rules of SourcePawn.
+    ///
+    /// `int`, `bool` and `char` are interchangeable: spcomp treats `bool` and `char` as weak tags,
+    /// which accept an untagged value and coerce to one, e.g. `bool b = 1;` compiles without a
+    /// warning. `float` is a strong tag and is not interchangeable. Methodmaps and enum structs
+    /// only accept themselves, and the methodmaps that inherit from them. Types that are not
+    /// inferred precisely enough yet, like arrays, enums and function types, are always accepted.
+    fn is_coercible(&self, expected: &TypeRef, actual: &TypeRef) -> bool {
+        let expected = normalize_tag(expected.clone());
+        let actual = normalize_tag(actual.clone());
+        if expected == actual {
+            return true;
+        }
+        match (&expected, &actual) {
+            (TypeRef::Any | TypeRef::Void | TypeRef::Array(_), _)
+            | (_, TypeRef::Any | TypeRef::Void | TypeRef::Array(_)) => true,
+            (
+                TypeRef::Int | TypeRef::Bool | TypeRef::Char,
+                TypeRef::Int | TypeRef::Bool | TypeRef::Char,
+            ) => true,
+            (TypeRef::Name(expected), TypeRef::Name(actual)) => {
+                !self.is_strict_type(expected)
+                    || !self.is_strict_type(actual)
+                    || self.inherits_from(actual, expected)
+            }
+            (TypeRef::Name(name), _) | (_, TypeRef::Name(name)) => !self.is_strict_type(name),
+            _ => false,
+        }
+    }
+
+    /// Whether the type is a methodmap or an enum struct, which cannot be used in place of
+    /// another type without a `view_as`.
+    fn is_strict_type(&self, name: &Name) -> bool {
+        matches!(
+            self.resolver.resolve_ident(&name.to_string()),
+            Some(ValueNs::MethodmapId(_) | ValueNs::EnumStructId(_))
+        )
+    }
+
+    /// Whether the methodmap `name` is `ancestor`, or inherits from it.
+    fn inherits_from(&self, name: &Name, ancestor: &Name) -> bool {
+        let mut name = name.clone();
+        let mut seen = FxHashSet::default();
+        while seen.insert(name.clone()) {
+            if &name == ancestor {
+                return true;
+            }
+            let Some(ValueNs::MethodmapId(it)) = self.resolver.resolve_ident(&name.to_string())
+            else {
+                return false;
+            };
+            let item_tree = self.db.file_item_tree(it.file_id);
+            let Some(inherits) = item_tree[it.value.lookup(self.db).id].inherits.clone() else {
+                return false;
+            };
+            name = inherits;
+        }
+
+        false
+    }
+}
+
+/// Convert the legacy tags (`Float:`, `String:`, `_:`, ...) to their new-style type.
+fn normalize_tag(type_ref: TypeRef) -> TypeRef {
+    match type_ref {
+        TypeRef::OldFloat => TypeRef::Float,
+        TypeRef::OldString => TypeRef::Char,
+        TypeRef::OldName(name) => match name.to_string().as_str() {
+            "_" => TypeRef::Int,
+            "bool" => TypeRef::Bool,
+            "Float" | "float" => TypeRef::Float,
+            "String" => TypeRef::Char,
+            "any" => TypeRef::Any,
+            _ => TypeRef::Name(name),
+        },
+        type_ref => type_ref,
+    }
+}
+
+/// The type of an arithmetic expression: a float if either operand is a float, as the float
+/// operators of spcomp accept an `int` on either side, otherwise the type of the left-hand side,
+/// like spcomp which gives the tag of the left operand to the result.
+///
+/// The tags of the operands are not checked against each other: spcomp only warns about
+/// arithmetic on mismatched tags, and enums are not inferred precisely enough to do the same.
+fn widen_float(lhs: Option<TypeRef>, rhs: Option<TypeRef>) -> Option<TypeRef> {
+    let is_float = |ty: &Option<TypeRef>| {
+        matches!(ty.clone().map(normalize_tag), Some(TypeRef::Float))
+    };
+    if is_float(&lhs) || is_float(&rhs) {
+        return Some(TypeRef::Float);
+    }
+
+    lhs
+}
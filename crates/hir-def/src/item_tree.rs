@@ -268,6 +268,8 @@ pub struct Function {
     pub params: IdxRange<Param>,
     pub special: Option<SpecialMethod>,
     pub ret_type: Option<TypeRef>,
+    /// The note of the `#pragma deprecated` directive preceding the function, if any.
+    pub deprecation: Option<SmolStr>,
     pub ast_id: AstId,
 }
 
@@ -342,6 +344,8 @@ pub struct Property {
     pub name: Name,
     pub getters_setters: IdxRange<Function>,
     pub type_ref: TypeRef,
+    /// The note of the `#pragma deprecated` directive preceding the property, if any.
+    pub deprecation: Option<SmolStr>,
     pub ast_id: AstId,
 }
 
@@ -2,7 +2,8 @@ use std::sync::Arc;
 
 use la_arena::{Idx, IdxRange, RawIdx};
 use lazy_static::lazy_static;
-use syntax::TSKind;
+use smol_str::SmolStr;
+use syntax::{utils::deprecation_note, TSKind};
 use tree_sitter::QueryCursor;
 use vfs::FileId;
 
@@ -131,6 +132,10 @@ impl<'db> Ctx<'db> {
         TypeRef::from_returntype_node(node, "returnType", &self.source)
     }
 
+    fn deprecation(&self, node: &tree_sitter::Node) -> Option<SmolStr> {
+        deprecation_note(*node, self.source.as_bytes()).map(SmolStr::from)
+    }
+
     fn lower_enum(&mut self, node: &tree_sitter::Node) {
         let start_idx = self.next_variant_idx();
         if let Some(entries_node) = node.child_by_field_name("entries") {
@@ -224,6 +229,7 @@ impl<'db> Ctx<'db> {
             visibility,
             params,
             special,
+            deprecation: self.deprecation(node),
             ast_id: self.source_ast_id_map.ast_id_of(node),
         };
 
@@ -412,6 +418,7 @@ impl<'db> Ctx<'db> {
                         ),
                         getters_setters: IdxRange::new(start_idx..end_idx),
                         type_ref: type_,
+                        deprecation: self.deprecation(&e),
                         ast_id: self.source_ast_id_map.ast_id_of(&e),
                     };
                     let property_idx = self.tree.data_mut().properties.alloc(res);
@@ -477,6 +484,7 @@ impl<'db> Ctx<'db> {
                     visibility: RawVisibilityId::PUBLIC,
                     params: IdxRange::new(idx..idx),
                     special: None,
+                    deprecation: None,
                     ast_id: self.source_ast_id_map.ast_id_of(parent),
                 };
                 self.tree.data_mut().functions.alloc(res);
@@ -503,6 +511,7 @@ impl<'db> Ctx<'db> {
                     visibility: RawVisibilityId::NONE,
                     params: IdxRange::new(start_idx..end_idx),
                     special: None,
+                    deprecation: None,
                     ast_id: self.source_ast_id_map.ast_id_of(parent), // We care about the method itself, not the getter/setter in the grammar.
                 };
                 self.tree.data_mut().functions.alloc(res);
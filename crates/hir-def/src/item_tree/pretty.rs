@@ -178,6 +178,7 @@ impl<'a> Printer<'a> {
                         type_ref,
                         getters_setters,
                         ast_id,
+                        ..
                     } = &self.tree[property_idx];
                     self.push(format!("// {}", ast_id).as_str());
                     self.newline();
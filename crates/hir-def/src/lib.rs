@@ -19,18 +19,20 @@ pub mod dyn_map;
 mod hir;
 mod infer;
 mod item_tree;
+mod references;
 pub mod resolver;
 pub mod src;
 
 pub use ast_id_map::NodePtr;
-pub use data::PropertyItem;
+pub use data::{EnumStructItemData, MethodmapItemData, ParamData, PropertyItem};
 pub use db::resolve_include_node;
 pub use db::DefDatabase;
 pub use db::{BlockDefMapQuery, BlockItemTreeQuery, BodyQuery, FileDefMapQuery, FileItemTreeQuery};
 pub use diagnostics::DefDiagnostic;
-pub use hir::ExprId;
+pub use hir::{type_ref::TypeRef, Expr, ExprId};
 pub use infer::{AttributeId, ConstructorDiagnosticKind, InferenceDiagnostic, InferenceResult};
-pub use item_tree::{print_item_tree, FileItem, Name};
+pub use item_tree::{print_item_tree, FileItem, FunctionKind, Name, RawVisibilityId};
+pub use references::FileReferences;
 
 trait Intern {
     type ID;
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use fxhash::FxHashSet;
+use syntax::TSKind;
+use vfs::FileId;
+
+use crate::{item_tree::RawVisibilityId, DefDatabase, Name};
+
+/// The names referenced in a file, and the files of the macros expanded in it.
+///
+/// This is purely name based: a name is considered referenced if an identifier with the same text
+/// appears anywhere in the file, outside of the name of a declaration. Usages are not resolved,
+/// so a local that shadows a global marks the global as used. This is conservative, which is
+/// what the unused lints need: they can miss an unused declaration, but never flag a used one.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FileReferences {
+    names: FxHashSet<Name>,
+    macro_files: FxHashSet<FileId>,
+}
+
+impl FileReferences {
+    pub(crate) fn file_references_query(db: &dyn DefDatabase, file_id: FileId) -> Arc<Self> {
+        let tree = db.parse(file_id);
+        let source = db.preprocessed_text(file_id);
+        let mut res = FileReferences::default();
+        res.collect_names(tree.root_node(), source.as_bytes());
+        res.macro_files = db
+            .preprocess_file(file_id)
+            .offsets()
+            .values()
+            .flatten()
+            .map(|offset| offset.file_id)
+            .collect();
+        res.names.shrink_to_fit();
+
+        Arc::new(res)
+    }
+
+    /// Whether an identifier with this name appears in the file.
+    pub fn contains(&self, name: &Name) -> bool {
+        self.names.contains(name)
+    }
+
+    /// Whether a macro declared in `file_id` is expanded in the file.
+    pub fn expands_macro_from(&self, file_id: FileId) -> bool {
+        self.macro_files.contains(&file_id)
+    }
+
+    fn collect_names(&mut self, node: tree_sitter::Node, source: &[u8]) {
+        if TSKind::from(node) == TSKind::identifier && !is_declaration_name(node) {
+            if let Ok(text) = node.utf8_text(source) {
+                self.names.insert(Name::from(text));
+            }
+        }
+        for child in node.children(&mut node.walk()) {
+            self.collect_names(child, source);
+        }
+    }
+}
+
+/// Whether the node is the name of the declaration it belongs to.
+///
+/// The name of a public function is not considered as a declaration, as it implements a forward.
+fn is_declaration_name(node: tree_sitter::Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    if parent.child_by_field_name("name") != Some(node) {
+        return false;
+    }
+    !(TSKind::from(parent) == TSKind::function_definition
+        && RawVisibilityId::from_node(&parent).contains(RawVisibilityId::PUBLIC))
+}
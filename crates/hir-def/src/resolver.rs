@@ -125,43 +125,10 @@ impl Resolver {
                     entries.dedup(); // FIXME: Use a HashSet instead of Vec
                     match entries.len() {
                         0 => continue,
-                        1 => match *entries.first().unwrap() {
-                            (FileDefId::FunctionId(it), file_id) => {
-                                let mut fn_ids: SmallVec<[InFile<FunctionId>; 1]> = SmallVec::new();
-                                fn_ids.push(InFile::new(file_id, it));
-                                return Some(ValueNs::FunctionId(fn_ids));
-                            }
-                            (FileDefId::MacroId(it), file_id) => {
-                                return Some(ValueNs::MacroId(InFile::new(file_id, it)));
-                            }
-                            (FileDefId::GlobalId(it), file_id) => {
-                                return Some(ValueNs::GlobalId(InFile::new(file_id, it)));
-                            }
-                            (FileDefId::EnumStructId(it), file_id) => {
-                                return Some(ValueNs::EnumStructId(InFile::new(file_id, it)));
-                            }
-                            (FileDefId::MethodmapId(it), file_id) => {
-                                return Some(ValueNs::MethodmapId(InFile::new(file_id, it)));
-                            }
-                            (FileDefId::EnumId(it), file_id) => {
-                                return Some(ValueNs::EnumId(InFile::new(file_id, it)));
-                            }
-                            (FileDefId::VariantId(it), file_id) => {
-                                return Some(ValueNs::VariantId(InFile::new(file_id, it)));
-                            }
-                            (FileDefId::TypedefId(it), file_id) => {
-                                return Some(ValueNs::TypedefId(InFile::new(file_id, it)));
-                            }
-                            (FileDefId::TypesetId(it), file_id) => {
-                                return Some(ValueNs::TypesetId(InFile::new(file_id, it)));
-                            }
-                            (FileDefId::FunctagId(it), file_id) => {
-                                return Some(ValueNs::FunctagId(InFile::new(file_id, it)));
-                            }
-                            (FileDefId::FuncenumId(it), file_id) => {
-                                return Some(ValueNs::FuncenumId(InFile::new(file_id, it)));
-                            }
-                        },
+                        1 => {
+                            let (entry, file_id) = *entries.first().unwrap();
+                            return Some(ValueNs::from_file_def(entry, file_id));
+                        }
                         _ => {
                             // Handle enum methodmaps by returning the methodmap id in priority if it exists
                             if let Some((FileDefId::MethodmapId(it), file_id)) = entries
@@ -186,6 +153,47 @@ impl Resolver {
         None
     }
 
+    /// Call `f` for every name visible from this resolver, inner-most scopes first.
+    ///
+    /// A name can be reported more than once if it is shadowed, callers are expected to
+    /// only keep the first occurence.
+    pub fn process_all_names(&self, f: &mut dyn FnMut(Name, ValueNs)) {
+        for scope in self.scopes() {
+            match scope {
+                Scope::Expr(scope) => {
+                    for scope_id in scope.expr_scopes.scope_chain(Some(scope.scope_id)) {
+                        for (name, expr_id) in scope.expr_scopes.entries(scope_id) {
+                            f(name.clone(), ValueNs::LocalId((scope.owner, expr_id)));
+                        }
+                    }
+                }
+                Scope::This(adt_id) => {
+                    let value = match adt_id {
+                        AdtId::EnumStructId(it) => {
+                            ValueNs::EnumStructId(InFile::new(self.file_id, *it))
+                        }
+                        AdtId::MethodmapId(it) => {
+                            ValueNs::MethodmapId(InFile::new(self.file_id, *it))
+                        }
+                    };
+                    f(Name::from("this"), value);
+                }
+                Scope::Global(def_maps) => {
+                    for def_map in def_maps {
+                        for (name, entries) in def_map.entries() {
+                            for entry in entries {
+                                f(
+                                    name.clone(),
+                                    ValueNs::from_file_def(*entry, def_map.file_id()),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// `expr_id` is required to be an expression id that comes after the top level expression scope in the given resolver
     #[must_use]
     pub fn update_to_inner_scope(
@@ -274,6 +282,28 @@ pub enum ValueNs {
     FuncenumId(InFile<FuncenumId>),
 }
 
+impl ValueNs {
+    fn from_file_def(def: FileDefId, file_id: FileId) -> Self {
+        match def {
+            FileDefId::FunctionId(it) => {
+                let mut fn_ids: SmallVec<[InFile<FunctionId>; 1]> = SmallVec::new();
+                fn_ids.push(InFile::new(file_id, it));
+                ValueNs::FunctionId(fn_ids)
+            }
+            FileDefId::MacroId(it) => ValueNs::MacroId(InFile::new(file_id, it)),
+            FileDefId::GlobalId(it) => ValueNs::GlobalId(InFile::new(file_id, it)),
+            FileDefId::EnumStructId(it) => ValueNs::EnumStructId(InFile::new(file_id, it)),
+            FileDefId::MethodmapId(it) => ValueNs::MethodmapId(InFile::new(file_id, it)),
+            FileDefId::EnumId(it) => ValueNs::EnumId(InFile::new(file_id, it)),
+            FileDefId::VariantId(it) => ValueNs::VariantId(InFile::new(file_id, it)),
+            FileDefId::TypedefId(it) => ValueNs::TypedefId(InFile::new(file_id, it)),
+            FileDefId::TypesetId(it) => ValueNs::TypesetId(InFile::new(file_id, it)),
+            FileDefId::FunctagId(it) => ValueNs::FunctagId(InFile::new(file_id, it)),
+            FileDefId::FuncenumId(it) => ValueNs::FuncenumId(InFile::new(file_id, it)),
+        }
+    }
+}
+
 pub trait HasResolver: Copy {
     /// Builds a resolver for type references inside this def.
     fn resolver(self, db: &dyn DefDatabase) -> Resolver;
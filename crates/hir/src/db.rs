@@ -1,5 +1,17 @@
+use std::sync::Arc;
+
 use base_db::Upcast;
-use hir_def::DefDatabase;
+use fxhash::FxHashSet;
+use hir_def::{DefDatabase, Name};
+use vfs::FileId;
 
 #[salsa::query_group(HirDatabaseStorage)]
-pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {}
+pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {
+    /// The file and all the files it includes, directly or not.
+    #[salsa::invoke(crate::include_closure_query)]
+    fn include_closure(&self, file_id: FileId) -> Arc<FxHashSet<FileId>>;
+
+    /// The names declared in the file and in all the files it includes, directly or not.
+    #[salsa::invoke(crate::include_closure_names_query)]
+    fn include_closure_names(&self, file_id: FileId) -> Arc<FxHashSet<Name>>;
+}
@@ -4,7 +4,8 @@
 //! This probably isn't the best way to do this -- ideally, diagnostics should
 //! be expressed in terms of hir types themselves.
 
-use hir_def::{InFile, Name, NodePtr};
+use hir_def::{InFile, Name, NodePtr, TypeRef};
+use vfs::FileId;
 
 macro_rules! diagnostics {
     ($($diag:ident,)*) => {
@@ -29,15 +30,29 @@ diagnostics![
     UnresolvedMethodCall,
     UnresolvedConstructor,
     UnresolvedNamedArg,
+    UnresolvedIdent,
     IncorrectNumberOfArguments,
+    MismatchedAssignmentType,
+    MismatchedArgType,
+    MismatchedReturnType,
     UnresolvedInherit,
     PreprocessorEvaluationError,
+    ErrorDirective,
+    WarningDirective,
+    FailedAssertion,
     UnresolvedMacro,
     InactiveCode,
+    UnusedVariable,
+    UnusedParameter,
+    UnusedGlobal,
+    UnusedFunction,
+    UnusedInclude,
+    Deprecated,
 ];
 
 #[derive(Debug)]
 pub struct UnresolvedInclude {
+    pub file_id: FileId,
     pub range: lsp_types::Range,
     pub path: String,
 }
@@ -78,6 +93,12 @@ pub struct UnresolvedNamedArg {
     pub callee: Name,
 }
 
+#[derive(Debug)]
+pub struct UnresolvedIdent {
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+}
+
 #[derive(Debug)]
 pub struct IncorrectNumberOfArguments {
     pub expr: InFile<NodePtr>,
@@ -86,6 +107,29 @@ pub struct IncorrectNumberOfArguments {
     pub actual: usize,
 }
 
+#[derive(Debug)]
+pub struct MismatchedAssignmentType {
+    pub expr: InFile<NodePtr>,
+    pub expected: TypeRef,
+    pub actual: TypeRef,
+}
+
+#[derive(Debug)]
+pub struct MismatchedArgType {
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+    pub index: usize,
+    pub expected: TypeRef,
+    pub actual: TypeRef,
+}
+
+#[derive(Debug)]
+pub struct MismatchedReturnType {
+    pub expr: InFile<NodePtr>,
+    pub expected: TypeRef,
+    pub actual: TypeRef,
+}
+
 #[derive(Debug)]
 pub struct UnresolvedInherit {
     pub expr: InFile<NodePtr>,
@@ -99,6 +143,24 @@ pub struct PreprocessorEvaluationError {
     pub text: String,
 }
 
+#[derive(Debug)]
+pub struct ErrorDirective {
+    pub range: lsp_types::Range,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct WarningDirective {
+    pub range: lsp_types::Range,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct FailedAssertion {
+    pub range: lsp_types::Range,
+    pub text: String,
+}
+
 #[derive(Debug)]
 pub struct UnresolvedMacro {
     pub range: lsp_types::Range,
@@ -109,3 +171,41 @@ pub struct UnresolvedMacro {
 pub struct InactiveCode {
     pub range: lsp_types::Range,
 }
+
+#[derive(Debug)]
+pub struct UnusedVariable {
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+}
+
+#[derive(Debug)]
+pub struct UnusedParameter {
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+}
+
+#[derive(Debug)]
+pub struct UnusedGlobal {
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+}
+
+#[derive(Debug)]
+pub struct UnusedFunction {
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+}
+
+#[derive(Debug)]
+pub struct UnusedInclude {
+    pub file_id: FileId,
+    pub range: lsp_types::Range,
+    pub path: String,
+}
+
+#[derive(Debug)]
+pub struct Deprecated {
+    pub expr: InFile<NodePtr>,
+    pub name: Name,
+    pub note: String,
+}
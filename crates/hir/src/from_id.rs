@@ -1,6 +1,6 @@
 use hir_def::{AttributeId, DefWithBodyId, ExprId, FieldId, FileDefId};
 
-use crate::{Attribute, DefWithBody, Field, FileDef};
+use crate::{Attribute, DefResolution, DefWithBody, Field, FileDef};
 
 macro_rules! from_id {
     ($(($id:path, $ty:path)),* $(,)?) => {$(
@@ -120,3 +120,21 @@ impl From<FileDef> for FileDefId {
         }
     }
 }
+
+impl From<FileDef> for DefResolution {
+    fn from(def: FileDef) -> Self {
+        match def {
+            FileDef::Function(it) => DefResolution::Function(it),
+            FileDef::Macro(it) => DefResolution::Macro(it),
+            FileDef::EnumStruct(it) => DefResolution::EnumStruct(it),
+            FileDef::Methodmap(it) => DefResolution::Methodmap(it),
+            FileDef::Global(it) => DefResolution::Global(it),
+            FileDef::Enum(it) => DefResolution::Enum(it),
+            FileDef::Variant(it) => DefResolution::Variant(it),
+            FileDef::Typedef(it) => DefResolution::Typedef(it),
+            FileDef::Typeset(it) => DefResolution::Typeset(it),
+            FileDef::Functag(it) => DefResolution::Functag(it),
+            FileDef::Funcenum(it) => DefResolution::Funcenum(it),
+        }
+    }
+}
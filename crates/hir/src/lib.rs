@@ -1,12 +1,17 @@
-use base_db::Tree;
+use std::sync::Arc;
+
+use base_db::{FileExtension, Tree};
 use db::HirDatabase;
+use fxhash::FxHashSet;
 use hir_def::{
-    resolver::ValueNs, DefDiagnostic, DefWithBodyId, EnumId, EnumStructId, ExprId, FuncenumId,
-    FunctagId, FunctionId, GlobalId, InFile, InferenceDiagnostic, LocalFieldId, Lookup, MacroId,
-    MethodmapId, Name, NodePtr, PropertyId, TypedefId, TypesetId, VariantId,
+    resolve_include_node, resolver::ValueNs, DefDiagnostic, DefWithBodyId, EnumId, EnumStructId,
+    EnumStructItemData, Expr, ExprId, FuncenumId, FunctagId, FunctionId, GlobalId, InFile,
+    InferenceDiagnostic, LocalFieldId, Lookup, MacroId, MethodmapId, MethodmapItemData, Name,
+    NodePtr, PropertyId, TypedefId, TypesetId, VariantId,
 };
 use preprocessor::PreprocessorError;
 use stdx::impl_from;
+use syntax::{utils::point_to_lsp_position, TSKind};
 use vfs::FileId;
 
 pub mod db;
@@ -17,9 +22,14 @@ mod semantics;
 mod source_analyzer;
 mod source_to_def;
 
-pub use crate::{diagnostics::*, has_source::HasSource, semantics::Semantics};
+pub use crate::{
+    diagnostics::*,
+    has_source::HasSource,
+    semantics::{Semantics, SemanticsScope},
+};
+pub use hir_def::{FunctionKind, ParamData, RawVisibilityId, TypeRef};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DefResolution {
     Function(Function),
     Macro(Macro),
@@ -123,9 +133,54 @@ impl DefResolution {
             DefResolution::File(it) => it.id,
         }
     }
+
+    /// The name of the definition, if it has one.
+    pub fn name(&self, db: &dyn HirDatabase) -> Option<Name> {
+        let name = match self {
+            DefResolution::Function(it) => it.name(db),
+            DefResolution::Macro(it) => it.name(db),
+            DefResolution::EnumStruct(it) => it.name(db),
+            DefResolution::Methodmap(it) => it.name(db),
+            DefResolution::Property(it) => it.name(db),
+            DefResolution::Enum(it) => it.name(db),
+            DefResolution::Variant(it) => it.name(db),
+            DefResolution::Typedef(it) => it.name(db)?,
+            DefResolution::Typeset(it) => it.name(db),
+            DefResolution::Functag(it) => it.name(db)?,
+            DefResolution::Funcenum(it) => it.name(db),
+            DefResolution::Field(it) => it.name(db)?,
+            DefResolution::Global(it) => it.name(db),
+            DefResolution::Local(_) | DefResolution::File(_) => return None,
+        };
+
+        Some(name)
+    }
+
+    /// The type of the value this definition evaluates to, if any.
+    ///
+    /// Enum structs and methodmaps evaluate to themselves, to handle `this` and static accesses.
+    pub fn type_ref(&self, db: &dyn HirDatabase) -> Option<TypeRef> {
+        match self {
+            DefResolution::Function(it) => it.type_ref(db),
+            DefResolution::EnumStruct(it) => Some(TypeRef::Name(it.name(db))),
+            DefResolution::Methodmap(it) => Some(TypeRef::Name(it.name(db))),
+            DefResolution::Property(it) => Some(it.type_ref(db)),
+            DefResolution::Field(it) => it.type_ref(db),
+            DefResolution::Global(it) => it.type_ref(db),
+            DefResolution::Local(it) => it.type_ref(db),
+            DefResolution::Macro(_)
+            | DefResolution::Enum(_)
+            | DefResolution::Variant(_)
+            | DefResolution::Typedef(_)
+            | DefResolution::Typeset(_)
+            | DefResolution::Functag(_)
+            | DefResolution::Funcenum(_)
+            | DefResolution::File(_) => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct File {
     pub(crate) id: FileId,
 }
@@ -147,6 +202,19 @@ impl File {
             .collect::<Vec<_>>()
     }
 
+    /// Whether all the includes of the project of the file could be resolved.
+    ///
+    /// Returns `false` if the file is not part of a project.
+    pub fn includes_resolved(self, db: &dyn HirDatabase) -> bool {
+        let Some(subgraph) = db.projet_subgraph(self.id) else {
+            return false;
+        };
+        subgraph
+            .nodes
+            .iter()
+            .all(|node| db.file_includes(node.file_id).1.is_empty())
+    }
+
     pub fn diagnostics(self, db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>) {
         let result = db.preprocess_file(self.id);
         let errors = result.errors();
@@ -162,12 +230,40 @@ impl File {
         acc.extend(errors.unresolved_include_errors.iter().map(|it| {
             AnyDiagnostic::UnresolvedInclude(
                 UnresolvedInclude {
+                    file_id: self.id,
                     range: *it.range(),
                     path: it.text().to_owned(),
                 }
                 .into(),
             )
         }));
+        acc.extend(errors.error_directives.iter().map(|it| {
+            AnyDiagnostic::ErrorDirective(
+                ErrorDirective {
+                    range: *it.range(),
+                    text: it.text().to_owned(),
+                }
+                .into(),
+            )
+        }));
+        acc.extend(errors.warning_directives.iter().map(|it| {
+            AnyDiagnostic::WarningDirective(
+                WarningDirective {
+                    range: *it.range(),
+                    text: it.text().to_owned(),
+                }
+                .into(),
+            )
+        }));
+        acc.extend(errors.assertion_errors.iter().map(|it| {
+            AnyDiagnostic::FailedAssertion(
+                FailedAssertion {
+                    range: *it.range(),
+                    text: it.text().to_owned(),
+                }
+                .into(),
+            )
+        }));
         acc.extend(errors.macro_not_found_errors.iter().map(|it| {
             AnyDiagnostic::UnresolvedMacro(
                 UnresolvedMacro {
@@ -183,10 +279,158 @@ impl File {
                 .iter()
                 .map(|range| AnyDiagnostic::InactiveCode(InactiveCode { range: *range }.into())),
         );
+        self.unused_declarations_diagnostics(db, acc);
+        self.unused_includes_diagnostics(db, acc);
         self.declarations(db)
             .iter()
             .for_each(|it| acc.extend(it.diagnostics(db)));
     }
+
+    /// Lints the `static` globals of the file which are never referenced in it, and the `stock`
+    /// functions of a plugin which are never referenced in its project.
+    fn unused_declarations_diagnostics(&self, db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>) {
+        let subgraph = db.projet_subgraph(self.id);
+        let is_plugin = subgraph.as_ref().is_some_and(|subgraph| {
+            subgraph
+                .nodes
+                .iter()
+                .any(|node| node.file_id == self.id && node.extension == FileExtension::Sp)
+        });
+        let references = db.file_references(self.id);
+        let tree = db.parse(self.id);
+        for def in File::from(self.id).declarations(db) {
+            match def {
+                FileDef::Global(it) if it.visibility(db).contains(RawVisibilityId::STATIC) => {
+                    let name = it.name(db);
+                    if references.contains(&name) {
+                        continue;
+                    }
+                    if let Some(expr) = name_node_ptr(db, it, &tree) {
+                        acc.push(UnusedGlobal { expr, name }.into());
+                    }
+                }
+                FileDef::Function(it)
+                    if is_plugin
+                        && it.kind(db) == FunctionKind::Def
+                        && it.visibility(db).contains(RawVisibilityId::STOCK) =>
+                {
+                    let name = it.name(db);
+                    let Some(subgraph) = subgraph.as_ref() else {
+                        continue;
+                    };
+                    if subgraph
+                        .nodes
+                        .iter()
+                        .any(|node| db.file_references(node.file_id).contains(&name))
+                    {
+                        continue;
+                    }
+                    if let Some(expr) = name_node_ptr(db, it, &tree) {
+                        acc.push(UnusedFunction { expr, name }.into());
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Lints the includes of `.inc` files of which no declaration or macro is used by the files
+    /// of the project that are not included by them.
+    fn unused_includes_diagnostics(&self, db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>) {
+        let Some(subgraph) = db.projet_subgraph(self.id) else {
+            return;
+        };
+        let tree = db.parse(self.id);
+        let source = db.preprocessed_text(self.id);
+        let root_node = tree.root_node();
+        for node in root_node.children(&mut root_node.walk()) {
+            if !matches!(TSKind::from(node), TSKind::preproc_include | TSKind::preproc_tryinclude) {
+                continue;
+            }
+            let Some((Some(target), .., FileExtension::Inc)) =
+                resolve_include_node(db.upcast(), self.id, &source, node)
+            else {
+                continue;
+            };
+            let included = db.include_closure(target);
+            let names = db.include_closure_names(target);
+            let used = subgraph
+                .nodes
+                .iter()
+                .filter(|node| !included.contains(&node.file_id))
+                .any(|node| {
+                    let references = db.file_references(node.file_id);
+                    included
+                        .iter()
+                        .any(|file_id| references.expands_macro_from(*file_id))
+                        || names.iter().any(|name| references.contains(name))
+                });
+            if used {
+                continue;
+            }
+            let Some(path_node) = node.child_by_field_name("path") else {
+                continue;
+            };
+            acc.push(
+                UnusedInclude {
+                    file_id: self.id,
+                    range: lsp_types::Range::new(
+                        point_to_lsp_position(&node.start_position()),
+                        point_to_lsp_position(&path_node.end_position()),
+                    ),
+                    path: path_node
+                        .utf8_text(source.as_bytes())
+                        .unwrap_or_default()
+                        .to_string(),
+                }
+                .into(),
+            );
+        }
+    }
+}
+
+pub(crate) fn include_closure_query(
+    db: &dyn HirDatabase,
+    file_id: FileId,
+) -> Arc<FxHashSet<FileId>> {
+    let mut res = FxHashSet::default();
+    let mut stack = vec![file_id];
+    while let Some(file_id) = stack.pop() {
+        if res.insert(file_id) {
+            stack.extend(db.file_includes(file_id).0.iter().map(|it| it.file_id()));
+        }
+    }
+
+    Arc::new(res)
+}
+
+pub(crate) fn include_closure_names_query(
+    db: &dyn HirDatabase,
+    file_id: FileId,
+) -> Arc<FxHashSet<Name>> {
+    let res = db
+        .include_closure(file_id)
+        .iter()
+        .flat_map(|file_id| {
+            db.file_def_map(*file_id)
+                .entries()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    Arc::new(res)
+}
+
+/// Pointer to the name of a declaration, used as the range of the unused lints.
+fn name_node_ptr<'tree>(
+    db: &dyn HirDatabase,
+    def: impl HasSource<'tree>,
+    tree: &'tree Tree,
+) -> Option<InFile<NodePtr>> {
+    let InFile { file_id, value } = def.source(db, tree)?;
+    let name_node = value.child_by_field_name("name").unwrap_or(value);
+    Some(InFile::new(file_id, NodePtr::from(&name_node)))
 }
 
 impl<'tree> File {
@@ -305,6 +549,7 @@ impl DefWithBody {
         let (_, source_map) = db.body_with_source_map(self.into());
         let infer = db.infer(self.into());
         let expr_syntax = |expr| source_map.expr_source(expr).expect("no matching source");
+        let mut includes_resolved = None;
         for d in infer.diagnostics.iter() {
             match d {
                 InferenceDiagnostic::UnresolvedField {
@@ -366,6 +611,21 @@ impl DefWithBody {
                     }
                     .into(),
                 ),
+                InferenceDiagnostic::UnresolvedIdent { expr, name } => {
+                    let expr = expr_syntax(*expr);
+                    // The identifier could be declared in an include that was not found.
+                    if *includes_resolved
+                        .get_or_insert_with(|| File::from(expr.file_id).includes_resolved(db))
+                    {
+                        acc.push(
+                            UnresolvedIdent {
+                                expr,
+                                name: name.clone(),
+                            }
+                            .into(),
+                        )
+                    }
+                }
                 InferenceDiagnostic::IncorrectNumberOfArguments {
                     expr,
                     name,
@@ -380,6 +640,97 @@ impl DefWithBody {
                     }
                     .into(),
                 ),
+                InferenceDiagnostic::MismatchedAssignmentType {
+                    expr,
+                    expected,
+                    actual,
+                } => acc.push(
+                    MismatchedAssignmentType {
+                        expr: expr_syntax(*expr),
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    }
+                    .into(),
+                ),
+                InferenceDiagnostic::MismatchedArgType {
+                    expr,
+                    name,
+                    index,
+                    expected,
+                    actual,
+                } => acc.push(
+                    MismatchedArgType {
+                        expr: expr_syntax(*expr),
+                        name: name.clone(),
+                        index: *index,
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    }
+                    .into(),
+                ),
+                InferenceDiagnostic::MismatchedReturnType {
+                    expr,
+                    expected,
+                    actual,
+                } => acc.push(
+                    MismatchedReturnType {
+                        expr: expr_syntax(*expr),
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    }
+                    .into(),
+                ),
+                InferenceDiagnostic::Deprecated { expr, name, note } => acc.push(
+                    Deprecated {
+                        expr: expr_syntax(*expr),
+                        name: name.clone(),
+                        note: note.to_string(),
+                    }
+                    .into(),
+                ),
+            }
+        }
+        self.unused_bindings_diagnostics(db, acc);
+    }
+
+    /// Lints the locals which are never used, and the unused parameters of the functions which
+    /// are not public, as the signature of a public function is imposed by its forward.
+    fn unused_bindings_diagnostics(self, db: &dyn HirDatabase, acc: &mut Vec<AnyDiagnostic>) {
+        let DefWithBody::Function(function) = self else {
+            // The parameters of a typedef or a functag are only a signature.
+            return;
+        };
+        let def = DefWithBodyId::from(self);
+        let (body, source_map) = db.body_with_source_map(def);
+        if body.body_expr.is_none() {
+            return;
+        }
+        let file_id = def.file_id(db.upcast());
+        let used = db.expr_scopes(def, file_id).used_bindings(&body);
+        let is_public = function.visibility(db).contains(RawVisibilityId::PUBLIC);
+        let tree = db.parse(file_id);
+        for (expr_id, expr) in body.exprs.iter() {
+            let Expr::Binding { ident_id, .. } = expr else {
+                continue;
+            };
+            if used.contains(&expr_id) {
+                continue;
+            }
+            let is_param = body.params.iter().any(|(_, param)| *param == expr_id);
+            if is_param && is_public {
+                continue;
+            }
+            let Some(source) = source_map.expr_source(expr_id) else {
+                continue;
+            };
+            let node = source.value.to_node(&tree);
+            let name_node = node.child_by_field_name("name").unwrap_or(node);
+            let expr = InFile::new(source.file_id, NodePtr::from(&name_node));
+            let name = body[*ident_id].clone();
+            if is_param {
+                acc.push(UnusedParameter { expr, name }.into());
+            } else {
+                acc.push(UnusedVariable { expr, name }.into());
             }
         }
     }
@@ -394,6 +745,45 @@ impl Function {
     pub fn name(self, db: &dyn HirDatabase) -> Name {
         db.function_data(self.id).name.clone()
     }
+
+    pub fn kind(self, db: &dyn HirDatabase) -> FunctionKind {
+        db.function_data(self.id).kind
+    }
+
+    /// The `public`, `stock` and `static` keywords of the function.
+    pub fn visibility(self, db: &dyn HirDatabase) -> RawVisibilityId {
+        let loc = self.id.lookup(db.upcast()).id;
+        loc.item_tree(db.upcast())[loc.value].visibility
+    }
+
+    /// The return type of the function, if it is declared.
+    pub fn type_ref(self, db: &dyn HirDatabase) -> Option<TypeRef> {
+        db.function_data(self.id).type_ref()
+    }
+
+    pub fn params(self, db: &dyn HirDatabase) -> Vec<ParamData> {
+        db.function_data(self.id).params().to_vec()
+    }
+
+    /// The calls made in the body of the function, with the function each of them resolves to.
+    pub fn calls(self, db: &dyn HirDatabase) -> Vec<(Function, InFile<NodePtr>)> {
+        let def = DefWithBodyId::FunctionId(self.id);
+        let (_, source_map) = db.body_with_source_map(def);
+        db.infer(def)
+            .calls()
+            .filter_map(|(expr, callee)| {
+                Some((Function::from(callee), source_map.expr_source(expr)?))
+            })
+            .collect()
+    }
+
+    /// Whether this function is a method of an enum struct or a methodmap.
+    pub fn is_method(self, db: &dyn HirDatabase) -> bool {
+        !matches!(
+            self.id.lookup(db.upcast()).container,
+            hir_def::ItemContainerId::FileId(_)
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -412,26 +802,159 @@ pub struct EnumStruct {
     pub(crate) id: EnumStructId,
 }
 
+impl EnumStruct {
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        db.enum_struct_data(self.id).name.clone()
+    }
+
+    pub fn fields(self, db: &dyn HirDatabase) -> Vec<Field> {
+        db.enum_struct_data(self.id)
+            .items
+            .iter()
+            .filter_map(|(id, item)| match item {
+                EnumStructItemData::Field(_) => Some(Field { parent: self, id }),
+                EnumStructItemData::Method(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn methods(self, db: &dyn HirDatabase) -> Vec<Function> {
+        db.enum_struct_data(self.id)
+            .items
+            .iter()
+            .filter_map(|(_, item)| match item {
+                EnumStructItemData::Method(id) => Some(Function::from(*id)),
+                EnumStructItemData::Field(_) => None,
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Methodmap {
     pub(crate) id: MethodmapId,
 }
 
+impl Methodmap {
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        db.methodmap_data(self.id).name.clone()
+    }
+
+    /// The methods of the methodmap, including the inherited ones.
+    ///
+    /// Constructors and destructors are not included.
+    pub fn methods(self, db: &dyn HirDatabase) -> Vec<Function> {
+        let data = db.methodmap_data(self.id);
+        let mut items = data.items_map.values().copied().collect::<Vec<_>>();
+        items.sort();
+        items
+            .into_iter()
+            .filter_map(|idx| match data.item(idx) {
+                MethodmapItemData::Method(id) => Some(Function::from(*id)),
+                MethodmapItemData::Property(_)
+                | MethodmapItemData::Constructor(_)
+                | MethodmapItemData::Destructor(_) => None,
+            })
+            .collect()
+    }
+
+    /// The properties of the methodmap, including the inherited ones.
+    pub fn properties(self, db: &dyn HirDatabase) -> Vec<Property> {
+        let data = db.methodmap_data(self.id);
+        let mut items = data.items_map.values().copied().collect::<Vec<_>>();
+        items.sort();
+        items
+            .into_iter()
+            .filter_map(|idx| data.property(idx).map(|it| Property::from(it.id)))
+            .collect()
+    }
+
+    /// The methods, constructors and destructors declared by the methodmap itself, in order of
+    /// declaration.
+    pub fn own_methods(self, db: &dyn HirDatabase) -> Vec<Function> {
+        self.own_items(db)
+            .into_iter()
+            .filter_map(|item| match item {
+                DefResolution::Function(it) => Some(it),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The methods, constructors, destructors and properties declared by the methodmap itself,
+    /// in order of declaration.
+    pub fn own_items(self, db: &dyn HirDatabase) -> Vec<DefResolution> {
+        let container = hir_def::ItemContainerId::MethodmapId(self.id);
+        db.methodmap_data(self.id)
+            .items
+            .iter()
+            .filter_map(|(_, item)| {
+                let (item_container, def) = match item {
+                    MethodmapItemData::Property(it) => (
+                        it.id.lookup(db.upcast()).container,
+                        DefResolution::Property(Property::from(it.id)),
+                    ),
+                    MethodmapItemData::Method(id)
+                    | MethodmapItemData::Constructor(id)
+                    | MethodmapItemData::Destructor(id) => (
+                        id.lookup(db.upcast()).container,
+                        DefResolution::Function(Function::from(*id)),
+                    ),
+                };
+                (item_container == container).then_some(def)
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Property {
     pub(crate) id: PropertyId,
 }
 
+impl Property {
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        let loc = self.id.lookup(db.upcast()).id;
+        loc.item_tree(db.upcast())[loc.value].name.clone()
+    }
+
+    pub fn type_ref(self, db: &dyn HirDatabase) -> TypeRef {
+        let loc = self.id.lookup(db.upcast()).id;
+        loc.item_tree(db.upcast())[loc.value].type_ref.clone()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Enum {
     pub(crate) id: EnumId,
 }
 
+impl Enum {
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        db.enum_data(self.id).name()
+    }
+
+    pub fn variants(self, db: &dyn HirDatabase) -> Vec<Variant> {
+        db.enum_data(self.id)
+            .variants
+            .iter()
+            .map(|(_, id)| Variant::from(*id))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Variant {
     pub(crate) id: VariantId,
 }
 
+impl Variant {
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        let loc = self.id.lookup(db.upcast()).id;
+        loc.item_tree(db.upcast())[loc.value].name.clone()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Typedef {
     pub(crate) id: TypedefId,
@@ -441,6 +964,10 @@ impl Typedef {
     pub fn name(self, db: &dyn HirDatabase) -> Option<Name> {
         db.typedef_data(self.id).name.clone()
     }
+
+    pub fn params(self, db: &dyn HirDatabase) -> Vec<ParamData> {
+        db.typedef_data(self.id).params.clone()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -452,6 +979,14 @@ impl Typeset {
     pub fn name(self, db: &dyn HirDatabase) -> Name {
         db.typeset_data(self.id).name.clone()
     }
+
+    pub fn typedefs(self, db: &dyn HirDatabase) -> Vec<Typedef> {
+        db.typeset_data(self.id)
+            .typedefs
+            .iter()
+            .map(|(_, id)| Typedef::from(*id))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -463,6 +998,10 @@ impl Functag {
     pub fn name(self, db: &dyn HirDatabase) -> Option<Name> {
         db.functag_data(self.id).name.clone()
     }
+
+    pub fn params(self, db: &dyn HirDatabase) -> Vec<ParamData> {
+        db.functag_data(self.id).params.clone()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -474,6 +1013,14 @@ impl Funcenum {
     pub fn name(self, db: &dyn HirDatabase) -> Name {
         db.funcenum_data(self.id).name.clone()
     }
+
+    pub fn functags(self, db: &dyn HirDatabase) -> Vec<Functag> {
+        db.funcenum_data(self.id)
+            .functags
+            .iter()
+            .map(|(_, id)| Functag::from(*id))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -481,12 +1028,45 @@ pub struct Global {
     pub(crate) id: GlobalId,
 }
 
+impl Global {
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        let loc = self.id.lookup(db.upcast());
+        loc.item_tree(db.upcast())[loc.value].name.clone()
+    }
+
+    /// The `public`, `stock` and `static` keywords of the global.
+    pub fn visibility(self, db: &dyn HirDatabase) -> RawVisibilityId {
+        let loc = self.id.lookup(db.upcast());
+        loc.item_tree(db.upcast())[loc.value].visibility
+    }
+
+    pub fn type_ref(self, db: &dyn HirDatabase) -> Option<TypeRef> {
+        let loc = self.id.lookup(db.upcast());
+        loc.item_tree(db.upcast())[loc.value].type_ref.clone()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Field {
     pub(crate) parent: EnumStruct,
     pub(crate) id: LocalFieldId,
 }
 
+impl Field {
+    pub fn name(self, db: &dyn HirDatabase) -> Option<Name> {
+        match db.enum_struct_data(self.parent.id).item(self.id) {
+            EnumStructItemData::Field(data) => Some(data.name.clone()),
+            EnumStructItemData::Method(_) => None,
+        }
+    }
+
+    pub fn type_ref(self, db: &dyn HirDatabase) -> Option<TypeRef> {
+        db.enum_struct_data(self.parent.id)
+            .field_type(self.id)
+            .cloned()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Attribute {
     Field(Field),
@@ -502,6 +1082,21 @@ pub struct Local {
     pub(crate) expr_id: ExprId,
 }
 
+impl Local {
+    /// The declared type of the local, if any.
+    pub fn type_ref(self, db: &dyn HirDatabase) -> Option<TypeRef> {
+        db.body(self.parent).binding_type_ref(self.expr_id).cloned()
+    }
+
+    /// Whether the local is a parameter of its function.
+    pub fn is_param(self, db: &dyn HirDatabase) -> bool {
+        db.body(self.parent)
+            .params
+            .iter()
+            .any(|(_, expr_id)| *expr_id == self.expr_id)
+    }
+}
+
 impl<'tree> Local {
     fn source(self, db: &dyn HirDatabase, tree: &'tree Tree) -> Option<LocalSource<'tree>> {
         let (_, source_map) = db.body_with_source_map(self.parent);
@@ -5,8 +5,8 @@ use std::{cell::RefCell, fmt, ops, sync::Arc};
 use base_db::{is_field_receiver_node, is_name_node, Tree};
 use hir_def::{
     resolve_include_node,
-    resolver::{global_resolver, ValueNs},
-    FileDefId, FunctionId, InFile, Name, NodePtr, PropertyItem,
+    resolver::{global_resolver, Resolver, ValueNs},
+    FileDefId, FunctionId, InFile, Name, NodePtr, PropertyItem, TypeRef,
 };
 use syntax::TSKind;
 use vfs::FileId;
@@ -158,7 +158,8 @@ impl<'db, DB: HirDatabase> Semantics<'db, DB> {
                 .funcenum_to_def(src)
                 .map(Funcenum::from)
                 .map(DefResolution::Funcenum),
-            _ => unreachable!(),
+            // Not a declaration, e.g. the name of a named argument.
+            _ => None,
         }
     }
 
@@ -529,6 +530,127 @@ impl<'db, DB: HirDatabase> Semantics<'db, DB> {
     pub fn to_file_def(&self, file_id: FileId) -> File {
         self.imp.file_to_def(file_id)
     }
+
+    /// Get the [`scope`](SemanticsScope) at a given position in a file.
+    ///
+    /// # Arguments
+    /// * `file_id` - The [`file_id`](FileId) of the file.
+    /// * `node` - The inner-most node at the position.
+    /// * `point` - The position in the preprocessed text.
+    pub fn scope_at(
+        &self,
+        file_id: FileId,
+        node: &tree_sitter::Node,
+        point: tree_sitter::Point,
+    ) -> SemanticsScope<'db> {
+        let resolver = self
+            .analyze_body_at(file_id, node, point, false)
+            .map(|analyzer| analyzer.resolver)
+            .unwrap_or_else(|| global_resolver(self.db, file_id));
+        SemanticsScope {
+            db: self.imp.db,
+            resolver,
+        }
+    }
+
+    /// Get the type of an expression node.
+    ///
+    /// Expressions that could not be lowered, like the ones of an incomplete statement,
+    /// fall back to the declared type of the identifier they refer to.
+    ///
+    /// # Arguments
+    /// * `file_id` - The [`file_id`](FileId) of the file containing the expression.
+    /// * `node` - The expression node.
+    pub fn type_of_node(&self, file_id: FileId, node: &tree_sitter::Node) -> Option<TypeRef> {
+        if let Some(type_ref) = self
+            .analyze_body_at(file_id, node, node.start_position(), true)
+            .and_then(|analyzer| analyzer.type_of_expr(self.db, node))
+        {
+            return Some(type_ref);
+        }
+        match TSKind::from(node) {
+            TSKind::identifier | TSKind::this => self.find_def(file_id, node)?.type_ref(self.db),
+            _ => None,
+        }
+    }
+
+    /// Whether an occurrence of a name in a function body is written to.
+    ///
+    /// Returns [`None`] if the occurrence was not lowered, e.g. if it is outside of a body.
+    ///
+    /// # Arguments
+    /// * `file_id` - The [`file_id`](FileId) of the file containing the occurrence.
+    /// * `node` - The name node.
+    pub fn is_write(&self, file_id: FileId, node: &tree_sitter::Node) -> Option<bool> {
+        self.analyze_body_at(file_id, node, node.start_position(), false)?.is_write(node)
+    }
+
+    fn analyze_body_at(
+        &self,
+        file_id: FileId,
+        node: &tree_sitter::Node,
+        point: tree_sitter::Point,
+        infer: bool,
+    ) -> Option<SourceAnalyzer> {
+        let mut container = *node;
+        loop {
+            if let TSKind::function_definition
+            | TSKind::enum_struct_method
+            | TSKind::methodmap_method
+            | TSKind::methodmap_method_constructor
+            | TSKind::methodmap_method_destructor
+            | TSKind::methodmap_property_method = TSKind::from(container)
+            {
+                break;
+            }
+            container = container.parent()?;
+        }
+        let body_node = container.child_by_field_name("body")?;
+        let id = self.fn_to_def(InFile::new(file_id, NodePtr::from(&container)))?;
+        let def = hir_def::DefWithBodyId::FunctionId(id);
+        let node = InFile::new(file_id, body_node);
+        let analyzer = if infer {
+            SourceAnalyzer::new_for_body(self.db, def, node, Some(point))
+        } else {
+            SourceAnalyzer::new_for_body_no_infer(self.db, def, node, Some(point))
+        };
+        Some(analyzer)
+    }
+}
+
+/// The set of names visible at a given position in a file.
+pub struct SemanticsScope<'db> {
+    db: &'db dyn HirDatabase,
+    resolver: Resolver,
+}
+
+impl fmt::Debug for SemanticsScope<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SemanticsScope {{ ... }}")
+    }
+}
+
+impl SemanticsScope<'_> {
+    /// Call `f` for every name visible in this scope, inner-most scopes first.
+    ///
+    /// Shadowed names are reported as well, callers should only keep the first occurence
+    /// of a name.
+    pub fn process_all_names(&self, f: &mut dyn FnMut(Name, DefResolution)) {
+        self.resolver.process_all_names(&mut |name, value| {
+            if let Some(def) = DefResolution::try_from(value) {
+                f(name, def)
+            }
+        });
+    }
+
+    /// Resolve a name, like the one of a type, in this scope.
+    pub fn resolve_ident(&self, name: &str) -> Option<DefResolution> {
+        DefResolution::try_from(self.resolver.resolve_ident(name)?)
+    }
+
+    pub fn db(&self) -> &dyn HirDatabase {
+        self.db
+    }
 }
 
 // FIXME: This is a hacky way to implement the `ToDef` trait...
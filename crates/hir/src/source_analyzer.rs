@@ -7,7 +7,7 @@ use hir_def::{
         Body, BodySourceMap,
     },
     resolver::{resolver_for_scope, HasResolver, Resolver},
-    DefWithBodyId, ExprId, InFile, InferenceResult,
+    DefWithBodyId, Expr, ExprId, InFile, InferenceResult, TypeRef,
 };
 use syntax::TSKind;
 use tree_sitter::Point;
@@ -143,6 +143,17 @@ impl SourceAnalyzer {
             .map(|it| it.into())
     }
 
+    pub(crate) fn type_of_expr(
+        &self,
+        db: &dyn HirDatabase,
+        node: &tree_sitter::Node,
+    ) -> Option<TypeRef> {
+        let node = expr_node(*node)?;
+        let src = InFile::new(self.file_id, &node);
+        let expr_id = self.expr_id(db, src)?;
+        self.infer.as_ref()?.type_of_expr(expr_id).cloned()
+    }
+
     pub(crate) fn resolve_named_arg(
         &self,
         db: &dyn HirDatabase,
@@ -156,6 +167,75 @@ impl SourceAnalyzer {
 
         Some(Local { parent, expr_id })
     }
+
+    /// Whether an occurrence of a name is written to: the name of a binding, or the place of an
+    /// assignment or an update, are writes.
+    pub(crate) fn is_write(&self, node: &tree_sitter::Node) -> Option<bool> {
+        let (_, body, source_map) = self.def.as_ref()?;
+        let parent = node.parent()?;
+        if parent.child_by_field_name("name") == Some(*node) {
+            let expr_id = source_map.node_expr(InFile::new(self.file_id, &parent))?;
+            return Some(matches!(body[expr_id], Expr::Binding { .. }));
+        }
+        let expr_id = source_map.node_expr(InFile::new(self.file_id, node))?;
+        let is_write = body.exprs.iter().any(|(_, expr)| {
+            let place = match expr {
+                Expr::BinaryOp { lhs, op: Some(op), .. } if is_assignment_op(*op) => *lhs,
+                Expr::UnaryOp {
+                    operand,
+                    op: Some(TSKind::anon_PLUS_PLUS_ | TSKind::anon_DASH_DASH_),
+                } => *operand,
+                _ => return false,
+            };
+            // Writing to a field or an element of an array is writing to the whole place.
+            iter::successors(Some(place), |place| match &body[*place] {
+                Expr::FieldAccess { target, .. } => Some(*target),
+                Expr::ScopeAccess { scope, .. } => Some(*scope),
+                Expr::ArrayIndexedAccess { array, .. } => Some(*array),
+                _ => None,
+            })
+            .any(|place| place == expr_id)
+        });
+
+        Some(is_write)
+    }
+}
+
+fn is_assignment_op(op: TSKind) -> bool {
+    matches!(
+        op,
+        TSKind::anon_EQ
+            | TSKind::anon_PLUS_EQ
+            | TSKind::anon_DASH_EQ
+            | TSKind::anon_STAR_EQ
+            | TSKind::anon_SLASH_EQ
+            | TSKind::anon_PERCENT_EQ_
+            | TSKind::anon_AMP_EQ
+            | TSKind::anon_PIPE_EQ
+            | TSKind::anon_CARET_EQ
+            | TSKind::anon_LT_LT_EQ
+            | TSKind::anon_GT_GT_EQ
+            | TSKind::anon_GT_GT_GT_EQ_
+    )
+}
+
+/// Get the node an expression was lowered from.
+///
+/// Field accesses and method calls are lowered from their field, not from the whole expression.
+fn expr_node(node: tree_sitter::Node) -> Option<tree_sitter::Node> {
+    match TSKind::from(node) {
+        TSKind::field_access => node.child_by_field_name("field"),
+        TSKind::call_expression => {
+            let function = node.child_by_field_name("function")?;
+            match TSKind::from(function) {
+                TSKind::field_access => function.child_by_field_name("field"),
+                _ => Some(node),
+            }
+        }
+        TSKind::new_expression => node.child_by_field_name("class"),
+        TSKind::parenthesized_expression => expr_node(node.child_by_field_name("expression")?),
+        _ => Some(node),
+    }
 }
 
 fn scope_for(
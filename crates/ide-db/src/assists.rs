@@ -0,0 +1,38 @@
+//! This module defines the types of assists, the code edits offered to the user, e.g. the fixes
+//! of a diagnostic.
+
+use crate::SourceChange;
+
+/// The kind of an assist, used by the client to group and filter the assists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssistKind {
+    /// A fix for a diagnostic.
+    QuickFix,
+    Refactor,
+}
+
+impl AssistKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AssistKind::QuickFix => "QuickFix",
+            AssistKind::Refactor => "Refactor",
+        }
+    }
+}
+
+/// Unique identifier of the assist, should not be shown to the user directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssistId(pub &'static str, pub AssistKind);
+
+#[derive(Debug, Clone)]
+pub struct Assist {
+    pub id: AssistId,
+
+    /// Short description of the assist, as shown in the UI.
+    pub label: String,
+
+    /// Target ranges are used to sort assists: the smaller the target range, the more specific
+    /// the assist.
+    pub target: lsp_types::Range,
+    pub source_change: SourceChange,
+}
@@ -22,7 +22,7 @@ impl Documentation {
     }
 
     pub fn from_node(mut node: tree_sitter::Node, source: &[u8]) -> Option<Documentation> {
-        let mut pragma = None;
+        let mut seen_pragma = false;
         let mut docs = Vec::new();
         match TSKind::from(&node) {
             TSKind::function_declaration
@@ -40,13 +40,16 @@ impl Documentation {
             | TSKind::methodmap_method
             | TSKind::methodmap_method_constructor
             | TSKind::methodmap_method_destructor
+            | TSKind::methodmap_property
             | TSKind::methodmap_property_getter
             | TSKind::methodmap_property_setter
             | TSKind::methodmap_property_native
             | TSKind::methodmap_property_method
             | TSKind::methodmap_native
             | TSKind::methodmap_native_constructor
-            | TSKind::methodmap_native_destructor => {
+            | TSKind::methodmap_native_destructor
+            | TSKind::preproc_define
+            | TSKind::preproc_macro => {
                 while let Some(prev_node) = node.prev_sibling() {
                     if node
                         .range()
@@ -57,17 +60,13 @@ impl Documentation {
                     {
                         break;
                     }
+                    // Skip the `#pragma deprecated` directive of the declaration, whose note is
+                    // read by `syntax::utils::deprecation_note`.
                     if TSKind::from(prev_node) == TSKind::preproc_pragma {
-                        if pragma.is_some() {
+                        if seen_pragma {
                             break;
                         }
-                        pragma = Some(
-                            prev_node
-                                .utf8_text(source)
-                                .ok()?
-                                .trim_start_matches("#pragma deprecated")
-                                .trim(),
-                        );
+                        seen_pragma = true;
                         node = prev_node;
                         continue;
                     }
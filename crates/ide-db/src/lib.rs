@@ -1,6 +1,9 @@
 //! base_db defines basic database traits. The concrete DB is defined by ide.
 
+mod assists;
 mod documentation;
+mod source_change;
+pub mod symbol_index;
 
 use std::{fmt, mem::ManuallyDrop, sync::Arc};
 
@@ -9,12 +12,14 @@ use base_db::{
     SourceDatabaseStorage, Upcast,
 };
 use fxhash::FxHashMap;
-use hir::db::HirDatabase;
+use hir::{db::HirDatabase, DefResolution, FunctionKind};
 use hir_def::DefDatabase;
 use salsa::{Cancelled, Durability};
 use vfs::FileId;
 
+pub use assists::{Assist, AssistId, AssistKind};
 pub use documentation::Documentation;
+pub use source_change::SourceChange;
 
 pub type Cancellable<T> = Result<T, Cancelled>;
 
@@ -28,7 +33,8 @@ pub type FxIndexMap<K, V> =
     hir_def::db::InternDatabaseStorage,
     hir_def::db::DefDatabaseStorage,
     preprocessor::db::PreprocDatabaseStorage,
-    hir::db::HirDatabaseStorage
+    hir::db::HirDatabaseStorage,
+    symbol_index::SymbolsDatabaseStorage
 )]
 pub struct RootDatabase {
     // We use `ManuallyDrop` here because every codegen unit that contains a
@@ -168,4 +174,53 @@ impl salsa::ParallelDatabase for RootDatabase {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum SymbolKind {
     Macro,
+    Function,
+    Forward,
+    Native,
+    Method,
+    Global,
+    Local,
+    Param,
+    Enum,
+    Variant,
+    EnumStruct,
+    Field,
+    Methodmap,
+    Property,
+    Typedef,
+    Typeset,
+    Functag,
+    Funcenum,
+}
+
+impl SymbolKind {
+    /// Get the [`kind`](SymbolKind) of a [`definition`](DefResolution).
+    ///
+    /// Returns [`None`] for files, which are not symbols.
+    pub fn from_def(db: &dyn HirDatabase, def: &DefResolution) -> Option<Self> {
+        let kind = match def {
+            DefResolution::Function(it) if it.is_method(db) => SymbolKind::Method,
+            DefResolution::Function(it) => match it.kind(db) {
+                FunctionKind::Def => SymbolKind::Function,
+                FunctionKind::Forward => SymbolKind::Forward,
+                FunctionKind::Native => SymbolKind::Native,
+            },
+            DefResolution::Macro(_) => SymbolKind::Macro,
+            DefResolution::EnumStruct(_) => SymbolKind::EnumStruct,
+            DefResolution::Methodmap(_) => SymbolKind::Methodmap,
+            DefResolution::Property(_) => SymbolKind::Property,
+            DefResolution::Enum(_) => SymbolKind::Enum,
+            DefResolution::Variant(_) => SymbolKind::Variant,
+            DefResolution::Typedef(_) => SymbolKind::Typedef,
+            DefResolution::Typeset(_) => SymbolKind::Typeset,
+            DefResolution::Functag(_) => SymbolKind::Functag,
+            DefResolution::Funcenum(_) => SymbolKind::Funcenum,
+            DefResolution::Field(_) => SymbolKind::Field,
+            DefResolution::Global(_) => SymbolKind::Global,
+            DefResolution::Local(it) if it.is_param(db) => SymbolKind::Param,
+            DefResolution::Local(_) => SymbolKind::Local,
+            DefResolution::File(_) => return None,
+        };
+        Some(kind)
+    }
 }
@@ -0,0 +1,43 @@
+//! This modules defines type to represent changes to the source code, that flow
+//! from the server to the client.
+
+use fxhash::FxHashMap;
+use vfs::FileId;
+
+/// A set of edits to apply to the source files.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct SourceChange {
+    pub source_file_edits: FxHashMap<FileId, Vec<lsp_types::TextEdit>>,
+}
+
+impl SourceChange {
+    /// Creates a new [`SourceChange`] with a single [`TextEdit`](lsp_types::TextEdit) for the
+    /// given [`FileId`].
+    pub fn from_text_edit(file_id: FileId, edit: lsp_types::TextEdit) -> Self {
+        let mut res = SourceChange::default();
+        res.insert_source_edit(file_id, edit);
+        res
+    }
+
+    /// Inserts a [`TextEdit`](lsp_types::TextEdit) for the given [`FileId`]. This properly handles
+    /// merging existing edits for a file if some already exist.
+    pub fn insert_source_edit(&mut self, file_id: FileId, edit: lsp_types::TextEdit) {
+        self.source_file_edits.entry(file_id).or_default().push(edit);
+    }
+
+    /// Inserts several [`TextEdit`](lsp_types::TextEdit) for the given [`FileId`].
+    pub fn extend_source_edits(
+        &mut self,
+        file_id: FileId,
+        edits: impl IntoIterator<Item = lsp_types::TextEdit>,
+    ) {
+        self.source_file_edits
+            .entry(file_id)
+            .or_default()
+            .extend(edits);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.source_file_edits.values().all(Vec::is_empty)
+    }
+}
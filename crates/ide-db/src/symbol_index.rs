@@ -0,0 +1,90 @@
+//! Index of the symbols declared in each file, searched by the workspace symbol requests.
+
+use std::sync::Arc;
+
+use base_db::Upcast;
+use hir::{db::HirDatabase, DefResolution, File, FileDef};
+use vfs::FileId;
+
+use crate::SymbolKind;
+
+#[salsa::query_group(SymbolsDatabaseStorage)]
+pub trait SymbolsDatabase: HirDatabase + Upcast<dyn HirDatabase> {
+    /// The symbols declared in the file, including the items of its containers.
+    fn file_symbols(&self, file_id: FileId) -> Arc<[FileSymbol]>;
+}
+
+/// A symbol declared in a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub def: DefResolution,
+
+    /// Name of the symbol that contains this symbol, e.g. the methodmap of a method.
+    pub container_name: Option<String>,
+}
+
+fn file_symbols(db: &dyn SymbolsDatabase, file_id: FileId) -> Arc<[FileSymbol]> {
+    let db: &dyn HirDatabase = db.upcast();
+    let mut res = Vec::new();
+    for def in File::from(file_id).declarations(db) {
+        // Variants are declared at the top level, but belong to their enum.
+        if !matches!(def, FileDef::Variant(_)) {
+            collect_symbols(db, DefResolution::from(def), None, &mut res);
+        }
+    }
+
+    res.into()
+}
+
+fn collect_symbols(
+    db: &dyn HirDatabase,
+    def: DefResolution,
+    container_name: Option<&str>,
+    acc: &mut Vec<FileSymbol>,
+) {
+    let (Some(kind), Some(name)) = (SymbolKind::from_def(db, &def), def.name(db)) else {
+        return;
+    };
+    let name = name.to_string();
+    for child in children(db, &def) {
+        collect_symbols(db, child, Some(&name), acc);
+    }
+    acc.push(FileSymbol {
+        name,
+        kind,
+        def,
+        container_name: container_name.map(ToString::to_string),
+    });
+}
+
+/// The items declared in a container, e.g. the fields and methods of an enum struct, in the order
+/// they are listed by the container.
+pub fn children(db: &dyn HirDatabase, def: &DefResolution) -> Vec<DefResolution> {
+    match def {
+        DefResolution::Methodmap(it) => it.own_items(db),
+        DefResolution::EnumStruct(it) => it
+            .fields(db)
+            .into_iter()
+            .map(DefResolution::Field)
+            .chain(it.methods(db).into_iter().map(DefResolution::Function))
+            .collect(),
+        DefResolution::Enum(it) => it
+            .variants(db)
+            .into_iter()
+            .map(DefResolution::Variant)
+            .collect(),
+        DefResolution::Typeset(it) => it
+            .typedefs(db)
+            .into_iter()
+            .map(DefResolution::Typedef)
+            .collect(),
+        DefResolution::Funcenum(it) => it
+            .functags(db)
+            .into_iter()
+            .map(DefResolution::Functag)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
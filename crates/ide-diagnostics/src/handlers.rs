@@ -1,10 +1,23 @@
+pub(crate) mod deprecated;
+pub(crate) mod error_directive;
+pub(crate) mod failed_assertion;
 pub(crate) mod inactive_code;
 pub(crate) mod incorrect_number_of_arguments;
+pub(crate) mod mismatched_arg_type;
+pub(crate) mod mismatched_assignment_type;
+pub(crate) mod mismatched_return_type;
 pub(crate) mod preprocessor_evaluation_error;
 pub(crate) mod unresolved_constructor;
 pub(crate) mod unresolved_field;
+pub(crate) mod unresolved_ident;
 pub(crate) mod unresolved_include;
 pub(crate) mod unresolved_inherit;
 pub(crate) mod unresolved_macro;
 pub(crate) mod unresolved_method_call;
 pub(crate) mod unresolved_named_arg;
+pub(crate) mod unused_function;
+pub(crate) mod unused_global;
+pub(crate) mod unused_include;
+pub(crate) mod unused_parameter;
+pub(crate) mod unused_variable;
+pub(crate) mod warning_directive;
@@ -0,0 +1,22 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::deprecated as f;
+
+// Diagnostic: deprecated
+//
+// This diagnostic is triggered if a function, a method or a property preceded by a
+// `#pragma deprecated` directive is used.
+pub(crate) fn deprecated(ctx: &DiagnosticsContext<'_>, d: &hir::Deprecated) -> Diagnostic {
+    let message = if d.note.is_empty() {
+        format!("`{}` is deprecated", d.name)
+    } else {
+        format!("`{}` is deprecated: {}", d.name, d.note)
+    };
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::Lint("deprecated", Severity::Warning),
+        message,
+        d.expr,
+    )
+    .with_deprecated(true)
+}
@@ -0,0 +1,17 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+pub(crate) use self::error_directive as f;
+
+// Diagnostic: error-directive
+//
+// This diagnostic is triggered if an `#error` directive is reached in an active branch.
+pub(crate) fn error_directive(
+    _ctx: &DiagnosticsContext<'_>,
+    d: &hir::ErrorDirective,
+) -> Diagnostic {
+    Diagnostic::new(
+        DiagnosticCode::SpCompError("E0000"),
+        format!("user error: {}", d.text),
+        d.range,
+    )
+}
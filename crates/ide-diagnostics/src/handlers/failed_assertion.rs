@@ -0,0 +1,17 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+pub(crate) use self::failed_assertion as f;
+
+// Diagnostic: failed-assertion
+//
+// This diagnostic is triggered if the condition of an `#assert` directive evaluates to false.
+pub(crate) fn failed_assertion(
+    _ctx: &DiagnosticsContext<'_>,
+    d: &hir::FailedAssertion,
+) -> Diagnostic {
+    Diagnostic::new(
+        DiagnosticCode::SpCompError("E0000"),
+        format!("assertion failed: {}", d.text),
+        d.range,
+    )
+}
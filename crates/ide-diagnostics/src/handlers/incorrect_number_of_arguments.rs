@@ -1,4 +1,11 @@
-use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+use hir::{DefResolution, HasSource, SemanticsScope, TypeRef};
+use ide_db::{Assist, SourceChange};
+use syntax::{
+    utils::{point_to_lsp_position, ts_range_to_lsp_range},
+    TSKind,
+};
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
 
 pub(crate) use self::incorrect_number_of_arguments as f;
 
@@ -21,4 +28,123 @@ pub(crate) fn incorrect_number_of_arguments(
         )
     };
     Diagnostic::new_with_syntax_node_ptr(ctx, DiagnosticCode::SpCompError("E0000"), message, d.expr)
+        .with_fixes(fixes(ctx, d))
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::IncorrectNumberOfArguments) -> Option<Vec<Assist>> {
+    if d.expected <= d.actual {
+        return None;
+    }
+    let db = ctx.sema.db;
+    let file_id = d.expr.file_id;
+    let tree = ctx.sema.parse(file_id);
+    let node = d.expr.value.to_node(&tree);
+    // The diagnostic is attached to the last argument of the call, or to the call itself if it
+    // has no arguments.
+    let call = if d.actual == 0 {
+        node
+    } else {
+        let mut args = node.parent()?;
+        while TSKind::from(args) != TSKind::call_arguments {
+            args = args.parent()?;
+        }
+        args.parent()?
+    };
+    let args = call.child_by_field_name("arguments")?;
+    let rparen = args.child(args.child_count().checked_sub(1)?)?;
+    if TSKind::from(rparen) != TSKind::anon_RPAREN || rparen.is_missing() {
+        return None;
+    }
+
+    let function = call.child_by_field_name("function")?;
+    let DefResolution::Function(def) = ctx.sema.find_def(file_id, &function)? else {
+        return None;
+    };
+    let def_file_id = DefResolution::Function(def).file_id(db);
+    let def_tree = ctx.sema.parse(def_file_id);
+    let def_source = ctx.sema.preprocessed_text(def_file_id);
+    let params = def.source(db, &def_tree)?.value.child_by_field_name("parameters")?;
+    let scope = ctx.sema.scope_at(file_id, &call, call.start_position());
+    let params = params
+        .children(&mut params.walk())
+        .filter(|param| TSKind::from(param) == TSKind::parameter_declaration)
+        .collect::<Vec<_>>();
+    // Fill the parameters up to the last mandatory one, including the optional ones before it.
+    let last_mandatory = params
+        .iter()
+        .rposition(|param| param.child_by_field_name("defaultValue").is_none())?;
+    // Drop the fix if a placeholder cannot be found for one of the missing arguments.
+    let missing = params
+        .into_iter()
+        .take(last_mandatory + 1)
+        .skip(d.actual)
+        .map(|param| placeholder(&scope, param, &def_source))
+        .collect::<Option<Vec<_>>>()?;
+    if missing.is_empty() {
+        return None;
+    }
+    let separator = if d.actual == 0 { "" } else { ", " };
+    let position = point_to_lsp_position(&rparen.start_position());
+    let edit = lsp_types::TextEdit::new(
+        lsp_types::Range::new(position, position),
+        format!("{}{}", separator, missing.join(", ")),
+    );
+
+    Some(vec![fix(
+        "add_missing_args",
+        "Add missing arguments",
+        SourceChange::from_text_edit(file_id, edit),
+        ts_range_to_lsp_range(&call.range()),
+    )])
+}
+
+/// Get the argument inserted for a missing parameter: its default value if it declares one, or a
+/// placeholder value of its type.
+///
+/// Returns [`None`] for the parameters passed by reference, which need a variable, and for the
+/// types without a literal, e.g. enum structs and function types.
+fn placeholder(
+    scope: &SemanticsScope<'_>,
+    param: tree_sitter::Node,
+    source: &str,
+) -> Option<String> {
+    if let Some(default) = param.child_by_field_name("defaultValue") {
+        return default.utf8_text(source.as_bytes()).ok().map(ToString::to_string);
+    }
+    let mut is_array = false;
+    for child in param.children(&mut param.walk()) {
+        match TSKind::from(child) {
+            TSKind::anon_AMP => return None,
+            TSKind::dimension | TSKind::fixed_dimension => is_array = true,
+            _ => (),
+        }
+    }
+    let type_ref = match TypeRef::from_returntype_node(&param, "type", source) {
+        Some(TypeRef::Array((type_ref, _))) => {
+            is_array = true;
+            Some(*type_ref)
+        }
+        type_ref => type_ref,
+    };
+    let placeholder = match type_ref {
+        Some(TypeRef::Char | TypeRef::OldString) if is_array => "\"\"".to_string(),
+        _ if is_array => return None,
+        // Parameters without a type are cells.
+        None | Some(TypeRef::Int | TypeRef::Any) => "0".to_string(),
+        Some(TypeRef::Float | TypeRef::OldFloat) => "0.0".to_string(),
+        Some(TypeRef::Bool) => "false".to_string(),
+        Some(TypeRef::Char) => "'\\0'".to_string(),
+        Some(TypeRef::OldName(name)) if name.to_string() == "Float" => "0.0".to_string(),
+        Some(TypeRef::OldName(name)) if name.to_string() == "bool" => "false".to_string(),
+        Some(TypeRef::Name(name) | TypeRef::OldName(name)) => {
+            match scope.resolve_ident(&name.to_string())? {
+                DefResolution::Methodmap(_) => "null".to_string(),
+                DefResolution::Enum(_) => format!("view_as<{}>(0)", name),
+                _ => return None,
+            }
+        }
+        Some(TypeRef::Void | TypeRef::Array(_)) => return None,
+    };
+
+    Some(placeholder)
 }
@@ -0,0 +1,25 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::mismatched_arg_type as f;
+
+// Diagnostic: mismatched-arg-type
+//
+// This diagnostic is triggered if the type of an argument does not match the type of the
+// parameter of the called function.
+pub(crate) fn mismatched_arg_type(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::MismatchedArgType,
+) -> Diagnostic {
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::Lint("mismatched-arg-type", Severity::Warning),
+        format!(
+            "mismatched types for argument {} of `{}`: expected `{}`, found `{}`",
+            d.index + 1,
+            d.name,
+            d.expected.to_str(),
+            d.actual.to_str()
+        ),
+        d.expr,
+    )
+}
@@ -0,0 +1,23 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::mismatched_assignment_type as f;
+
+// Diagnostic: mismatched-assignment-type
+//
+// This diagnostic is triggered if the type of an assigned value does not match the type of the
+// variable, field or property it is assigned to.
+pub(crate) fn mismatched_assignment_type(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::MismatchedAssignmentType,
+) -> Diagnostic {
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::Lint("mismatched-assignment-type", Severity::Warning),
+        format!(
+            "mismatched types: expected `{}`, found `{}`",
+            d.expected.to_str(),
+            d.actual.to_str()
+        ),
+        d.expr,
+    )
+}
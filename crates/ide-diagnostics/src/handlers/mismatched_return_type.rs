@@ -0,0 +1,23 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::mismatched_return_type as f;
+
+// Diagnostic: mismatched-return-type
+//
+// This diagnostic is triggered if the type of a returned value does not match the return type
+// of the function.
+pub(crate) fn mismatched_return_type(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::MismatchedReturnType,
+) -> Diagnostic {
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::Lint("mismatched-return-type", Severity::Warning),
+        format!(
+            "mismatched return type: expected `{}`, found `{}`",
+            d.expected.to_str(),
+            d.actual.to_str()
+        ),
+        d.expr,
+    )
+}
@@ -1,4 +1,8 @@
-use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+use hir::DefResolution;
+use ide_db::{Assist, SourceChange};
+use syntax::utils::ts_range_to_lsp_range;
+
+use crate::{closest_name, fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
 
 pub(crate) use self::unresolved_field as f;
 
@@ -23,6 +27,39 @@ pub(crate) fn unresolved_field(
         ),
         d.expr,
     )
-    // .with_fixes(fixes(ctx, d))
-    // .experimental()
+    .with_fixes(fixes(ctx, d))
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedField) -> Option<Vec<Assist>> {
+    let db = ctx.sema.db;
+    let tree = ctx.sema.parse(d.expr.file_id);
+    let node = d.expr.value.to_node(&tree);
+    let candidates = match ctx
+        .sema
+        .scope_at(d.expr.file_id, &node, node.start_position())
+        .resolve_ident(&d.receiver.to_string())?
+    {
+        DefResolution::EnumStruct(it) => it
+            .fields(db)
+            .into_iter()
+            .filter_map(|field| field.name(db))
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>(),
+        DefResolution::Methodmap(it) => it
+            .properties(db)
+            .into_iter()
+            .map(|property| property.name(db).to_string())
+            .collect(),
+        _ => return None,
+    };
+    let suggestion = closest_name(&d.name.to_string(), candidates)?;
+    let range = ts_range_to_lsp_range(&node.range());
+    let edit = lsp_types::TextEdit::new(range, suggestion.clone());
+
+    Some(vec![fix(
+        "change_field_name",
+        &format!("Change to `{}`", suggestion),
+        SourceChange::from_text_edit(d.expr.file_id, edit),
+        range,
+    )])
 }
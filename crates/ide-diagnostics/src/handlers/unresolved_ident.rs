@@ -0,0 +1,102 @@
+use base_db::{FileExtension, SourceDatabase, SourceDatabaseExt};
+use hir_def::DefDatabase;
+use ide_db::{Assist, RootDatabase, SourceChange};
+use lsp_types::{Position, Range, TextEdit};
+use syntax::utils::ts_range_to_lsp_range;
+use vfs::FileId;
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+pub(crate) use self::unresolved_ident as f;
+
+// Diagnostic: unresolved-ident
+//
+// This diagnostic is triggered if an identifier is not declared in any of the files of the
+// project. It is only emitted when all the includes of the project could be resolved.
+pub(crate) fn unresolved_ident(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::UnresolvedIdent,
+) -> Diagnostic {
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::SpCompError("E0000"),
+        format!("cannot find `{}` in this scope", d.name),
+        d.expr,
+    )
+    .with_fixes(fixes(ctx, d))
+}
+
+fn fixes(ctx: &DiagnosticsContext<'_>, d: &hir::UnresolvedIdent) -> Option<Vec<Assist>> {
+    let db = ctx.sema.db;
+    let file_id = d.expr.file_id;
+    let subgraph = db.projet_subgraph(file_id)?;
+    let tree = ctx.sema.parse(file_id);
+    let target = ts_range_to_lsp_range(&d.expr.value.to_node(&tree).range());
+
+    let mut res = db
+        .known_files()
+        .into_iter()
+        .filter(|(candidate, ext)| {
+            *ext == FileExtension::Inc
+                && !subgraph.contains_file(*candidate)
+                && db.file_def_map(*candidate).get(&d.name).is_some()
+        })
+        .filter_map(|(candidate, _)| include_path(db, candidate))
+        .map(|path| {
+            let edit = add_include_edit(db, file_id, &path);
+            fix(
+                "add_include",
+                &format!("Add `#include <{}>`", path),
+                SourceChange::from_text_edit(file_id, edit),
+                target,
+            )
+        })
+        .collect::<Vec<_>>();
+    res.sort_by(|a, b| a.label.cmp(&b.label));
+
+    (!res.is_empty()).then_some(res)
+}
+
+/// Path of an include file relative to the root it belongs to, without its extension, as it
+/// would be written between chevrons.
+fn include_path(db: &RootDatabase, file_id: FileId) -> Option<String> {
+    let source_root = db.source_root(db.file_source_root(file_id));
+    let path = source_root
+        .path_for_file(&file_id)?
+        .strip_prefix(source_root.root())?
+        .as_ref()
+        .to_str()?
+        .replace('\\', "/");
+
+    Some(path.strip_suffix(".inc").unwrap_or(&path).to_string())
+}
+
+/// Insert the include after the last include of the file, or at the top of the file.
+fn add_include_edit(db: &RootDatabase, file_id: FileId, path: &str) -> TextEdit {
+    let text = db.file_text(file_id);
+    let lines = text.lines().collect::<Vec<_>>();
+    let last_include = lines.iter().rposition(|line| {
+        let line = line.trim_start();
+        line.starts_with("#include") || line.starts_with("#tryinclude")
+    });
+    match last_include {
+        Some(idx) if idx + 1 < lines.len() => {
+            let position = Position::new(idx as u32 + 1, 0);
+            TextEdit::new(
+                Range::new(position, position),
+                format!("#include <{}>\n", path),
+            )
+        }
+        Some(idx) => {
+            let position = Position::new(idx as u32, lines[idx].chars().count() as u32);
+            TextEdit::new(
+                Range::new(position, position),
+                format!("\n#include <{}>", path),
+            )
+        }
+        None => TextEdit::new(
+            Range::new(Position::new(0, 0), Position::new(0, 0)),
+            format!("#include <{}>\n", path),
+        ),
+    }
+}
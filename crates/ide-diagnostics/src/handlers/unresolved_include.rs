@@ -1,4 +1,7 @@
-use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+use ide_db::{Assist, SourceChange};
+use lsp_types::{Position, Range, TextEdit};
+
+use crate::{fix, Diagnostic, DiagnosticCode, DiagnosticsContext};
 
 pub(crate) use self::unresolved_include as f;
 
@@ -14,6 +17,20 @@ pub(crate) fn unresolved_include(
         format!("file `{}` was not found", d.path),
         d.range,
     )
-    // .with_fixes(fixes(ctx, d))
-    // .experimental()
+    .with_fixes(fixes(d))
+}
+
+fn fixes(d: &hir::UnresolvedInclude) -> Option<Vec<Assist>> {
+    let line = d.range.start.line;
+    let edit = TextEdit::new(
+        Range::new(Position::new(line, 0), Position::new(line + 1, 0)),
+        String::new(),
+    );
+
+    Some(vec![fix(
+        "remove_unresolved_include",
+        "Remove unresolved include",
+        SourceChange::from_text_edit(d.file_id, edit),
+        d.range,
+    )])
 }
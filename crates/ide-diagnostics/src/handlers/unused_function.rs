@@ -0,0 +1,16 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::unused_function as f;
+
+// Diagnostic: unused-function
+//
+// This diagnostic is triggered if a `stock` function of a plugin is never used in its project.
+pub(crate) fn unused_function(ctx: &DiagnosticsContext<'_>, d: &hir::UnusedFunction) -> Diagnostic {
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::Lint("unused-function", Severity::WeakWarning),
+        format!("function `{}` is never used", d.name),
+        d.expr,
+    )
+    .with_unused(true)
+}
@@ -0,0 +1,16 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::unused_global as f;
+
+// Diagnostic: unused-global
+//
+// This diagnostic is triggered if a `static` global variable is never used in its file.
+pub(crate) fn unused_global(ctx: &DiagnosticsContext<'_>, d: &hir::UnusedGlobal) -> Diagnostic {
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::Lint("unused-global", Severity::WeakWarning),
+        format!("static variable `{}` is never used", d.name),
+        d.expr,
+    )
+    .with_unused(true)
+}
@@ -0,0 +1,16 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::unused_include as f;
+
+// Diagnostic: unused-include
+//
+// This diagnostic is triggered if none of the declarations and macros of an included `.inc` file,
+// or of the files it includes, are used by the other files of the project.
+pub(crate) fn unused_include(_ctx: &DiagnosticsContext<'_>, d: &hir::UnusedInclude) -> Diagnostic {
+    Diagnostic::new(
+        DiagnosticCode::Lint("unused-include", Severity::WeakWarning),
+        format!("unused include `{}`", d.path),
+        d.range,
+    )
+    .with_unused(true)
+}
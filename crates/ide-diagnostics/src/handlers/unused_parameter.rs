@@ -0,0 +1,20 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::unused_parameter as f;
+
+// Diagnostic: unused-parameter
+//
+// This diagnostic is triggered if a parameter of a function which is not public is never used
+// in its body.
+pub(crate) fn unused_parameter(
+    ctx: &DiagnosticsContext<'_>,
+    d: &hir::UnusedParameter,
+) -> Diagnostic {
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::Lint("unused-parameter", Severity::WeakWarning),
+        format!("unused parameter `{}`", d.name),
+        d.expr,
+    )
+    .with_unused(true)
+}
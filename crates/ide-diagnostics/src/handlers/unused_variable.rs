@@ -0,0 +1,16 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext, Severity};
+
+pub(crate) use self::unused_variable as f;
+
+// Diagnostic: unused-variable
+//
+// This diagnostic is triggered if a local variable is never used in its function.
+pub(crate) fn unused_variable(ctx: &DiagnosticsContext<'_>, d: &hir::UnusedVariable) -> Diagnostic {
+    Diagnostic::new_with_syntax_node_ptr(
+        ctx,
+        DiagnosticCode::Lint("unused-variable", Severity::WeakWarning),
+        format!("unused variable `{}`", d.name),
+        d.expr,
+    )
+    .with_unused(true)
+}
@@ -0,0 +1,17 @@
+use crate::{Diagnostic, DiagnosticCode, DiagnosticsContext};
+
+pub(crate) use self::warning_directive as f;
+
+// Diagnostic: warning-directive
+//
+// This diagnostic is triggered if a `#warning` directive is reached in an active branch.
+pub(crate) fn warning_directive(
+    _ctx: &DiagnosticsContext<'_>,
+    d: &hir::WarningDirective,
+) -> Diagnostic {
+    Diagnostic::new(
+        DiagnosticCode::SpCompWarning("W0000"),
+        format!("user warning: {}", d.text),
+        d.range,
+    )
+}
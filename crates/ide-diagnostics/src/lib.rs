@@ -2,7 +2,7 @@ use base_db::Tree;
 use fxhash::FxHashSet;
 use hir::{AnyDiagnostic, Semantics};
 use hir_def::{DefDatabase, InFile, NodePtr};
-use ide_db::RootDatabase;
+use ide_db::{Assist, AssistId, AssistKind, RootDatabase, SourceChange};
 use queries::ERROR_QUERY;
 use syntax::utils::ts_range_to_lsp_range;
 use tree_sitter::QueryCursor;
@@ -35,9 +35,9 @@ pub struct Diagnostic {
     pub range: lsp_types::Range,
     pub severity: Severity,
     pub unused: bool,
+    pub deprecated: bool,
     pub experimental: bool,
-    // pub fixes: Option<Vec<Assist>>,
-    // The node that will be affected by `#[allow]` and similar attributes.
+    pub fixes: Option<Vec<Assist>>,
 }
 
 impl Diagnostic {
@@ -57,7 +57,9 @@ impl Diagnostic {
                 DiagnosticCode::Lint(_, s) => s,
             },
             unused: false,
+            deprecated: false,
             experimental: false,
+            fixes: None,
         }
     }
 
@@ -79,15 +81,20 @@ impl Diagnostic {
         self
     }
 
-    // fn with_fixes(mut self, fixes: Option<Vec<Assist>>) -> Diagnostic {
-    //     self.fixes = fixes;
-    //     self
-    // }
+    fn with_fixes(mut self, fixes: Option<Vec<Assist>>) -> Diagnostic {
+        self.fixes = fixes;
+        self
+    }
 
     fn with_unused(mut self, unused: bool) -> Diagnostic {
         self.unused = unused;
         self
     }
+
+    fn with_deprecated(mut self, deprecated: bool) -> Diagnostic {
+        self.deprecated = deprecated;
+        self
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -107,6 +114,8 @@ pub struct DiagnosticsConfig {
     /// Whether native diagnostics are enabled.
     pub enabled: bool,
     pub disable_experimental: bool,
+
+    /// Codes of the lints to disable.
     pub disabled: FxHashSet<String>,
 }
 
@@ -136,22 +145,90 @@ pub fn diagnostics(
                 handlers::unresolved_constructor::f(&ctx, &d)
             }
             AnyDiagnostic::UnresolvedNamedArg(d) => handlers::unresolved_named_arg::f(&ctx, &d),
+            AnyDiagnostic::UnresolvedIdent(d) => handlers::unresolved_ident::f(&ctx, &d),
             AnyDiagnostic::IncorrectNumberOfArguments(d) => {
                 handlers::incorrect_number_of_arguments::f(&ctx, &d)
             }
+            AnyDiagnostic::MismatchedAssignmentType(d) => {
+                handlers::mismatched_assignment_type::f(&ctx, &d)
+            }
+            AnyDiagnostic::MismatchedArgType(d) => handlers::mismatched_arg_type::f(&ctx, &d),
+            AnyDiagnostic::MismatchedReturnType(d) => {
+                handlers::mismatched_return_type::f(&ctx, &d)
+            }
             AnyDiagnostic::UnresolvedInherit(d) => handlers::unresolved_inherit::f(&ctx, &d),
             AnyDiagnostic::PreprocessorEvaluationError(d) => {
                 handlers::preprocessor_evaluation_error::f(&ctx, &d)
             }
+            AnyDiagnostic::ErrorDirective(d) => handlers::error_directive::f(&ctx, &d),
+            AnyDiagnostic::WarningDirective(d) => handlers::warning_directive::f(&ctx, &d),
+            AnyDiagnostic::FailedAssertion(d) => handlers::failed_assertion::f(&ctx, &d),
             AnyDiagnostic::UnresolvedMacro(d) => handlers::unresolved_macro::f(&ctx, &d),
             AnyDiagnostic::InactiveCode(d) => handlers::inactive_code::f(&ctx, &d),
+            AnyDiagnostic::UnusedVariable(d) => handlers::unused_variable::f(&ctx, &d),
+            AnyDiagnostic::UnusedParameter(d) => handlers::unused_parameter::f(&ctx, &d),
+            AnyDiagnostic::UnusedGlobal(d) => handlers::unused_global::f(&ctx, &d),
+            AnyDiagnostic::UnusedFunction(d) => handlers::unused_function::f(&ctx, &d),
+            AnyDiagnostic::UnusedInclude(d) => handlers::unused_include::f(&ctx, &d),
+            AnyDiagnostic::Deprecated(d) => handlers::deprecated::f(&ctx, &d),
         };
         res.push(d);
     }
 
+    // The errors and warnings that mirror the compiler's share their codes, so only lints can be
+    // disabled.
+    res.retain(|d| {
+        !matches!(d.code, DiagnosticCode::Lint(..)) || !config.disabled.contains(d.code.as_str())
+    });
+
     res
 }
 
+fn fix(
+    id: &'static str,
+    label: &str,
+    source_change: SourceChange,
+    target: lsp_types::Range,
+) -> Assist {
+    Assist {
+        id: AssistId(id, AssistKind::QuickFix),
+        label: label.to_string(),
+        target,
+        source_change,
+    }
+}
+
+/// Find the candidate closest to `name`, to suggest it in place of a misspelled name.
+///
+/// Candidates more than a third of the length of `name` away from it are ignored.
+fn closest_name(name: &str, candidates: impl IntoIterator<Item = String>) -> Option<String> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate != name)
+        .map(|candidate| (edit_distance(name, &candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by(|(a_distance, a), (b_distance, b)| a_distance.cmp(b_distance).then(a.cmp(b)))
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings, ignoring case.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.to_lowercase().chars().collect::<Vec<_>>();
+    let b = b.to_lowercase().chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+
+    prev[b.len()]
+}
+
 /// Capture all the syntax errors of a document and add them to its Local Diagnostics.
 /// Overrides all previous Local Diagnostics.
 ///
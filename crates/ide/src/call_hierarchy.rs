@@ -0,0 +1,243 @@
+//! Call hierarchy of functions, methods and natives.
+//!
+//! Calls are computed from the inferred bodies of the functions of the projects that include
+//! the file of the target. A forward declared in an include file is considered to call the
+//! functions of the plugins that implement it.
+
+use base_db::{FileExtension, FilePosition, SourceDatabaseExt};
+use hir::{DefResolution, File, FileDef, Function, FunctionKind, HasSource, Semantics};
+use hir_def::NodePtr;
+use ide_db::{RootDatabase, SymbolKind};
+use itertools::Itertools;
+use syntax::{utils::ts_range_to_lsp_range, TSKind};
+use vfs::FileId;
+
+use crate::{
+    goto_definition::find_inner_name_range,
+    references::{def_at_position, files_to_search},
+    s_range_to_u_range,
+};
+
+/// A function of the call hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub detail: Option<String>,
+    pub file_id: FileId,
+
+    /// Range of the whole declaration, seen by the user.
+    pub range: lsp_types::Range,
+
+    /// Range of the name of the declaration, seen by the user.
+    pub selection_range: lsp_types::Range,
+}
+
+/// A caller or a callee of a function, with the ranges of the calls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallItem {
+    pub target: CallHierarchyItem,
+
+    /// Ranges of the calls, seen by the user, in the file of the caller.
+    pub ranges: Vec<lsp_types::Range>,
+}
+
+pub(crate) fn call_hierarchy(db: &RootDatabase, pos: FilePosition) -> Option<CallHierarchyItem> {
+    let sema = &Semantics::new(db);
+    let DefResolution::Function(function) = def_at_position(sema, pos)?.info else {
+        return None;
+    };
+
+    call_hierarchy_item(sema, function)
+}
+
+pub(crate) fn incoming_calls(db: &RootDatabase, pos: FilePosition) -> Option<Vec<CallItem>> {
+    let sema = &Semantics::new(db);
+    let DefResolution::Function(target) = def_at_position(sema, pos)?.info else {
+        return None;
+    };
+
+    // The resolver can pick either the forward or its implementation for a call, they are
+    // considered to be the same function.
+    let mut targets = vec![target];
+    let forwards = forwards_of(db, target);
+    if is_inc_forward(db, target) {
+        targets.extend(implementations_of(db, target));
+    } else {
+        targets.extend(forwards.iter().copied());
+    }
+
+    let mut calls = Vec::new();
+    for file_id in files_to_search(db, &DefResolution::Function(target)) {
+        for caller in functions_in_file(db, file_id) {
+            for range in caller
+                .calls(db)
+                .into_iter()
+                .filter(|(callee, _)| targets.contains(callee))
+                .filter_map(|(_, ptr)| call_range(sema, ptr.file_id, ptr.value))
+            {
+                calls.push((caller, range));
+            }
+        }
+    }
+    for forward in forwards {
+        if let Some(range) = name_range(sema, forward) {
+            calls.push((forward, range));
+        }
+    }
+
+    Some(group_calls(sema, calls))
+}
+
+pub(crate) fn outgoing_calls(db: &RootDatabase, pos: FilePosition) -> Option<Vec<CallItem>> {
+    let sema = &Semantics::new(db);
+    let DefResolution::Function(caller) = def_at_position(sema, pos)?.info else {
+        return None;
+    };
+
+    let mut calls = caller
+        .calls(db)
+        .into_iter()
+        .filter_map(|(callee, ptr)| Some((callee, call_range(sema, ptr.file_id, ptr.value)?)))
+        .collect_vec();
+    if is_inc_forward(db, caller) {
+        if let Some(range) = name_range(sema, caller) {
+            calls.extend(
+                implementations_of(db, caller)
+                    .into_iter()
+                    .map(|implementation| (implementation, range)),
+            );
+        }
+    }
+
+    Some(group_calls(sema, calls))
+}
+
+fn call_hierarchy_item(
+    sema: &Semantics<RootDatabase>,
+    function: Function,
+) -> Option<CallHierarchyItem> {
+    let db = sema.db;
+    let def = DefResolution::Function(function);
+    let kind = SymbolKind::from_def(db, &def)?;
+    let file_id = def.file_id(db);
+    let tree = sema.parse(file_id);
+    let node = def.clone().source(db, &tree)?.value;
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let offsets = preprocessing_results.offsets();
+
+    Some(CallHierarchyItem {
+        name: function.name(db).to_string(),
+        kind,
+        detail: def.type_ref(db).map(|type_ref| type_ref.to_str()),
+        file_id,
+        range: s_range_to_u_range(offsets, ts_range_to_lsp_range(&node.range())),
+        selection_range: s_range_to_u_range(offsets, find_inner_name_range(&node)),
+    })
+}
+
+/// Group the calls by function, in the order of their first call.
+fn group_calls(
+    sema: &Semantics<RootDatabase>,
+    calls: Vec<(Function, lsp_types::Range)>,
+) -> Vec<CallItem> {
+    calls
+        .into_iter()
+        .sorted_by_key(|(_, range)| range.start)
+        .into_group_map_by(|(function, _)| *function)
+        .into_iter()
+        .filter_map(|(function, calls)| {
+            Some(CallItem {
+                target: call_hierarchy_item(sema, function)?,
+                ranges: calls.into_iter().map(|(_, range)| range).collect(),
+            })
+        })
+        .sorted_by(|a, b| {
+            (a.target.file_id, a.ranges.first().map(|range| range.start))
+                .cmp(&(b.target.file_id, b.ranges.first().map(|range| range.start)))
+        })
+        .collect()
+}
+
+/// Get the range of the name of the callee of a call, seen by the user.
+///
+/// Method and constructor calls are recorded on the name of the callee, function calls on the
+/// whole call expression.
+fn call_range(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+    ptr: NodePtr,
+) -> Option<lsp_types::Range> {
+    let tree = sema.parse(file_id);
+    let node = ptr.to_node(&tree);
+    let name = match TSKind::from(node) {
+        TSKind::call_expression => node.child_by_field_name("function")?,
+        _ => node,
+    };
+    let preprocessing_results = sema.preprocess_file(file_id);
+
+    Some(s_range_to_u_range(
+        preprocessing_results.offsets(),
+        ts_range_to_lsp_range(&name.range()),
+    ))
+}
+
+fn name_range(sema: &Semantics<RootDatabase>, function: Function) -> Option<lsp_types::Range> {
+    call_hierarchy_item(sema, function).map(|item| item.selection_range)
+}
+
+/// Get the functions, enum struct methods and methodmap methods declared in a file.
+pub(crate) fn functions_in_file(db: &RootDatabase, file_id: FileId) -> Vec<Function> {
+    File::from(file_id)
+        .declarations(db)
+        .into_iter()
+        .flat_map(|def| match def {
+            FileDef::Function(it) => vec![it],
+            FileDef::EnumStruct(it) => it.methods(db),
+            FileDef::Methodmap(it) => it.own_methods(db),
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+/// Whether a function is a forward declared in an include file, to be implemented by plugins.
+fn is_inc_forward(db: &RootDatabase, function: Function) -> bool {
+    function.kind(db) == FunctionKind::Forward && is_declared_in_inc(db, function)
+}
+
+/// Whether a function is declared in an include file.
+pub(crate) fn is_declared_in_inc(db: &RootDatabase, function: Function) -> bool {
+    let file_id = DefResolution::Function(function).file_id(db);
+    db.known_files()
+        .into_iter()
+        .any(|(id, extension)| id == file_id && extension == FileExtension::Inc)
+}
+
+/// Get the plugin functions that implement a forward.
+pub(crate) fn implementations_of(db: &RootDatabase, forward: Function) -> Vec<Function> {
+    let name = forward.name(db);
+    files_to_search(db, &DefResolution::Function(forward))
+        .into_iter()
+        .sorted()
+        .flat_map(|file_id| functions_in_file(db, file_id))
+        .filter(|function| {
+            function.kind(db) == FunctionKind::Def
+                && !function.is_method(db)
+                && function.name(db) == name
+        })
+        .collect()
+}
+
+/// Get the forwards declared in include files that a plugin function implements.
+fn forwards_of(db: &RootDatabase, function: Function) -> Vec<Function> {
+    if function.kind(db) != FunctionKind::Def || function.is_method(db) {
+        return Vec::new();
+    }
+    let name = function.name(db);
+    files_to_search(db, &DefResolution::Function(function))
+        .into_iter()
+        .sorted()
+        .flat_map(|file_id| functions_in_file(db, file_id))
+        .filter(|forward| is_inc_forward(db, *forward) && forward.name(db) == name)
+        .collect()
+}
@@ -0,0 +1,439 @@
+use base_db::{FilePosition, FileRange, SourceDatabase, SourceDatabaseExt};
+use fxhash::FxHashSet;
+use hir::{DefResolution, HasSource, Semantics, TypeRef};
+use ide_db::{Documentation, RootDatabase, SymbolKind};
+use lazy_static::lazy_static;
+use preprocessor::db::PreprocDatabase;
+use regex::Regex;
+use syntax::{
+    utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+    TSKind,
+};
+use vfs::{FileId, VfsPath};
+
+use crate::u_pos_to_s_pos;
+
+/// A single completion proposal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionItem {
+    /// Label in the completion pop up which identifies completion.
+    pub label: String,
+
+    /// What item (function, file, etc) this completion is for.
+    pub kind: CompletionKind,
+
+    /// Additional info to show in the UI pop up, like the declaration of a symbol.
+    pub detail: Option<String>,
+
+    /// Where the symbol comes from, usually the name of the file it is defined in.
+    pub description: Option<String>,
+
+    /// Text to insert instead of the label, if different.
+    pub insert_text: Option<String>,
+
+    /// Range of the definition of the symbol in the preprocessed file.
+    ///
+    /// Used to lazily resolve the documentation of the item.
+    pub data: Option<FileRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompletionKind {
+    SymbolKind(SymbolKind),
+    Keyword,
+    File,
+    Directory,
+}
+
+/// Keywords proposed along with the names in scope.
+const KEYWORDS: &[&str] = &[
+    "Float", "String", "any", "bool", "break", "case", "char", "const", "continue", "delete", "do",
+    "else", "enum", "false", "float", "for", "forward", "if", "int", "methodmap", "native", "new",
+    "null", "public", "return", "sizeof", "static", "stock", "switch", "this", "true", "view_as",
+    "void", "while",
+];
+
+impl From<SymbolKind> for CompletionKind {
+    fn from(kind: SymbolKind) -> Self {
+        CompletionKind::SymbolKind(kind)
+    }
+}
+
+pub(crate) fn completions(
+    db: &RootDatabase,
+    pos: FilePosition,
+    trigger_character: Option<char>,
+) -> Option<Vec<CompletionItem>> {
+    let sema = &Semantics::new(db);
+    let text = db.file_text(pos.file_id);
+    let line = text.lines().nth(pos.position.line as usize).unwrap_or_default();
+    let line_prefix = line.get(..pos.position.character as usize)?;
+
+    if let Some(items) = include_completions(db, pos.file_id, line_prefix) {
+        return Some(items);
+    }
+
+    let before_ident = line_prefix.trim_end_matches(|c: char| c.is_alphanumeric() || c == '_');
+    if let Some(receiver) = before_ident.strip_suffix('.') {
+        return member_completions(sema, pos, receiver, false);
+    }
+    if let Some(receiver) = before_ident.strip_suffix("::") {
+        return member_completions(sema, pos, receiver, true);
+    }
+    if trigger_character.is_some() {
+        // Trigger characters are only meaningful in includes and member accesses,
+        // don't pop up the whole scope when typing a comparison or a label.
+        return None;
+    }
+
+    let preprocessing_results = sema.preprocess_file(pos.file_id);
+    let mut position = pos.position;
+    u_pos_to_s_pos(
+        preprocessing_results.args_map(),
+        preprocessing_results.offsets(),
+        &mut position,
+    );
+    let tree = sema.parse(pos.file_id);
+    let point = lsp_position_to_ts_point(&position);
+    let node = tree.root_node().descendant_for_point_range(point, point)?;
+
+    let mut seen = FxHashSet::default();
+    let mut res = Vec::new();
+    sema.scope_at(pos.file_id, &node, point)
+        .process_all_names(&mut |name, def| {
+            if !seen.insert(name.to_string()) {
+                return;
+            }
+            res.extend(render_def(sema, name.to_string(), def));
+        });
+
+    // Macros are not scoped, add the ones the preprocessor knows about but that
+    // could not be reached through the item trees of the include graph.
+    for (name, macro_) in preprocessing_results.macros().iter() {
+        if !seen.insert(name.to_string()) {
+            continue;
+        }
+        if let Some(def) = sema.find_macro_def(macro_.file_id(), macro_.idx()) {
+            res.extend(render_def(sema, name.to_string(), DefResolution::Macro(def)));
+        }
+    }
+
+    res.extend(KEYWORDS.iter().map(|keyword| CompletionItem {
+        label: keyword.to_string(),
+        kind: CompletionKind::Keyword,
+        detail: None,
+        description: None,
+        insert_text: None,
+        data: None,
+    }));
+
+    Some(res)
+}
+
+/// Complete the members of the receiver of a `.` or `::` access.
+///
+/// # Arguments
+/// * `sema` - The [`Semantics`](Semantics) of the database.
+/// * `pos` - The position of the completion request.
+/// * `receiver_prefix` - The text of the line, up to the end of the receiver.
+/// * `is_scope_access` - Whether the receiver is accessed with `::`.
+fn member_completions(
+    sema: &Semantics<RootDatabase>,
+    pos: FilePosition,
+    receiver_prefix: &str,
+    is_scope_access: bool,
+) -> Option<Vec<CompletionItem>> {
+    let db = sema.db;
+    let receiver_prefix = receiver_prefix.trim_end();
+    let preprocessing_results = sema.preprocess_file(pos.file_id);
+    let mut position = lsp_types::Position::new(pos.position.line, receiver_prefix.len() as u32);
+    u_pos_to_s_pos(
+        preprocessing_results.args_map(),
+        preprocessing_results.offsets(),
+        &mut position,
+    );
+    let tree = sema.parse(pos.file_id);
+    let end = lsp_position_to_ts_point(&position);
+    let receiver = receiver_node(&tree.root_node(), end)?;
+    let scope = sema.scope_at(pos.file_id, &receiver, end);
+
+    let def = if is_scope_access {
+        sema.find_def(pos.file_id, &receiver)?
+    } else {
+        let Some(TypeRef::Name(type_name)) = sema.type_of_node(pos.file_id, &receiver) else {
+            return None;
+        };
+        scope.resolve_ident(&type_name.to_string())?
+    };
+
+    let mut members = Vec::new();
+    match def {
+        DefResolution::Methodmap(methodmap) => {
+            members.extend(
+                methodmap
+                    .properties(db)
+                    .into_iter()
+                    .map(|it| (it.name(db), DefResolution::Property(it))),
+            );
+            members.extend(
+                methodmap
+                    .methods(db)
+                    .into_iter()
+                    .map(|it| (it.name(db), DefResolution::Function(it))),
+            );
+        }
+        DefResolution::EnumStruct(enum_struct) => {
+            members.extend(
+                enum_struct
+                    .fields(db)
+                    .into_iter()
+                    .filter_map(|it| Some((it.name(db)?, DefResolution::Field(it)))),
+            );
+            members.extend(
+                enum_struct
+                    .methods(db)
+                    .into_iter()
+                    .map(|it| (it.name(db), DefResolution::Function(it))),
+            );
+        }
+        DefResolution::Enum(enum_) if is_scope_access => {
+            members.extend(
+                enum_
+                    .variants(db)
+                    .into_iter()
+                    .map(|it| (it.name(db), DefResolution::Variant(it))),
+            );
+        }
+        _ => return None,
+    }
+
+    Some(
+        members
+            .into_iter()
+            .filter_map(|(name, def)| render_def(sema, name.to_string(), def))
+            .collect(),
+    )
+}
+
+/// Find the receiver of a member access, given the position of its end.
+///
+/// The receiver is the outer-most postfix expression that ends at that position,
+/// e.g. `foo.bar()` in `foo.bar().`.
+fn receiver_node<'tree>(
+    root: &tree_sitter::Node<'tree>,
+    end: tree_sitter::Point,
+) -> Option<tree_sitter::Node<'tree>> {
+    let start = tree_sitter::Point::new(end.row, end.column.checked_sub(1)?);
+    let mut node = root.descendant_for_point_range(start, start)?;
+    let mut receiver = None;
+    while node.end_position() == end {
+        if matches!(
+            TSKind::from(node),
+            TSKind::identifier
+                | TSKind::this
+                | TSKind::field_access
+                | TSKind::call_expression
+                | TSKind::array_indexed_access
+                | TSKind::scope_access
+                | TSKind::view_as
+                | TSKind::parenthesized_expression
+        ) {
+            receiver = Some(node);
+        } else if receiver.is_some() {
+            break;
+        }
+        match node.parent() {
+            Some(parent) => node = parent,
+            None => break,
+        }
+    }
+
+    receiver
+}
+
+/// Resolve the documentation of a completion item from the range of its definition.
+pub(crate) fn resolve_completion(db: &RootDatabase, frange: FileRange) -> Option<Documentation> {
+    let sema = &Semantics::new(db);
+    let tree = sema.parse(frange.file_id);
+    let text = db.preprocessed_text(frange.file_id);
+    let mut node = tree.root_node().descendant_for_point_range(
+        lsp_position_to_ts_point(&frange.range.start),
+        lsp_position_to_ts_point(&frange.range.end),
+    )?;
+    // Several nodes can span the same range, the documentation is attached to the outer-most one.
+    while let Some(parent) = node.parent() {
+        if TSKind::from(parent) == TSKind::source_file
+            || ts_range_to_lsp_range(&parent.range()) != frange.range
+        {
+            break;
+        }
+        node = parent;
+    }
+
+    Documentation::from_node(node, text.as_bytes()).filter(|docs| !docs.as_str().is_empty())
+}
+
+fn render_def(
+    sema: &Semantics<RootDatabase>,
+    label: String,
+    def: DefResolution,
+) -> Option<CompletionItem> {
+    let db = sema.db;
+    let kind = SymbolKind::from_def(db, &def)?;
+    let file_id = def.file_id(db);
+    let tree = sema.parse(file_id);
+    let node = def.source(db, &tree)?.value;
+    let text = db.preprocessed_text(file_id);
+
+    Some(CompletionItem {
+        label,
+        kind: kind.into(),
+        detail: render_detail(&node, text.as_bytes()),
+        description: file_name(db, file_id),
+        insert_text: None,
+        data: Some(FileRange {
+            file_id,
+            range: ts_range_to_lsp_range(&node.range()),
+        }),
+    })
+}
+
+/// Render the declaration of a definition node, without its body.
+fn render_detail(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let text = node.utf8_text(source).ok()?;
+    let mut detail = match node.child_by_field_name("body") {
+        Some(body) => &text[..body.start_byte() - node.start_byte()],
+        None => text.split('{').next().unwrap_or(text),
+    }
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    if matches!(
+        TSKind::from(node),
+        TSKind::variable_declaration | TSKind::old_variable_declaration
+    ) {
+        if let Some(type_node) = node
+            .parent()
+            .and_then(|parent| parent.child_by_field_name("type"))
+        {
+            detail = format!("{} {};", type_node.utf8_text(source).ok()?, detail);
+        }
+    }
+
+    Some(detail)
+}
+
+fn file_name(db: &RootDatabase, file_id: FileId) -> Option<String> {
+    let source_root = db.source_root(db.file_source_root(file_id));
+    let (name, extension) = source_root.path_for_file(&file_id)?.name_and_extension()?;
+    match extension {
+        Some(extension) => Some(format!("{}.{}", name, extension)),
+        None => Some(name.to_string()),
+    }
+}
+
+/// Complete the path of an `#include` or `#tryinclude` directive.
+fn include_completions(
+    db: &RootDatabase,
+    file_id: FileId,
+    line_prefix: &str,
+) -> Option<Vec<CompletionItem>> {
+    lazy_static! {
+        static ref RE_INCLUDE: Regex =
+            Regex::new(r#"^\s*#\s*(?:try)?include\s*([<"])([^>"]*)$"#).unwrap();
+    }
+    let captures = RE_INCLUDE.captures(line_prefix)?;
+    let is_chevron = &captures[1] == "<";
+    let typed_path = captures[2].replace('\\', "/");
+    let typed_dir = match typed_path.rfind('/') {
+        Some(idx) => &typed_path[..idx],
+        None => "",
+    };
+
+    let file_path = db
+        .source_root(db.file_source_root(file_id))
+        .path_for_file(&file_id)?
+        .clone();
+    let current_dir = file_path.parent()?;
+    let mut base_dirs = vec![current_dir.clone()];
+    if let Some(subgraph) = db.projet_subgraph(file_id) {
+        let root_path = db
+            .source_root(db.file_source_root(subgraph.root.file_id))
+            .path_for_file(&subgraph.root.file_id)
+            .and_then(VfsPath::parent);
+        base_dirs.extend(root_path);
+    }
+    if is_chevron {
+        base_dirs.extend(current_dir.join("include"));
+        base_dirs.extend(
+            db.source_roots()
+                .iter()
+                .filter(|source_root| source_root.is_include_dir)
+                .map(|source_root| source_root.root().clone()),
+        );
+    }
+
+    let mut seen = FxHashSet::default();
+    let mut res = Vec::new();
+    for base_dir in base_dirs {
+        let dir = if typed_dir.is_empty() {
+            base_dir
+        } else {
+            match base_dir.join(typed_dir) {
+                Some(dir) => dir,
+                None => continue,
+            }
+        };
+        for source_root in db.source_roots().iter() {
+            for path in source_root.iter().filter_map(|id| source_root.path_for_file(&id)) {
+                let Some(rel_path) = path.strip_prefix(&dir) else {
+                    continue;
+                };
+                let mut components = AsRef::<std::path::Path>::as_ref(rel_path).components();
+                let Some(first) = components.next().and_then(|it| it.as_os_str().to_str()) else {
+                    continue;
+                };
+                if components.next().is_some() {
+                    if !seen.insert(first.to_string()) {
+                        continue;
+                    }
+                    res.push(CompletionItem {
+                        label: first.to_string(),
+                        kind: CompletionKind::Directory,
+                        detail: dir.join(first).map(|it| it.to_string()),
+                        description: None,
+                        insert_text: None,
+                        data: None,
+                    });
+                    continue;
+                }
+                let Some((name, extension)) = path.name_and_extension() else {
+                    continue;
+                };
+                let label = match extension {
+                    Some("inc") | None => name.to_string(),
+                    Some(extension) => format!("{}.{}", name, extension),
+                };
+                if !seen.insert(label.clone()) {
+                    continue;
+                }
+                let insert_text = if is_chevron {
+                    format!("{}>", label)
+                } else {
+                    label.clone()
+                };
+                res.push(CompletionItem {
+                    label,
+                    kind: CompletionKind::File,
+                    detail: Some(path.to_string()),
+                    description: None,
+                    insert_text: Some(insert_text),
+                    data: None,
+                });
+            }
+        }
+    }
+
+    Some(res)
+}
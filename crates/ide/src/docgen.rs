@@ -0,0 +1,248 @@
+//! API reference of the items declared in a file, used to generate the documentation of include
+//! files.
+
+use base_db::{SourceDatabaseExt, Tree};
+use fxhash::FxHashMap;
+use hir::{DefResolution, File, FileDef, HasSource, Semantics};
+use ide_db::{Documentation, RootDatabase, SymbolKind};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use preprocessor::{db::PreprocDatabase, Offset};
+use regex::Regex;
+use syntax::{
+    utils::{deprecation_note, point_to_lsp_position},
+    TSKind,
+};
+use vfs::FileId;
+
+use crate::s_range_to_u_range;
+
+/// The documentation of an item, with the items it contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemDoc {
+    pub name: String,
+    pub kind: SymbolKind,
+
+    /// Source of the declaration, without its body.
+    pub signature: String,
+
+    /// Description of the item, in markdown.
+    pub description: String,
+
+    /// Names of the parameters, with their description in markdown.
+    pub params: Vec<(String, String)>,
+
+    /// Description of the return value, in markdown.
+    pub returns: Option<String>,
+
+    /// Note of the `#pragma deprecated` directive of the item, if it is deprecated.
+    pub deprecated: Option<String>,
+    pub children: Vec<ItemDoc>,
+}
+
+pub(crate) fn file_docs(db: &RootDatabase, file_id: FileId) -> Vec<ItemDoc> {
+    let sema = &Semantics::new(db);
+    let tree = sema.parse(file_id);
+    let source = db.preprocessed_text(file_id);
+    let text = db.file_text(file_id);
+    let preprocessing_results = db.preprocess_file(file_id);
+    let ctx = DocCtx {
+        db,
+        tree: &tree,
+        source: source.as_bytes(),
+        text: &text,
+        offsets: preprocessing_results.offsets(),
+    };
+
+    File::from(file_id)
+        .declarations(db)
+        .into_iter()
+        // Variants are declared at the top level, but belong to their enum. Globals are not part
+        // of the API of an include file.
+        .filter(|def| !matches!(def, FileDef::Variant(_) | FileDef::Global(_)))
+        .filter_map(|def| ctx.item_doc(DefResolution::from(def)))
+        .collect()
+}
+
+struct DocCtx<'a> {
+    db: &'a RootDatabase,
+    tree: &'a Tree,
+
+    /// Preprocessed text of the file, which the tree was parsed from.
+    source: &'a [u8],
+
+    /// Text of the file, as written by the user.
+    text: &'a str,
+    offsets: &'a FxHashMap<u32, Vec<Offset>>,
+}
+
+impl DocCtx<'_> {
+    fn item_doc(&self, def: DefResolution) -> Option<ItemDoc> {
+        let db = self.db;
+        let kind = SymbolKind::from_def(db, &def)?;
+        let node = def.clone().source(db, self.tree)?.value;
+        let name = def_name(db, &def)?;
+        let (description, params, returns) = Documentation::from_node(node, self.source)
+            .map(|docs| split_docs(docs.as_str()))
+            .unwrap_or_default();
+        let children = match def {
+            DefResolution::Methodmap(it) => it.own_items(db),
+            DefResolution::EnumStruct(it) => it
+                .fields(db)
+                .into_iter()
+                .map(DefResolution::Field)
+                .chain(it.methods(db).into_iter().map(DefResolution::Function))
+                .collect(),
+            DefResolution::Enum(it) => it
+                .variants(db)
+                .into_iter()
+                .map(DefResolution::Variant)
+                .collect(),
+            DefResolution::Typeset(it) => it
+                .typedefs(db)
+                .into_iter()
+                .map(DefResolution::Typedef)
+                .collect(),
+            DefResolution::Funcenum(it) => it
+                .functags(db)
+                .into_iter()
+                .map(DefResolution::Functag)
+                .collect(),
+            _ => Vec::new(),
+        }
+        .into_iter()
+        .filter_map(|child| self.item_doc(child))
+        .collect();
+
+        Some(ItemDoc {
+            name,
+            kind,
+            signature: self.signature(node)?,
+            description,
+            params,
+            returns,
+            deprecated: deprecation_note(node, self.source),
+            children,
+        })
+    }
+
+    /// Get the source of a declaration as written by the user, without the body of functions and
+    /// the items of containers.
+    ///
+    /// The source is taken from the text of the file rather than the preprocessed text, so that
+    /// the macros used in the declaration are not expanded.
+    fn signature(&self, node: tree_sitter::Node) -> Option<String> {
+        let s_text = node.utf8_text(self.source).ok()?;
+        let s_end = match node.child_by_field_name("body") {
+            Some(body) => body.start_position(),
+            None => match TSKind::from(node) {
+                TSKind::methodmap
+                | TSKind::methodmap_property
+                | TSKind::enum_struct
+                | TSKind::r#enum
+                | TSKind::typeset
+                | TSKind::funcenum => match s_text.find('{') {
+                    Some(end) => end_point(node.start_position(), &s_text[..end]),
+                    None => node.end_position(),
+                },
+                _ => node.end_position(),
+            },
+        };
+        let u_pos = |point: &tree_sitter::Point| {
+            let s_pos = point_to_lsp_position(point);
+            s_range_to_u_range(self.offsets, lsp_types::Range::new(s_pos, s_pos)).start
+        };
+        let text = text_range(self.text, u_pos(&node.start_position()), u_pos(&s_end))?;
+
+        Some(text.lines().map(str::trim_end).join("\n").trim().to_string())
+    }
+}
+
+/// The point at the end of `text`, which starts at `start`.
+fn end_point(start: tree_sitter::Point, text: &str) -> tree_sitter::Point {
+    match text.rfind('\n') {
+        Some(last_newline) => tree_sitter::Point {
+            row: start.row + text.matches('\n').count(),
+            column: text.len() - last_newline - 1,
+        },
+        None => tree_sitter::Point {
+            row: start.row,
+            column: start.column + text.len(),
+        },
+    }
+}
+
+/// Get the text between two positions of a file, whose columns are byte offsets in their line.
+fn text_range(text: &str, start: lsp_types::Position, end: lsp_types::Position) -> Option<&str> {
+    let offset = |pos: lsp_types::Position| {
+        let line_start: usize = text
+            .split_inclusive('\n')
+            .take(pos.line as usize)
+            .map(str::len)
+            .sum();
+        line_start + pos.character as usize
+    };
+
+    text.get(offset(start)..offset(end))
+}
+
+fn def_name(db: &RootDatabase, def: &DefResolution) -> Option<String> {
+    let name = match def {
+        DefResolution::Function(it) => it.name(db),
+        DefResolution::Macro(it) => it.name(db),
+        DefResolution::EnumStruct(it) => it.name(db),
+        DefResolution::Methodmap(it) => it.name(db),
+        DefResolution::Property(it) => it.name(db),
+        DefResolution::Enum(it) => it.name(db),
+        DefResolution::Variant(it) => it.name(db),
+        DefResolution::Typedef(it) => it.name(db)?,
+        DefResolution::Typeset(it) => it.name(db),
+        DefResolution::Functag(it) => it.name(db)?,
+        DefResolution::Funcenum(it) => it.name(db),
+        DefResolution::Field(it) => it.name(db)?,
+        DefResolution::Global(it) => it.name(db),
+        DefResolution::Local(_) | DefResolution::File(_) => return None,
+    };
+
+    Some(name.to_string())
+}
+
+/// Split the documentation of an item in its description, the description of its parameters and
+/// the description of its return value, all in markdown.
+///
+/// Tags other than `@param` and `@return` are kept in the description.
+fn split_docs(docs: &str) -> (String, Vec<(String, String)>, Option<String>) {
+    lazy_static! {
+        static ref TAG_RE: Regex = Regex::new(r"(?m)^[ \t*]*@([A-Za-z]+)\b[ \t]*").unwrap();
+    }
+    let to_markdown = |text: &str| {
+        Documentation::new(text.trim().to_string())
+            .to_markdown()
+            .trim()
+            .to_string()
+    };
+
+    let tags = TAG_RE.captures_iter(docs).collect_vec();
+    let description_end = tags
+        .first()
+        .map_or(docs.len(), |tag| tag.get(0).unwrap().start());
+    let mut description = docs[..description_end].to_string();
+    let mut params = Vec::new();
+    let mut returns = None;
+    for (i, tag) in tags.iter().enumerate() {
+        let start = tag.get(0).unwrap();
+        let end = tags.get(i + 1).map_or(docs.len(), |next| next.get(0).unwrap().start());
+        let text = &docs[start.end()..end];
+        match &tag[1] {
+            "param" => {
+                let text = text.trim();
+                let (name, text) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+                params.push((name.trim().to_string(), to_markdown(text)));
+            }
+            "return" | "returns" => returns = Some(to_markdown(text)),
+            _ => description.push_str(&docs[start.start()..end]),
+        }
+    }
+
+    (to_markdown(&description), params, returns)
+}
@@ -0,0 +1,141 @@
+//! Occurrences of the symbol under the cursor in the current file.
+//!
+//! Occurrences in a function body are categorized from the expression they are lowered to: the
+//! place written by an assignment or an update, and the name of a binding, are writes, anything
+//! else is a read. Outside of the bodies, only the names of the global variables are writes.
+
+use base_db::{FilePosition, SourceDatabaseExt};
+use hir::{DefResolution, HasSource, Semantics};
+use ide_db::RootDatabase;
+use syntax::{
+    utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+    TSKind,
+};
+use vfs::FileId;
+
+use crate::{
+    goto_definition::find_inner_name_range,
+    references::{def_at_position, find_macro_refs_in_file, s_range_to_user_range},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceCategory {
+    Read,
+    Write,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighlightedRange {
+    /// Range of the occurrence, seen by the user.
+    pub range: lsp_types::Range,
+
+    /// Category of the occurrence, `None` for the declaration of a symbol that is not a binding.
+    pub category: Option<ReferenceCategory>,
+}
+
+pub(crate) fn highlight_related(
+    db: &RootDatabase,
+    pos: FilePosition,
+) -> Option<Vec<HighlightedRange>> {
+    let sema = &Semantics::new(db);
+    let def = def_at_position(sema, pos)?.info;
+    let file_id = pos.file_id;
+    let name = def_name(sema, &def)?;
+    let source = sema.preprocessed_text(file_id);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let text = db.file_text(file_id);
+    let tree = sema.parse(file_id);
+
+    let mut res = Vec::new();
+    if let DefResolution::Macro(_) = def {
+        res.extend(
+            find_macro_refs_in_file(sema, &def, file_id)
+                .into_iter()
+                .map(|range| HighlightedRange {
+                    range,
+                    category: Some(ReferenceCategory::Read),
+                }),
+        );
+    }
+    let mut cursor = tree.root_node().walk();
+    'outer: loop {
+        let node = cursor.node();
+        if matches!(
+            TSKind::from(node),
+            TSKind::identifier | TSKind::r#type | TSKind::this
+        ) && node.utf8_text(source.as_bytes()).ok() == Some(name.as_str())
+            && sema.find_def(file_id, &node).as_ref() == Some(&def)
+        {
+            // Occurrences that only exist in the expansion of a macro are not highlighted.
+            let s_range = ts_range_to_lsp_range(&node.range());
+            if let Some(range) =
+                s_range_to_user_range(&preprocessing_results, &text, s_range, &name)
+            {
+                res.push(HighlightedRange {
+                    range,
+                    category: category(sema, file_id, node),
+                });
+            }
+        }
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+    res.sort_by_key(|highlight| highlight.range.start);
+    res.dedup_by_key(|highlight| highlight.range);
+
+    Some(res)
+}
+
+/// Get the name of a definition, as written in its declaration.
+fn def_name(sema: &Semantics<RootDatabase>, def: &DefResolution) -> Option<String> {
+    let file_id = def.file_id(sema.db);
+    let tree = sema.parse(file_id);
+    let node = def.clone().source(sema.db, &tree)?.value;
+    let range = find_inner_name_range(&node);
+    let name = node
+        .descendant_for_point_range(
+            lsp_position_to_ts_point(&range.start),
+            lsp_position_to_ts_point(&range.end),
+        )?
+        .utf8_text(sema.preprocessed_text(file_id).as_bytes())
+        .ok()?
+        .to_string();
+
+    Some(name)
+}
+
+/// Categorize an occurrence of a symbol.
+fn category(
+    sema: &Semantics<RootDatabase>,
+    file_id: FileId,
+    node: tree_sitter::Node,
+) -> Option<ReferenceCategory> {
+    if let Some(is_write) = sema.is_write(file_id, &node) {
+        return Some(if is_write {
+            ReferenceCategory::Write
+        } else {
+            ReferenceCategory::Read
+        });
+    }
+
+    let parent = node.parent()?;
+    if parent.child_by_field_name("name") == Some(node) {
+        // Outside of the bodies, only the names of the global variables are written to.
+        return matches!(
+            TSKind::from(parent),
+            TSKind::variable_declaration | TSKind::old_variable_declaration
+        )
+        .then_some(ReferenceCategory::Write);
+    }
+
+    Some(ReferenceCategory::Read)
+}
@@ -0,0 +1,210 @@
+//! Folding ranges of blocks and bodies, multi-line comments, groups of includes, conditional
+//! directives, inactive code, and `// #pragma region` markers.
+//!
+//! Directives are blanked out of the preprocessed text, they are read from the user's text.
+
+use base_db::SourceDatabaseExt;
+use hir::Semantics;
+use ide_db::RootDatabase;
+use syntax::{utils::ts_range_to_lsp_range, TSKind};
+use vfs::FileId;
+
+use crate::s_range_to_u_range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldKind {
+    /// Braced body of a statement or a declaration.
+    Block,
+
+    /// Multi-line comment.
+    Comment,
+
+    /// Consecutive `#include` and `#tryinclude` directives.
+    Includes,
+
+    /// Region delimited by `// #pragma region` and `// #pragma endregion` comments.
+    Region,
+
+    /// Branch of an `#if`, `#elseif` or `#else` directive.
+    Conditional,
+
+    /// Code disabled by the preprocessor.
+    InactiveCode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fold {
+    /// Range of the fold, seen by the user.
+    pub range: lsp_types::Range,
+    pub kind: FoldKind,
+}
+
+pub(crate) fn folding_ranges(db: &RootDatabase, file_id: FileId) -> Vec<Fold> {
+    let sema = &Semantics::new(db);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let offsets = preprocessing_results.offsets();
+    let inactive_ranges = preprocessing_results.inactive_ranges();
+    let source = sema.preprocessed_text(file_id);
+    let tree = sema.parse(file_id);
+
+    let mut res = Vec::new();
+    let mut regions = Vec::new();
+    let mut cursor = tree.root_node().walk();
+    'outer: loop {
+        let node = cursor.node();
+        let range = s_range_to_u_range(offsets, ts_range_to_lsp_range(&node.range()));
+        match TSKind::from(node) {
+            TSKind::block
+            | TSKind::methodmap
+            | TSKind::methodmap_property
+            | TSKind::enum_struct
+            | TSKind::r#enum
+            | TSKind::r#struct
+            | TSKind::typeset
+            | TSKind::funcenum
+            | TSKind::switch_statement => res.push(Fold {
+                range,
+                kind: FoldKind::Block,
+            }),
+            TSKind::comment => {
+                let text = node.utf8_text(source.as_bytes()).unwrap_or_default();
+                match region_marker(text) {
+                    Some(true) => regions.push(range.start),
+                    Some(false) => {
+                        if let Some(start) = regions.pop() {
+                            res.push(Fold {
+                                range: lsp_types::Range::new(start, range.end),
+                                kind: FoldKind::Region,
+                            });
+                        }
+                    }
+                    None => res.push(Fold {
+                        range,
+                        kind: FoldKind::Comment,
+                    }),
+                }
+            }
+            _ => (),
+        }
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    directive_folds(&db.file_text(file_id), inactive_ranges, &mut res);
+    res.extend(inactive_ranges.iter().map(|range| Fold {
+        range: *range,
+        kind: FoldKind::InactiveCode,
+    }));
+
+    res.retain(|fold| fold.range.start.line < fold.range.end.line);
+    res.sort_by_key(|fold| (fold.range.start, fold.range.end));
+
+    res
+}
+
+/// Fold the groups of includes and the branches of the conditional directives.
+///
+/// The branches that start with an inactive range are already folded as inactive code.
+fn directive_folds(text: &str, inactive_ranges: &[lsp_types::Range], acc: &mut Vec<Fold>) {
+    let is_inactive = |line: u32| {
+        inactive_ranges
+            .iter()
+            .any(|range| range.start.line <= line && line <= range.end.line)
+    };
+    let branch = |start: u32, end: u32, acc: &mut Vec<Fold>| {
+        if end > start && !inactive_ranges.iter().any(|range| range.start.line == start) {
+            acc.push(Fold {
+                range: line_range(text, start, end - 1),
+                kind: FoldKind::Conditional,
+            });
+        }
+    };
+
+    let mut conditions = Vec::new();
+    let mut includes: Option<(u32, u32)> = None;
+    for (idx, line) in text.lines().enumerate() {
+        let idx = idx as u32;
+        let directive = directive_name(line);
+        let is_include = matches!(directive, Some("include" | "tryinclude")) && !is_inactive(idx);
+        if !is_include {
+            if let Some((start, end)) = includes.take() {
+                acc.push(Fold {
+                    range: line_range(text, start, end),
+                    kind: FoldKind::Includes,
+                });
+            }
+        }
+        match directive {
+            _ if is_include => {
+                includes = Some((includes.map_or(idx, |(start, _)| start), idx));
+            }
+            Some("if") => conditions.push(idx),
+            Some("elseif" | "else") => {
+                if let Some(start) = conditions.pop() {
+                    branch(start, idx, acc);
+                    conditions.push(idx);
+                }
+            }
+            Some("endif") => {
+                if let Some(start) = conditions.pop() {
+                    branch(start, idx, acc);
+                }
+            }
+            _ => (),
+        }
+    }
+    if let Some((start, end)) = includes {
+        acc.push(Fold {
+            range: line_range(text, start, end),
+            kind: FoldKind::Includes,
+        });
+    }
+}
+
+/// Get the name of the directive of a line, e.g. `include` for `#include <sourcemod>`.
+fn directive_name(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix('#')?.trim_start();
+    let end = rest
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(rest.len());
+
+    Some(&rest[..end])
+}
+
+/// Get the range from the start of the line `start` to the end of the line `end`.
+fn line_range(text: &str, start: u32, end: u32) -> lsp_types::Range {
+    let end_character = text
+        .lines()
+        .nth(end as usize)
+        .map_or(0, |line| line.chars().count() as u32);
+
+    lsp_types::Range::new(
+        lsp_types::Position::new(start, 0),
+        lsp_types::Position::new(end, end_character),
+    )
+}
+
+/// Returns `Some(true)` if the comment opens a region, `Some(false)` if it closes one.
+///
+/// `// #pragma region`, `// #region` and `// region` are all accepted, as well as their
+/// `endregion` counterparts.
+fn region_marker(comment: &str) -> Option<bool> {
+    let text = comment.strip_prefix("//")?.trim_start();
+    let text = text.strip_prefix("#pragma").unwrap_or(text).trim_start();
+    let text = text.strip_prefix('#').unwrap_or(text);
+    let word = text.split_whitespace().next()?;
+    match word {
+        "region" => Some(true),
+        "endregion" => Some(false),
+        _ => None,
+    }
+}
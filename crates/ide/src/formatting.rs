@@ -0,0 +1,124 @@
+//! Formatting of whole files, ranges of lines, and of the lines of the construct that was just
+//! closed while typing.
+//!
+//! The tree of a file is parsed from its preprocessed text. The lines whose preprocessed text
+//! differs from the user's text are protected, so that the edits of the formatter can be applied
+//! to the user's text.
+
+use base_db::{FilePosition, SourceDatabaseExt};
+use formatter::{FormattingConfig, ProtectedLines};
+use hir::Semantics;
+use ide_db::RootDatabase;
+use preprocessor::db::PreprocDatabase;
+use syntax::{utils::lsp_position_to_ts_point, TSKind};
+use vfs::FileId;
+
+use crate::u_pos_to_s_pos;
+
+pub(crate) fn format(
+    db: &RootDatabase,
+    file_id: FileId,
+    config: &FormattingConfig,
+    range: Option<lsp_types::Range>,
+) -> Option<Vec<lsp_types::TextEdit>> {
+    let sema = &Semantics::new(db);
+    let tree = sema.parse(file_id);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let source = db.preprocessed_text(file_id);
+    let protected = protected_lines(
+        &db.file_text(file_id),
+        &source,
+        preprocessing_results.inactive_ranges(),
+    );
+
+    formatter::format(tree.tree(), &source, &protected, config, range)
+}
+
+/// Format the lines of the block closed by a `}`, or the line ended by a `;`, that was just
+/// typed before `pos`.
+pub(crate) fn format_on_type(
+    db: &RootDatabase,
+    mut pos: FilePosition,
+    ch: char,
+    config: &FormattingConfig,
+) -> Option<Vec<lsp_types::TextEdit>> {
+    let (start_line, end_line) = match ch {
+        '}' => {
+            let sema = &Semantics::new(db);
+            let preprocessing_results = sema.preprocess_file(pos.file_id);
+            u_pos_to_s_pos(
+                preprocessing_results.args_map(),
+                preprocessing_results.offsets(),
+                &mut pos.position,
+            );
+            pos.position.character = pos.position.character.checked_sub(1)?;
+            let tree = sema.parse(pos.file_id);
+            let point = lsp_position_to_ts_point(&pos.position);
+            let brace = tree.root_node().descendant_for_point_range(point, point)?;
+            if TSKind::from(brace) != TSKind::anon_RBRACE {
+                return None;
+            }
+            let body = brace.parent()?;
+            // Include the declaration or statement of the body, for the opening brace.
+            let node = body
+                .parent()
+                .filter(|parent| TSKind::from(parent) != TSKind::source_file)
+                .unwrap_or(body);
+            (node.start_position().row as u32, node.end_position().row as u32)
+        }
+        ';' => (pos.position.line, pos.position.line),
+        _ => return None,
+    };
+
+    format(
+        db,
+        pos.file_id,
+        config,
+        Some(lsp_types::Range::new(
+            lsp_types::Position::new(start_line, 0),
+            lsp_types::Position::new(end_line, 0),
+        )),
+    )
+}
+
+/// Compute the lines of the preprocessed text that must be kept as they are in the user's text.
+///
+/// Directives and inactive regions are left untouched. Lines with an expanded macro can only be
+/// re-indented, if their indentation was not changed by the expansion.
+fn protected_lines(
+    text: &str,
+    preprocessed_text: &str,
+    inactive_ranges: &[lsp_types::Range],
+) -> ProtectedLines {
+    let mut res = ProtectedLines::default();
+    let preprocessed_lines = preprocessed_text.lines().collect::<Vec<_>>();
+    let mut continued_directive = false;
+    for (idx, line) in text.lines().enumerate() {
+        let preprocessed_line = preprocessed_lines.get(idx).copied().unwrap_or_default();
+        let is_directive = continued_directive || line.trim_start().starts_with('#');
+        continued_directive = is_directive && line.trim_end().ends_with('\\');
+        if is_directive {
+            res.verbatim.insert(idx as u32);
+            continue;
+        }
+        if line == preprocessed_line {
+            continue;
+        }
+        if !preprocessed_line.trim().is_empty()
+            && indentation(line) == indentation(preprocessed_line)
+        {
+            res.indent_only.insert(idx as u32);
+        } else {
+            res.verbatim.insert(idx as u32);
+        }
+    }
+    for range in inactive_ranges {
+        res.verbatim.extend(range.start.line..=range.end.line);
+    }
+
+    res
+}
+
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
@@ -0,0 +1,55 @@
+//! Declarations of functions, i.e. the forwards and natives of the include files that they
+//! implement.
+//!
+//! Falls back to the definition when there is no such declaration.
+
+use base_db::FilePosition;
+use hir::{DefResolution, Function, FunctionKind, Semantics};
+use ide_db::RootDatabase;
+use itertools::Itertools;
+
+use crate::{
+    call_hierarchy::{functions_in_file, is_declared_in_inc},
+    goto_definition::{goto_definition, NavigationTarget},
+    references::{def_at_position, files_to_search},
+    RangeInfo,
+};
+
+pub(crate) fn goto_declaration(
+    db: &RootDatabase,
+    pos: FilePosition,
+) -> Option<RangeInfo<Vec<NavigationTarget>>> {
+    let sema = &Semantics::new(db);
+    let RangeInfo { range, info: def } = def_at_position(sema, pos)?;
+    let navs = match def {
+        DefResolution::Function(function) => prototypes_of(db, function)
+            .into_iter()
+            .filter_map(|it| NavigationTarget::from_def(sema, DefResolution::Function(it)))
+            .collect_vec(),
+        _ => Vec::new(),
+    };
+    if navs.is_empty() {
+        return goto_definition(db, pos);
+    }
+
+    Some(RangeInfo::new(range, navs))
+}
+
+/// Get the forwards and natives declared in include files that a plugin function implements.
+fn prototypes_of(db: &RootDatabase, function: Function) -> Vec<Function> {
+    if function.kind(db) != FunctionKind::Def || function.is_method(db) {
+        return Vec::new();
+    }
+    let name = function.name(db);
+    files_to_search(db, &DefResolution::Function(function))
+        .into_iter()
+        .sorted()
+        .flat_map(|file_id| functions_in_file(db, file_id))
+        .filter(|prototype| {
+            prototype.kind(db) != FunctionKind::Def
+                && !prototype.is_method(db)
+                && prototype.name(db) == name
+                && is_declared_in_inc(db, *prototype)
+        })
+        .collect()
+}
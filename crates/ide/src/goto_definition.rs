@@ -55,21 +55,32 @@ pub(crate) fn goto_definition(
         None => s_range_to_u_range(offsets, ts_range_to_lsp_range(&node.range())),
     };
 
-    let file_id = def.file_id(db);
-    let source_tree = sema.parse(file_id);
-    let def_node = def.source(db, &source_tree)?.value;
+    let navs = vec![NavigationTarget::from_def(sema, def)?];
 
-    let name_range = find_inner_name_range(&def_node);
+    RangeInfo::new(u_range, navs).into()
+}
 
-    let target_preprocessing_results = sema.preprocess_file(file_id);
-    let target_offsets = target_preprocessing_results.offsets();
-    let navs = vec![NavigationTarget {
-        file_id,
-        full_range: s_range_to_u_range(target_offsets, ts_range_to_lsp_range(&def_node.range())),
-        focus_range: s_range_to_u_range(target_offsets, name_range).into(),
-    }];
+impl NavigationTarget {
+    /// Get the target of the declaration of a definition, seen by the user.
+    pub(crate) fn from_def(sema: &Semantics<RootDatabase>, def: DefResolution) -> Option<Self> {
+        let db = sema.db;
+        let file_id = def.file_id(db);
+        let source_tree = sema.parse(file_id);
+        let def_node = def.source(db, &source_tree)?.value;
 
-    RangeInfo::new(u_range, navs).into()
+        let name_range = find_inner_name_range(&def_node);
+
+        let target_preprocessing_results = sema.preprocess_file(file_id);
+        let target_offsets = target_preprocessing_results.offsets();
+        Some(NavigationTarget {
+            file_id,
+            full_range: s_range_to_u_range(
+                target_offsets,
+                ts_range_to_lsp_range(&def_node.range()),
+            ),
+            focus_range: s_range_to_u_range(target_offsets, name_range).into(),
+        })
+    }
 }
 
 /// Find the range of the inner name node of a definition node if there is one.
@@ -0,0 +1,56 @@
+//! Implementations of forwards, and usages of methodmap natives.
+//!
+//! A forward is implemented by the public functions of the plugins that share its name. The
+//! natives of a methodmap are implemented by an extension, their usages are listed instead.
+
+use base_db::FilePosition;
+use hir::{DefResolution, Function, FunctionKind, Semantics};
+use ide_db::RootDatabase;
+
+use crate::{
+    call_hierarchy::implementations_of,
+    goto_definition::NavigationTarget,
+    references::{def_at_position, find_refs_to_def},
+    RangeInfo,
+};
+
+pub(crate) fn goto_implementation(
+    db: &RootDatabase,
+    pos: FilePosition,
+) -> Option<RangeInfo<Vec<NavigationTarget>>> {
+    let sema = &Semantics::new(db);
+    let RangeInfo { range, info: def } = def_at_position(sema, pos)?;
+    let DefResolution::Function(function) = def else {
+        return None;
+    };
+
+    let navs = match function.kind(db) {
+        FunctionKind::Forward => implementations_of(db, function)
+            .into_iter()
+            .filter_map(|it| NavigationTarget::from_def(sema, DefResolution::Function(it)))
+            .collect(),
+        FunctionKind::Native if function.is_method(db) => {
+            find_refs_to_def(sema, &DefResolution::Function(function))?
+                .into_file_ranges(false)
+                .into_iter()
+                .map(|frange| NavigationTarget {
+                    file_id: frange.file_id,
+                    full_range: frange.range,
+                    focus_range: None,
+                })
+                .collect()
+        }
+        _ => return None,
+    };
+
+    Some(RangeInfo::new(range, navs))
+}
+
+/// Whether a function can be the origin of a go to implementation.
+pub(crate) fn has_implementations(db: &RootDatabase, function: Function) -> bool {
+    match function.kind(db) {
+        FunctionKind::Forward => true,
+        FunctionKind::Native => function.is_method(db),
+        FunctionKind::Def => false,
+    }
+}
@@ -0,0 +1,79 @@
+//! Type definition of an expression, i.e. the methodmap, enum struct, enum, typedef or typeset
+//! that its inferred type names.
+//!
+//! Arrays are looked through, to the type of their elements.
+
+use base_db::FilePosition;
+use hir::{DefResolution, Semantics, TypeRef};
+use ide_db::RootDatabase;
+use syntax::{
+    utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+    TSKind,
+};
+
+use crate::{goto_definition::NavigationTarget, s_range_to_u_range, u_pos_to_s_pos, RangeInfo};
+
+pub(crate) fn goto_type_definition(
+    db: &RootDatabase,
+    mut pos: FilePosition,
+) -> Option<RangeInfo<Vec<NavigationTarget>>> {
+    let sema = &Semantics::new(db);
+    let preprocessing_results = sema.preprocess_file(pos.file_id);
+    let offsets = preprocessing_results.offsets();
+    let source_u_range =
+        u_pos_to_s_pos(preprocessing_results.args_map(), offsets, &mut pos.position);
+    let tree = sema.parse(pos.file_id);
+    let point = lsp_position_to_ts_point(&pos.position);
+    let node = tree.root_node().descendant_for_point_range(point, point)?;
+
+    let expr = expr_of_name(node);
+    let mut type_ref = sema
+        .type_of_node(pos.file_id, &expr)
+        .or_else(|| sema.find_def(pos.file_id, &node)?.type_ref(db))?;
+    while let TypeRef::Array((inner, _)) = type_ref {
+        type_ref = *inner;
+    }
+    let (TypeRef::Name(name) | TypeRef::OldName(name)) = type_ref else {
+        return None;
+    };
+    let def = sema
+        .scope_at(pos.file_id, &node, point)
+        .resolve_ident(&name.to_string())?;
+    if !matches!(
+        def,
+        DefResolution::Methodmap(_)
+            | DefResolution::EnumStruct(_)
+            | DefResolution::Enum(_)
+            | DefResolution::Typedef(_)
+            | DefResolution::Typeset(_)
+            | DefResolution::Functag(_)
+            | DefResolution::Funcenum(_)
+    ) {
+        return None;
+    }
+
+    let u_range = match source_u_range {
+        Some(u_range) => u_range,
+        None => s_range_to_u_range(offsets, ts_range_to_lsp_range(&node.range())),
+    };
+
+    Some(RangeInfo::new(
+        u_range,
+        vec![NavigationTarget::from_def(sema, def)?],
+    ))
+}
+
+/// Get the expression whose value is named by a node, e.g. `foo.bar` for `bar`.
+fn expr_of_name(node: tree_sitter::Node) -> tree_sitter::Node {
+    let Some(parent) = node.parent() else {
+        return node;
+    };
+    match TSKind::from(parent) {
+        TSKind::field_access | TSKind::scope_access
+            if parent.child_by_field_name("field") == Some(node) =>
+        {
+            parent
+        }
+        _ => node,
+    }
+}
@@ -1,11 +1,11 @@
-use hir::{HasSource, Semantics};
+use hir::{DefResolution, HasSource, Semantics};
 use ide_db::{Documentation, RootDatabase};
 use preprocessor::{db::PreprocDatabase, PreprocessingResult};
 use syntax::utils::{lsp_position_to_ts_point, ts_range_to_lsp_range};
 
 use crate::{
-    goto_definition::find_macro_def, markup::Markup, s_range_to_u_range, u_pos_to_s_pos,
-    FilePosition, RangeInfo,
+    goto_definition::find_macro_def, goto_implementation::has_implementations, markup::Markup,
+    s_range_to_u_range, u_pos_to_s_pos, FilePosition, RangeInfo,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -61,6 +61,7 @@ pub(crate) fn hover(
     config: &HoverConfig,
 ) -> Option<RangeInfo<HoverResult>> {
     let sema = &Semantics::new(db);
+    let u_fpos = fpos;
     let preprocessing_results = sema.preprocess_file(fpos.file_id);
     let offsets = preprocessing_results.offsets();
     let tree = sema.parse(fpos.file_id);
@@ -85,6 +86,13 @@ pub(crate) fn hover(
         None => s_range_to_u_range(offsets, ts_range_to_lsp_range(&node.range())),
     };
 
+    let actions = match def {
+        DefResolution::Function(function) if has_implementations(db, function) => {
+            vec![HoverAction::Implementation(u_fpos)]
+        }
+        _ => vec![],
+    };
+
     let file_id = def.file_id(db);
     let source_tree = sema.parse(file_id);
     let text = db.preprocessed_text(file_id);
@@ -94,7 +102,7 @@ pub(crate) fn hover(
     if !config.documentation {
         let res = HoverResult {
             markup: Markup::fenced_block(source_text),
-            actions: vec![],
+            actions,
         };
         return Some(RangeInfo::new(u_range, res));
     }
@@ -105,13 +113,13 @@ pub(crate) fn hover(
                 Markup::fenced_block(source_text),
                 Markup::from(docs.to_markdown()),
             )),
-            actions: vec![],
+            actions,
         };
         return Some(RangeInfo::new(u_range, res));
     }
     let res = HoverResult {
         markup: Markup::fenced_block(source_text),
-        actions: vec![],
+        actions,
     };
     Some(RangeInfo::new(u_range, res))
 }
@@ -0,0 +1,305 @@
+//! Inlay hints for the names of the parameters at call sites, the inferred types of untyped
+//! bindings, and the values of enum variants and `#define` constants.
+
+use std::sync::Arc;
+
+use fxhash::FxHashMap;
+use hir::{DefResolution, HasSource, Semantics};
+use ide_db::RootDatabase;
+use preprocessor::{db::PreprocDatabase, Offset};
+use syntax::{utils::point_to_lsp_position, TSKind};
+use vfs::FileId;
+
+use crate::{s_range_to_u_range, signature_help::param_names};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlayHintsConfig {
+    pub parameter_hints: bool,
+    pub type_hints: bool,
+    pub enum_value_hints: bool,
+    pub define_value_hints: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InlayKind {
+    /// Name of the parameter an argument is passed to.
+    Parameter,
+
+    /// Inferred type of a binding declared without a type.
+    Type,
+
+    /// Value of an enum variant declared without a value.
+    EnumValue,
+
+    /// Value of a `#define` constant, at its use sites.
+    DefineValue,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InlayHint {
+    /// Position of the hint, seen by the user.
+    pub position: lsp_types::Position,
+    pub kind: InlayKind,
+    pub label: String,
+}
+
+/// Maximum length of the value of a `#define` constant to be displayed as a hint.
+const DEFINE_VALUE_MAX_LEN: usize = 24;
+
+pub(crate) fn inlay_hints(
+    db: &RootDatabase,
+    config: &InlayHintsConfig,
+    file_id: FileId,
+    range: Option<lsp_types::Range>,
+) -> Vec<InlayHint> {
+    let sema = &Semantics::new(db);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let ctx = InlayHintsCtx {
+        sema,
+        file_id,
+        source: db.preprocessed_text(file_id),
+        offsets: preprocessing_results.offsets(),
+    };
+
+    let mut res = Vec::new();
+    let tree = sema.parse(file_id);
+    let mut cursor = tree.root_node().walk();
+    'outer: loop {
+        let node = cursor.node();
+        match TSKind::from(node) {
+            TSKind::call_expression | TSKind::new_expression if config.parameter_hints => {
+                ctx.param_hints(node, &mut res);
+            }
+            TSKind::old_variable_declaration if config.type_hints => {
+                ctx.type_hint(node, &mut res);
+            }
+            TSKind::r#enum if config.enum_value_hints => {
+                ctx.enum_value_hints(node, &mut res);
+            }
+            _ => (),
+        }
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+    if config.define_value_hints {
+        ctx.define_value_hints(&mut res);
+    }
+
+    if let Some(range) = range {
+        res.retain(|hint| range.start <= hint.position && hint.position <= range.end);
+    }
+    res.sort_by_key(|hint| hint.position);
+
+    res
+}
+
+struct InlayHintsCtx<'a> {
+    sema: &'a Semantics<'a, RootDatabase>,
+    file_id: FileId,
+    source: Arc<str>,
+    offsets: &'a FxHashMap<u32, Vec<Offset>>,
+}
+
+impl InlayHintsCtx<'_> {
+    /// Hint the names of the parameters in front of the positional arguments of a call.
+    ///
+    /// Arguments that are already named after their parameter, named arguments and the
+    /// arguments of a rest parameter are not hinted.
+    fn param_hints(&self, call: tree_sitter::Node, acc: &mut Vec<InlayHint>) -> Option<()> {
+        let db = self.sema.db;
+        let callee = match TSKind::from(call) {
+            TSKind::call_expression => {
+                let function = call.child_by_field_name("function")?;
+                match TSKind::from(function) {
+                    TSKind::field_access => function.child_by_field_name("field")?,
+                    _ => function,
+                }
+            }
+            _ => call.child_by_field_name("class")?,
+        };
+        let args = call
+            .children(&mut call.walk())
+            .find(|n| TSKind::from(n) == TSKind::call_arguments)?;
+        let DefResolution::Function(func) = self.sema.find_def(self.file_id, &callee)? else {
+            return None;
+        };
+        let params = func.params(db);
+        let def = DefResolution::Function(func);
+        let def_file_id = def.file_id(db);
+        let def_tree = self.sema.parse(def_file_id);
+        let def_source = db.preprocessed_text(def_file_id);
+        let names = param_names(def.source(db, &def_tree)?.value, def_source.as_bytes());
+
+        for (idx, arg) in args
+            .children(&mut args.walk())
+            .filter(|n| {
+                !matches!(
+                    TSKind::from(n),
+                    TSKind::anon_LPAREN
+                        | TSKind::anon_RPAREN
+                        | TSKind::anon_COMMA
+                        | TSKind::comment
+                )
+            })
+            .enumerate()
+        {
+            if TSKind::from(arg) == TSKind::named_arg {
+                break;
+            }
+            let Some(param) = params.get(idx) else {
+                break;
+            };
+            if param.is_rest {
+                break;
+            }
+            let Some(Some(name)) = names.get(idx) else {
+                continue;
+            };
+            if arg.utf8_text(self.source.as_bytes()).ok() == Some(name.as_str()) {
+                continue;
+            }
+            acc.push(InlayHint {
+                position: self.u_pos(point_to_lsp_position(&arg.start_position())),
+                kind: InlayKind::Parameter,
+                label: format!("{name}:"),
+            });
+        }
+
+        Some(())
+    }
+
+    /// Hint the type of an old style declaration without a tag, deduced from its initial value.
+    fn type_hint(&self, decl: tree_sitter::Node, acc: &mut Vec<InlayHint>) -> Option<()> {
+        if decl.child_by_field_name("type").is_some() {
+            return None;
+        }
+        let name = decl.child_by_field_name("name")?;
+        let value = decl.child_by_field_name("initialValue")?;
+        let type_ref = self.sema.type_of_node(self.file_id, &value)?;
+        acc.push(InlayHint {
+            position: self.u_pos(point_to_lsp_position(&name.end_position())),
+            kind: InlayKind::Type,
+            label: format!(": {}", type_ref.to_str()),
+        });
+
+        Some(())
+    }
+
+    /// Hint the values of the variants of an enum that are declared without a value.
+    ///
+    /// The values can only be computed while the previous values are integer literals.
+    fn enum_value_hints(&self, enum_: tree_sitter::Node, acc: &mut Vec<InlayHint>) -> Option<()> {
+        // Enums with a custom increment, e.g. `enum Flags (<<= 1)`, are not supported.
+        if enum_
+            .children(&mut enum_.walk())
+            .any(|n| TSKind::from(n) == TSKind::anon_LPAREN)
+        {
+            return None;
+        }
+        let entries = enum_.child_by_field_name("entries")?;
+        let mut next = Some(0i64);
+        for entry in entries
+            .children(&mut entries.walk())
+            .filter(|n| TSKind::from(n) == TSKind::enum_entry)
+        {
+            if let Some(value) = entry.child_by_field_name("value") {
+                next = value
+                    .utf8_text(self.source.as_bytes())
+                    .ok()
+                    .and_then(parse_int)
+                    .and_then(|value| value.checked_add(1));
+                continue;
+            }
+            if let (Some(value), Some(name)) = (next, entry.child_by_field_name("name")) {
+                acc.push(InlayHint {
+                    position: self.u_pos(point_to_lsp_position(&name.end_position())),
+                    kind: InlayKind::EnumValue,
+                    label: format!("= {value}"),
+                });
+            }
+            next = next.and_then(|value| value.checked_add(1));
+        }
+
+        Some(())
+    }
+
+    /// Hint the values of the `#define` constants expanded in the file, when they are literals.
+    fn define_value_hints(&self, acc: &mut Vec<InlayHint>) {
+        let db = self.sema.db;
+        for offset in self.offsets.values().flatten() {
+            let Some(def) = self
+                .sema
+                .find_macro_def(offset.file_id, offset.idx)
+                .map(DefResolution::from)
+            else {
+                continue;
+            };
+            let def_file_id = def.file_id(db);
+            let def_tree = self.sema.parse(def_file_id);
+            let def_source = db.preprocessed_text(def_file_id);
+            let Some(value) = def
+                .source(db, &def_tree)
+                .map(|src| src.value)
+                .filter(|node| TSKind::from(node) == TSKind::preproc_define)
+                .and_then(|node| node.child_by_field_name("value"))
+                .and_then(|value| value.utf8_text(def_source.as_bytes()).ok())
+                .map(str::trim)
+                .filter(|value| is_literal(value))
+            else {
+                continue;
+            };
+            acc.push(InlayHint {
+                position: offset.range.end,
+                kind: InlayKind::DefineValue,
+                label: format!("= {value}"),
+            });
+        }
+    }
+
+    /// Convert a position of the preprocessed text to a position seen by the user.
+    fn u_pos(&self, s_pos: lsp_types::Position) -> lsp_types::Position {
+        s_range_to_u_range(self.offsets, lsp_types::Range::new(s_pos, s_pos)).start
+    }
+}
+
+/// Parse an integer literal, in any of the bases supported by the compiler.
+fn parse_int(text: &str) -> Option<i64> {
+    let text = text.trim().replace('_', "");
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text.trim_start()),
+        None => (false, text.as_str()),
+    };
+    let value = if let Some(hex) = text.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if let Some(bin) = text.strip_prefix("0b") {
+        i64::from_str_radix(bin, 2).ok()?
+    } else if let Some(oct) = text.strip_prefix("0o") {
+        i64::from_str_radix(oct, 8).ok()?
+    } else {
+        text.parse().ok()?
+    };
+
+    Some(if negative { -value } else { value })
+}
+
+/// Whether the value of a `#define` is a short number, string or character literal.
+fn is_literal(value: &str) -> bool {
+    if value.is_empty() || value.len() > DEFINE_VALUE_MAX_LEN {
+        return false;
+    }
+    if value.starts_with('"') && value.ends_with('"') || value.starts_with('\'') {
+        return true;
+    }
+
+    value.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.')
+        && (parse_int(value).is_some() || value.parse::<f64>().is_ok())
+}
@@ -1,10 +1,25 @@
 //! base_db defines basic database traits. The concrete DB is defined by ide.
 
+mod call_hierarchy;
+mod completion;
+mod docgen;
+mod document_highlight;
+mod folding_ranges;
+mod formatting;
+mod goto_declaration;
 mod goto_definition;
+mod goto_implementation;
+mod goto_type_definition;
 mod hover;
+mod inlay_hints;
 mod markup;
 mod prime_caches;
+mod references;
+mod rename;
+mod selection_ranges;
+mod signature_help;
 mod status;
+mod symbols;
 mod syntax_highlighting;
 
 use std::sync::Arc;
@@ -15,20 +30,31 @@ use base_db::{
 use fxhash::FxHashMap;
 use hir_def::{print_item_tree, DefDatabase};
 use hover::HoverResult;
-use ide_db::RootDatabase;
+use ide_db::{Documentation, RootDatabase};
 use itertools::Itertools;
 use preprocessor::{db::PreprocDatabase, ArgsMap, Offset};
-use salsa::{Cancelled, ParallelDatabase};
+use salsa::{Cancelled, Database, ParallelDatabase};
 use syntax::range_contains_pos;
 use vfs::FileId;
 
+pub use call_hierarchy::{CallHierarchyItem, CallItem};
+pub use completion::{CompletionItem, CompletionKind};
+pub use docgen::ItemDoc;
+pub use document_highlight::{HighlightedRange, ReferenceCategory};
+pub use folding_ranges::{Fold, FoldKind};
+pub use formatter::{BraceStyle, FormattingConfig};
 pub use goto_definition::NavigationTarget;
 pub use hover::{HoverConfig, HoverDocFormat};
-pub use ide_db::Cancellable;
+pub use inlay_hints::{InlayHint, InlayHintsConfig, InlayKind};
+pub use ide_db::{Assist, AssistId, AssistKind, Cancellable, SourceChange};
 pub use ide_diagnostics::{Diagnostic, DiagnosticsConfig, Severity};
 pub use line_index::{LineCol, LineIndex, WideEncoding, WideLineCol};
 pub use markup::Markup;
 pub use prime_caches::ParallelPrimeCachesProgress;
+pub use references::ReferenceSearchResult;
+pub use rename::{RenameError, RenameResult};
+pub use signature_help::SignatureHelp;
+pub use symbols::{DocumentSymbol, WorkspaceSymbol};
 pub use syntax_highlighting::{Highlight, HlMod, HlMods, HlRange, HlTag};
 
 /// Info associated with a [`range`](lsp_types::Range).
@@ -111,6 +137,23 @@ impl Analysis {
         self.with_db(|db| db.graph())
     }
 
+    /// Gets the current revision of the database, which changes whenever one of its inputs does.
+    pub fn revision(&self) -> Cancellable<String> {
+        self.with_db(|db| format!("{:?}", db.salsa_runtime().current_revision()))
+    }
+
+    /// Get the root files of the projects, i.e. the `.sp` files that are not included.
+    pub fn project_roots(&self) -> Cancellable<Vec<FileId>> {
+        self.with_db(|db| {
+            db.graph()
+                .find_roots()
+                .into_iter()
+                .filter(|node| node.extension == FileExtension::Sp)
+                .map(|node| node.file_id)
+                .collect_vec()
+        })
+    }
+
     /// Gets the preprocessed text of the file.
     pub fn preprocessed_text(&self, file_id: FileId) -> Cancellable<Arc<str>> {
         self.with_db(|db| db.preprocessed_text(file_id))
@@ -199,6 +242,32 @@ impl Analysis {
         self.with_db(|db| goto_definition::goto_definition(db, pos))
     }
 
+    /// Returns the forwards and natives implemented by the function at `position`, or its
+    /// definition.
+    pub fn goto_declaration(
+        &self,
+        pos: FilePosition,
+    ) -> Cancellable<Option<RangeInfo<Vec<NavigationTarget>>>> {
+        self.with_db(|db| goto_declaration::goto_declaration(db, pos))
+    }
+
+    /// Returns the implementations of the forward, or the usages of the methodmap native, at
+    /// `position`.
+    pub fn goto_implementation(
+        &self,
+        pos: FilePosition,
+    ) -> Cancellable<Option<RangeInfo<Vec<NavigationTarget>>>> {
+        self.with_db(|db| goto_implementation::goto_implementation(db, pos))
+    }
+
+    /// Returns the definition of the type of the expression at `position`.
+    pub fn goto_type_definition(
+        &self,
+        pos: FilePosition,
+    ) -> Cancellable<Option<RangeInfo<Vec<NavigationTarget>>>> {
+        self.with_db(|db| goto_type_definition::goto_type_definition(db, pos))
+    }
+
     /// Returns the hover information at `position`.
     pub fn hover(
         &self,
@@ -208,6 +277,134 @@ impl Analysis {
         self.with_db(|db| hover::hover(db, pos, config))
     }
 
+    /// Returns the inlay hints of the file, optionally restricted to a range seen by the user.
+    pub fn inlay_hints(
+        &self,
+        config: &InlayHintsConfig,
+        file_id: FileId,
+        range: Option<lsp_types::Range>,
+    ) -> Cancellable<Vec<InlayHint>> {
+        self.with_db(|db| inlay_hints::inlay_hints(db, config, file_id, range))
+    }
+
+    /// Returns the references to the symbol at `position`, in every file of its projects.
+    pub fn find_all_refs(&self, pos: FilePosition) -> Cancellable<Option<ReferenceSearchResult>> {
+        self.with_db(|db| references::find_all_refs(db, pos))
+    }
+
+    /// Returns the symbols declared in the file, nested in the symbols that contain them.
+    pub fn document_symbols(&self, file_id: FileId) -> Cancellable<Vec<DocumentSymbol>> {
+        self.with_db(|db| symbols::document_symbols(db, file_id))
+    }
+
+    /// Returns the documentation of the items declared in the file, to generate its API reference.
+    pub fn file_docs(&self, file_id: FileId) -> Cancellable<Vec<ItemDoc>> {
+        self.with_db(|db| docgen::file_docs(db, file_id))
+    }
+
+    /// Returns the occurrences of the symbol at `position` in its file.
+    pub fn highlight_related(
+        &self,
+        pos: FilePosition,
+    ) -> Cancellable<Option<Vec<HighlightedRange>>> {
+        self.with_db(|db| document_highlight::highlight_related(db, pos))
+    }
+
+    /// Returns the selection ranges of each position, from the innermost to the outermost.
+    pub fn selection_ranges(
+        &self,
+        file_id: FileId,
+        positions: Vec<lsp_types::Position>,
+    ) -> Cancellable<Vec<Vec<lsp_types::Range>>> {
+        self.with_db(|db| selection_ranges::selection_ranges(db, file_id, positions))
+    }
+
+    /// Returns the folding ranges of the file.
+    pub fn folding_ranges(&self, file_id: FileId) -> Cancellable<Vec<Fold>> {
+        self.with_db(|db| folding_ranges::folding_ranges(db, file_id))
+    }
+
+    /// Returns the symbols of every known file whose name fuzzy matches `query`.
+    pub fn workspace_symbols(&self, query: &str) -> Cancellable<Vec<WorkspaceSymbol>> {
+        self.with_db(|db| symbols::workspace_symbols(db, query))
+    }
+
+    /// Returns the signature of the call surrounding `position`.
+    pub fn signature_help(&self, pos: FilePosition) -> Cancellable<Option<SignatureHelp>> {
+        self.with_db(|db| signature_help::signature_help(db, pos))
+    }
+
+    /// Checks that the symbol at `position` can be renamed, and returns the range of its name.
+    pub fn prepare_rename(&self, pos: FilePosition) -> Cancellable<RenameResult<RangeInfo<()>>> {
+        self.with_db(|db| rename::prepare_rename(db, pos))
+    }
+
+    /// Returns the edits required to rename the symbol at `position` to `new_name`.
+    pub fn rename(
+        &self,
+        pos: FilePosition,
+        new_name: &str,
+    ) -> Cancellable<RenameResult<SourceChange>> {
+        self.with_db(|db| rename::rename(db, pos, new_name))
+    }
+
+    /// Returns the function at `position`, as an item of the call hierarchy.
+    pub fn call_hierarchy(&self, pos: FilePosition) -> Cancellable<Option<CallHierarchyItem>> {
+        self.with_db(|db| call_hierarchy::call_hierarchy(db, pos))
+    }
+
+    /// Returns the callers of the function at `position`.
+    pub fn incoming_calls(&self, pos: FilePosition) -> Cancellable<Option<Vec<CallItem>>> {
+        self.with_db(|db| call_hierarchy::incoming_calls(db, pos))
+    }
+
+    /// Returns the functions called by the function at `position`.
+    pub fn outgoing_calls(&self, pos: FilePosition) -> Cancellable<Option<Vec<CallItem>>> {
+        self.with_db(|db| call_hierarchy::outgoing_calls(db, pos))
+    }
+
+    /// Returns the completion items at `position`.
+    ///
+    /// The column of `position` is a UTF-8 offset in its line, like the columns of the syntax
+    /// trees.
+    pub fn completions(
+        &self,
+        pos: FilePosition,
+        trigger_character: Option<char>,
+    ) -> Cancellable<Option<Vec<CompletionItem>>> {
+        self.with_db(|db| completion::completions(db, pos, trigger_character))
+    }
+
+    /// Returns the documentation of a completion item, from the range of its definition.
+    pub fn resolve_completion(&self, frange: FileRange) -> Cancellable<Option<Documentation>> {
+        self.with_db(|db| completion::resolve_completion(db, frange))
+    }
+
+    /// Returns the edits to format the file, or only the lines of `range`.
+    ///
+    /// The columns of the edits are UTF-8 offsets in their line, like the columns of the syntax
+    /// trees.
+    pub fn format(
+        &self,
+        file_id: FileId,
+        config: &FormattingConfig,
+        range: Option<lsp_types::Range>,
+    ) -> Cancellable<Option<Vec<lsp_types::TextEdit>>> {
+        self.with_db(|db| formatting::format(db, file_id, config, range))
+    }
+
+    /// Returns the edits to format the lines affected by typing `ch` before `position`.
+    ///
+    /// The columns of `position` and of the edits are UTF-8 offsets in their line.
+    pub fn format_on_type(
+        &self,
+        pos: FilePosition,
+        ch: char,
+        config: &FormattingConfig,
+    ) -> Cancellable<Option<Vec<lsp_types::TextEdit>>> {
+        self.with_db(|db| formatting::format_on_type(db, pos, ch, config))
+    }
+
     /// Returns the highlighted ranges for the file.
     pub fn highlight(&self, file_id: FileId) -> Cancellable<Vec<syntax_highlighting::HlRange>> {
         self.with_db(|db| syntax_highlighting::highlight(db, file_id, None))
@@ -0,0 +1,280 @@
+use base_db::{FilePosition, FileRange, SourceDatabase, SourceDatabaseExt};
+use fxhash::{FxHashMap, FxHashSet};
+use hir::{DefResolution, HasSource, Semantics};
+use ide_db::RootDatabase;
+use preprocessor::PreprocessingResult;
+use syntax::{
+    utils::{lsp_position_to_ts_point, ts_range_to_lsp_range},
+    TSKind,
+};
+use vfs::FileId;
+
+use crate::{
+    goto_definition::{find_inner_name_range, find_macro_def},
+    s_range_to_u_range, u_pos_to_s_pos, RangeInfo,
+};
+
+/// The result of a search for the references of a definition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReferenceSearchResult {
+    /// Range of the name of the definition.
+    pub declaration: Option<FileRange>,
+
+    /// Ranges of the references to the definition, excluding the declaration, grouped by file.
+    pub references: FxHashMap<FileId, Vec<lsp_types::Range>>,
+
+    /// Ranges of the macro invocations whose expansion references the definition, without
+    /// the name of the definition appearing in the user's text.
+    pub expansion_refs: Vec<FileRange>,
+}
+
+impl ReferenceSearchResult {
+    /// Flatten the references, optionally including the declaration.
+    pub fn into_file_ranges(self, include_declaration: bool) -> Vec<FileRange> {
+        let mut res = Vec::new();
+        if include_declaration {
+            res.extend(self.declaration);
+        }
+        for (file_id, ranges) in self.references {
+            res.extend(ranges.into_iter().map(|range| FileRange { file_id, range }));
+        }
+
+        res
+    }
+}
+
+pub(crate) fn find_all_refs(db: &RootDatabase, pos: FilePosition) -> Option<ReferenceSearchResult> {
+    let sema = &Semantics::new(db);
+    let def = def_at_position(sema, pos)?.info;
+
+    find_refs_to_def(sema, &def)
+}
+
+/// Find the definition referenced at the given position, seen by the user.
+///
+/// The returned range is the range of the reference, seen by the user.
+pub(crate) fn def_at_position(
+    sema: &Semantics<RootDatabase>,
+    pos: FilePosition,
+) -> Option<RangeInfo<DefResolution>> {
+    let preprocessing_results = sema.preprocess_file(pos.file_id);
+    let offsets = preprocessing_results.offsets();
+    if let Some((offset, def)) = find_macro_def(offsets, &pos.position, sema) {
+        return Some(RangeInfo::new(offset.range, def));
+    }
+
+    let mut position = pos.position;
+    let source_u_range =
+        u_pos_to_s_pos(preprocessing_results.args_map(), offsets, &mut position);
+    let tree = sema.parse(pos.file_id);
+    let point = lsp_position_to_ts_point(&position);
+    let node = tree.root_node().descendant_for_point_range(point, point)?;
+    let def = sema.find_def(pos.file_id, &node)?;
+    let u_range = match source_u_range {
+        Some(u_range) => u_range,
+        None => s_range_to_u_range(offsets, ts_range_to_lsp_range(&node.range())),
+    };
+
+    Some(RangeInfo::new(u_range, def))
+}
+
+/// Find all the references to a definition, in every project that includes its file.
+pub(crate) fn find_refs_to_def(
+    sema: &Semantics<RootDatabase>,
+    def: &DefResolution,
+) -> Option<ReferenceSearchResult> {
+    let db = sema.db;
+    let def_file_id = def.file_id(db);
+    let def_tree = sema.parse(def_file_id);
+    let def_node = def.clone().source(db, &def_tree)?.value;
+    let name_s_range = find_inner_name_range(&def_node);
+    let name = def_node
+        .descendant_for_point_range(
+            lsp_position_to_ts_point(&name_s_range.start),
+            lsp_position_to_ts_point(&name_s_range.end),
+        )?
+        .utf8_text(sema.preprocessed_text(def_file_id).as_bytes())
+        .ok()?
+        .to_string();
+    let declaration = s_range_to_user_range(
+        &sema.preprocess_file(def_file_id),
+        &db.file_text(def_file_id),
+        name_s_range,
+        &name,
+    )
+    .map(|range| FileRange {
+        file_id: def_file_id,
+        range,
+    });
+
+    let mut references = FxHashMap::default();
+    let mut expansion_refs = Vec::new();
+    for file_id in files_to_search(db, def) {
+        let mut ranges = match def {
+            DefResolution::Macro(_) => find_macro_refs_in_file(sema, def, file_id),
+            _ => {
+                let (ranges, expansion_ranges) =
+                    find_name_refs_in_file(sema, def, &name, file_id);
+                expansion_refs.extend(
+                    expansion_ranges
+                        .into_iter()
+                        .map(|range| FileRange { file_id, range }),
+                );
+                ranges
+            }
+        };
+        ranges.retain(|range| {
+            declaration
+                != Some(FileRange {
+                    file_id,
+                    range: *range,
+                })
+        });
+        if !ranges.is_empty() {
+            references.insert(file_id, ranges);
+        }
+    }
+
+    Some(ReferenceSearchResult {
+        declaration,
+        references,
+        expansion_refs,
+    })
+}
+
+/// Get the files where a definition can be referenced.
+///
+/// Locals can only be referenced in the file that declares them. Other definitions can
+/// be referenced by any file of the projects that include their file.
+pub(crate) fn files_to_search(db: &RootDatabase, def: &DefResolution) -> FxHashSet<FileId> {
+    let file_id = def.file_id(db);
+    let mut res = FxHashSet::default();
+    res.insert(file_id);
+    if matches!(def, DefResolution::Local(_)) {
+        return res;
+    }
+    for subgraph in db.graph().find_subgraphs() {
+        if subgraph.contains_file(file_id) {
+            res.extend(subgraph.file_ids());
+        }
+    }
+
+    res
+}
+
+/// Find the references to a definition by its name.
+///
+/// Returns the ranges of the references seen by the user, and the ranges of the references
+/// that only exist in the expansion of a macro.
+fn find_name_refs_in_file(
+    sema: &Semantics<RootDatabase>,
+    def: &DefResolution,
+    name: &str,
+    file_id: FileId,
+) -> (Vec<lsp_types::Range>, Vec<lsp_types::Range>) {
+    let source = sema.preprocessed_text(file_id);
+    if !source.contains(name) {
+        return Default::default();
+    }
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let text = sema.db.file_text(file_id);
+    let tree = sema.parse(file_id);
+
+    let mut res = Vec::new();
+    let mut expansion_res = Vec::new();
+    let mut cursor = tree.root_node().walk();
+    'outer: loop {
+        let node = cursor.node();
+        if matches!(
+            TSKind::from(node),
+            TSKind::identifier | TSKind::r#type | TSKind::this
+        ) && node.utf8_text(source.as_bytes()).ok() == Some(name)
+            && sema.find_def(file_id, &node).as_ref() == Some(def)
+        {
+            let s_range = ts_range_to_lsp_range(&node.range());
+            match s_range_to_user_range(&preprocessing_results, &text, s_range, name) {
+                Some(range) => res.push(range),
+                None => expansion_res.push(s_range_to_u_range(
+                    preprocessing_results.offsets(),
+                    s_range,
+                )),
+            }
+        }
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    (res, expansion_res)
+}
+
+/// Macros are expanded by the preprocessor, their references are the expansions it recorded.
+pub(crate) fn find_macro_refs_in_file(
+    sema: &Semantics<RootDatabase>,
+    def: &DefResolution,
+    file_id: FileId,
+) -> Vec<lsp_types::Range> {
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let mut res = preprocessing_results
+        .offsets()
+        .values()
+        .flatten()
+        .filter(|offset| {
+            sema.find_macro_def(offset.file_id, offset.idx)
+                .map(DefResolution::from)
+                .as_ref()
+                == Some(def)
+        })
+        .map(|offset| offset.range)
+        .collect::<Vec<_>>();
+    res.sort_by_key(|range| (range.start.line, range.start.character));
+
+    res
+}
+
+/// Convert a range of the preprocessed text to a range seen by the user.
+///
+/// Ranges inside of a macro argument are mapped back to the argument. Returns `None` if the
+/// range does not match the name in the user's text, i.e. it comes from the body of a macro.
+pub(crate) fn s_range_to_user_range(
+    preprocessing_results: &PreprocessingResult,
+    text: &str,
+    s_range: lsp_types::Range,
+    name: &str,
+) -> Option<lsp_types::Range> {
+    let u_range = preprocessing_results
+        .args_map()
+        .get(&s_range.start.line)
+        .and_then(|args| {
+            args.iter().find(|(_, arg_s_range)| {
+                arg_s_range.start <= s_range.start && s_range.end <= arg_s_range.end
+            })
+        })
+        .map(|(arg_u_range, arg_s_range)| {
+            let mut u_range = s_range;
+            u_range.start.line = arg_u_range.start.line;
+            u_range.end.line = arg_u_range.start.line;
+            u_range.start.character =
+                arg_u_range.start.character + s_range.start.character - arg_s_range.start.character;
+            u_range.end.character = u_range.start.character + name.chars().count() as u32;
+            u_range
+        })
+        .unwrap_or_else(|| s_range_to_u_range(preprocessing_results.offsets(), s_range));
+
+    let line = text.lines().nth(u_range.start.line as usize)?;
+    let u_text: String = line
+        .chars()
+        .skip(u_range.start.character as usize)
+        .take(u_range.end.character.saturating_sub(u_range.start.character) as usize)
+        .collect();
+
+    (u_text == name).then_some(u_range)
+}
@@ -0,0 +1,119 @@
+//! Renaming of symbols across the projects that include them.
+//!
+//! Occurrences are found with [`find_refs_to_def`], and the rename is refused if any of them
+//! cannot be edited, i.e. when the name only appears in the body of a macro.
+
+use std::fmt;
+
+use base_db::{FilePosition, SourceDatabaseExt};
+use hir::{DefResolution, FunctionKind, Semantics};
+use ide_db::{RootDatabase, SourceChange};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    references::{def_at_position, find_refs_to_def},
+    RangeInfo,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameError(pub String);
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for RenameError {}
+
+pub type RenameResult<T> = Result<T, RenameError>;
+
+macro_rules! format_err {
+    ($fmt:expr) => {RenameError(format!($fmt))};
+    ($fmt:expr, $($arg:tt)+) => {RenameError(format!($fmt, $($arg)+))}
+}
+
+macro_rules! bail {
+    ($($tokens:tt)*) => {return Err(format_err!($($tokens)*))}
+}
+
+/// Check that the symbol at `pos` can be renamed, and return the range of its name.
+pub(crate) fn prepare_rename(db: &RootDatabase, pos: FilePosition) -> RenameResult<RangeInfo<()>> {
+    let sema = &Semantics::new(db);
+    let RangeInfo { range, info: def } =
+        def_at_position(sema, pos).ok_or_else(|| format_err!("No references found at position"))?;
+    check_def_is_renamable(db, &def)?;
+
+    Ok(RangeInfo::new(range, ()))
+}
+
+/// Rename the symbol at `pos` to `new_name`, in every file of the projects that include it.
+pub(crate) fn rename(
+    db: &RootDatabase,
+    pos: FilePosition,
+    new_name: &str,
+) -> RenameResult<SourceChange> {
+    check_identifier(new_name)?;
+    let sema = &Semantics::new(db);
+    let def = def_at_position(sema, pos)
+        .ok_or_else(|| format_err!("No references found at position"))?
+        .info;
+    check_def_is_renamable(db, &def)?;
+
+    let refs =
+        find_refs_to_def(sema, &def).ok_or_else(|| format_err!("No references found at position"))?;
+    if let Some(frange) = refs.expansion_refs.first() {
+        bail!(
+            "Cannot rename a symbol referenced inside of a macro body (used by the macro at line {}, character {})",
+            frange.range.start.line + 1,
+            frange.range.start.character + 1
+        );
+    }
+    if refs.declaration.is_none() {
+        bail!("Cannot rename a symbol declared inside of a macro body");
+    }
+
+    let mut source_change = SourceChange::default();
+    for frange in refs.into_file_ranges(true) {
+        source_change.insert_source_edit(
+            frange.file_id,
+            lsp_types::TextEdit::new(frange.range, new_name.to_string()),
+        );
+    }
+
+    Ok(source_change)
+}
+
+fn check_identifier(new_name: &str) -> RenameResult<()> {
+    lazy_static! {
+        static ref RE_IDENT: Regex = Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap();
+    }
+    if !RE_IDENT.is_match(new_name) {
+        bail!("Invalid name `{}`: not an identifier", new_name);
+    }
+
+    Ok(())
+}
+
+fn check_def_is_renamable(db: &RootDatabase, def: &DefResolution) -> RenameResult<()> {
+    match def {
+        DefResolution::Function(function) => {
+            if function.kind(db) == FunctionKind::Native {
+                let source_root = db.source_root(db.file_source_root(def.file_id(db)));
+                if source_root.is_include_dir {
+                    bail!("Cannot rename a native declared in an include directory");
+                }
+            }
+        }
+        DefResolution::Macro(_)
+        | DefResolution::Property(_)
+        | DefResolution::Variant(_)
+        | DefResolution::Field(_)
+        | DefResolution::Global(_)
+        | DefResolution::Local(_) => (),
+        _ => bail!("This symbol cannot be renamed"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Selection ranges, to expand the selection from a position to the nodes that contain it,
+//! e.g. identifier, call, statement, block, function and file.
+//!
+//! The tree is built over the preprocessed text, the ranges of its nodes are mapped back to the
+//! ranges seen by the user. Ranges that do not contain the previous one once mapped, like the
+//! ones of the nodes of a macro expansion, are skipped.
+
+use fxhash::FxHashMap;
+use hir::Semantics;
+use ide_db::RootDatabase;
+use preprocessor::Offset;
+use syntax::utils::{lsp_position_to_ts_point, ts_range_to_lsp_range};
+use vfs::FileId;
+
+use crate::{s_range_to_u_range, u_pos_to_s_pos};
+
+/// Compute the selection ranges of each position, from the innermost to the outermost.
+pub(crate) fn selection_ranges(
+    db: &RootDatabase,
+    file_id: FileId,
+    positions: Vec<lsp_types::Position>,
+) -> Vec<Vec<lsp_types::Range>> {
+    let sema = &Semantics::new(db);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let offsets = preprocessing_results.offsets();
+    let tree = sema.parse(file_id);
+
+    positions
+        .into_iter()
+        .map(|u_pos| {
+            let mut res = Vec::new();
+            let mut s_pos = u_pos;
+            // A macro argument is selected as a whole first.
+            if let Some(arg_range) =
+                u_pos_to_s_pos(preprocessing_results.args_map(), offsets, &mut s_pos)
+            {
+                res.push(arg_range);
+            }
+            let point = lsp_position_to_ts_point(&s_pos);
+            let mut node = tree.root_node().descendant_for_point_range(point, point);
+            while let Some(current) = node {
+                let range = u_range(offsets, ts_range_to_lsp_range(&current.range()));
+                let last = res
+                    .last()
+                    .copied()
+                    .unwrap_or_else(|| lsp_types::Range::new(u_pos, u_pos));
+                if range != last && range.start <= last.start && last.end <= range.end {
+                    res.push(range);
+                }
+                node = current.parent();
+            }
+
+            res
+        })
+        .collect()
+}
+
+/// Convert a range of the preprocessed text to a range seen by the user, one bound at a time
+/// as they can be on different lines.
+fn u_range(offsets: &FxHashMap<u32, Vec<Offset>>, s_range: lsp_types::Range) -> lsp_types::Range {
+    let u_pos = |s_pos| s_range_to_u_range(offsets, lsp_types::Range::new(s_pos, s_pos)).start;
+
+    lsp_types::Range::new(u_pos(s_range.start), u_pos(s_range.end))
+}
@@ -0,0 +1,249 @@
+//! Signature help for function, method and constructor calls.
+//!
+//! The signature is rendered from the declaration of the callee, and the active parameter is
+//! computed from the position of the cursor in the arguments of the call.
+
+use std::ops::Range;
+
+use hir::{DefResolution, HasSource, ParamData, Semantics, TypeRef};
+use ide_db::{Documentation, RootDatabase};
+use itertools::Itertools;
+use preprocessor::db::PreprocDatabase;
+use syntax::{utils::lsp_position_to_ts_point, TSKind};
+use tree_sitter::Point;
+
+use crate::{u_pos_to_s_pos, FilePosition};
+
+/// Contains information about an item signature as seen from a use site.
+///
+/// This includes the "active parameter", which is the parameter whose value is currently being
+/// edited.
+#[derive(Debug)]
+pub struct SignatureHelp {
+    pub doc: Option<Documentation>,
+    pub signature: String,
+    pub active_parameter: Option<usize>,
+    parameters: Vec<Range<usize>>,
+}
+
+impl SignatureHelp {
+    /// The labels of the parameters, as they appear in the signature.
+    pub fn parameter_labels(&self) -> impl Iterator<Item = &str> + '_ {
+        self.parameters.iter().map(move |it| &self.signature[it.clone()])
+    }
+
+    /// The byte ranges of the parameters in the signature.
+    pub fn parameter_ranges(&self) -> &[Range<usize>] {
+        &self.parameters
+    }
+
+    fn push_param(&mut self, param: &str) {
+        if !self.parameters.is_empty() {
+            self.signature.push_str(", ");
+        }
+        let start = self.signature.len();
+        self.signature.push_str(param);
+        self.parameters.push(start..self.signature.len());
+    }
+}
+
+pub(crate) fn signature_help(db: &RootDatabase, mut fpos: FilePosition) -> Option<SignatureHelp> {
+    let sema = &Semantics::new(db);
+    let preprocessing_results = sema.preprocess_file(fpos.file_id);
+    u_pos_to_s_pos(
+        preprocessing_results.args_map(),
+        preprocessing_results.offsets(),
+        &mut fpos.position,
+    );
+    let source = db.preprocessed_text(fpos.file_id);
+    let tree = sema.parse(fpos.file_id);
+    let point = lsp_position_to_ts_point(&fpos.position);
+    let node = tree.root_node().descendant_for_point_range(point, point)?;
+    let (callee, args) = find_call(node, point)?;
+
+    let (def, params) = match sema.find_def(fpos.file_id, &callee)? {
+        DefResolution::Function(it) => (DefResolution::Function(it), it.params(db)),
+        // Calls of a variable typed as a callback, e.g. a parameter of a `Call_` style helper.
+        def => {
+            let Some(TypeRef::Name(type_name)) = def.type_ref(db) else {
+                return None;
+            };
+            match sema
+                .scope_at(fpos.file_id, &callee, point)
+                .resolve_ident(&type_name.to_string())?
+            {
+                DefResolution::Typedef(it) => (DefResolution::Typedef(it), it.params(db)),
+                DefResolution::Functag(it) => (DefResolution::Functag(it), it.params(db)),
+                _ => return None,
+            }
+        }
+    };
+
+    let def_file_id = def.file_id(db);
+    let def_tree = sema.parse(def_file_id);
+    let def_source = db.preprocessed_text(def_file_id);
+    let def_node = def.source(db, &def_tree)?.value;
+    let (mut res, param_names) = render_signature(def_node, def_source.as_bytes())?;
+    res.active_parameter =
+        active_parameter(&param_names, &params, args, point, source.as_bytes());
+
+    Some(res)
+}
+
+/// Find the innermost call whose arguments contain `point`.
+///
+/// Returns the node that names the callee, and the arguments of the call.
+fn find_call(
+    node: tree_sitter::Node,
+    point: Point,
+) -> Option<(tree_sitter::Node, tree_sitter::Node)> {
+    let mut candidate = Some(node);
+    while let Some(node) = candidate {
+        candidate = node.parent();
+        if TSKind::from(node) != TSKind::call_arguments || point <= node.start_position() {
+            continue;
+        }
+        if let Some(last) = node.child(node.child_count().saturating_sub(1)) {
+            if TSKind::from(last) == TSKind::anon_RPAREN
+                && !last.is_missing()
+                && point > last.start_position()
+            {
+                continue;
+            }
+        }
+        let Some(parent) = node.parent() else {
+            continue;
+        };
+        let callee = match TSKind::from(parent) {
+            TSKind::call_expression => {
+                let function = parent.child_by_field_name("function")?;
+                match TSKind::from(function) {
+                    TSKind::field_access => function.child_by_field_name("field")?,
+                    _ => function,
+                }
+            }
+            TSKind::new_expression => parent.child_by_field_name("class")?,
+            _ => continue,
+        };
+        return Some((callee, node));
+    }
+
+    None
+}
+
+/// Render the signature of a function, typedef or functag from its declaration.
+///
+/// Also returns the names of the parameters, in order.
+fn render_signature(
+    def_node: tree_sitter::Node,
+    source: &[u8],
+) -> Option<(SignatureHelp, Vec<Option<String>>)> {
+    let params_node = params_node(def_node)?;
+    let prefix =
+        std::str::from_utf8(&source[def_node.start_byte()..params_node.start_byte()]).ok()?;
+
+    let mut res = SignatureHelp {
+        doc: Documentation::from_node(def_node, source),
+        signature: collapse_whitespace(prefix),
+        active_parameter: None,
+        parameters: Vec::new(),
+    };
+    let mut param_names = Vec::new();
+    res.signature.push('(');
+    for param in params(params_node) {
+        res.push_param(&collapse_whitespace(param.utf8_text(source).ok()?));
+        param_names.push(param_name(param, source));
+    }
+    res.signature.push(')');
+
+    Some((res, param_names))
+}
+
+/// Get the names of the parameters of a function, typedef or functag from its declaration.
+pub(crate) fn param_names(def_node: tree_sitter::Node, source: &[u8]) -> Vec<Option<String>> {
+    params_node(def_node)
+        .map(|params_node| {
+            params(params_node)
+                .map(|param| param_name(param, source))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn params_node(def_node: tree_sitter::Node) -> Option<tree_sitter::Node> {
+    match TSKind::from(def_node) {
+        TSKind::typedef => def_node
+            .children(&mut def_node.walk())
+            .find(|n| TSKind::from(n) == TSKind::typedef_expression)?
+            .child_by_field_name("parameters"),
+        _ => def_node.child_by_field_name("parameters"),
+    }
+}
+
+fn params(params_node: tree_sitter::Node) -> impl Iterator<Item = tree_sitter::Node> {
+    params_node
+        .children(&mut params_node.walk())
+        .filter(|n| {
+            matches!(
+                TSKind::from(n),
+                TSKind::parameter_declaration | TSKind::rest_parameter
+            )
+        })
+        .collect_vec()
+        .into_iter()
+}
+
+fn param_name(param: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    param
+        .child_by_field_name("name")
+        .and_then(|name| name.utf8_text(source).ok())
+        .map(ToString::to_string)
+}
+
+/// Compute the index of the parameter being edited at `point`.
+///
+/// Named arguments (`.param=`) are matched against the names of the parameters of the callee,
+/// like the resolver does when inferring the call. Otherwise, the index is the number of commas
+/// before `point`. Extra arguments are attributed to a trailing rest parameter.
+fn active_parameter(
+    param_names: &[Option<String>],
+    params: &[ParamData],
+    args: tree_sitter::Node,
+    point: Point,
+    source: &[u8],
+) -> Option<usize> {
+    let mut cursor = args.walk();
+    let children = args.children(&mut cursor).collect_vec();
+    let named_arg = children
+        .iter()
+        .find(|arg| {
+            TSKind::from(*arg) == TSKind::named_arg
+                && arg.start_position() <= point
+                && point <= arg.end_position()
+        })
+        .and_then(|arg| arg.child_by_field_name("arg_name"))
+        .and_then(|name| name.utf8_text(source).ok());
+    let idx = match named_arg {
+        Some(name) => param_names
+            .iter()
+            .position(|param_name| param_name.as_deref() == Some(name))?,
+        None => children
+            .iter()
+            .filter(|child| {
+                TSKind::from(*child) == TSKind::anon_COMMA && child.end_position() <= point
+            })
+            .count(),
+    };
+
+    if idx < params.len() {
+        return Some(idx);
+    }
+    params
+        .last()
+        .filter(|param| param.is_rest)
+        .map(|_| params.len() - 1)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().join(" ")
+}
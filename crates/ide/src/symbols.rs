@@ -0,0 +1,197 @@
+//! Symbols declared in a file, and fuzzy search of the symbols declared in every known file.
+
+use std::sync::Arc;
+
+use base_db::{SourceDatabaseExt, Tree};
+use fxhash::FxHashMap;
+use hir::{DefResolution, File, FileDef, HasSource, Semantics};
+use ide_db::{
+    symbol_index::{self, SymbolsDatabase},
+    RootDatabase, SymbolKind,
+};
+use itertools::Itertools;
+use preprocessor::{db::PreprocDatabase, Offset};
+use syntax::utils::{lsp_position_to_ts_point, ts_range_to_lsp_range};
+use vfs::FileId;
+
+use crate::{goto_definition::find_inner_name_range, s_range_to_u_range};
+
+/// Maximum number of results of a workspace symbol search.
+const WORKSPACE_SYMBOLS_LIMIT: usize = 128;
+
+/// A symbol declared in a file, with the symbols it contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentSymbol {
+    pub name: String,
+    pub detail: Option<String>,
+    pub kind: SymbolKind,
+
+    /// Range of the whole declaration, seen by the user.
+    pub range: lsp_types::Range,
+
+    /// Range of the name of the declaration, seen by the user.
+    pub selection_range: lsp_types::Range,
+    pub children: Vec<DocumentSymbol>,
+}
+
+/// A symbol found by a workspace symbol search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+
+    /// Name of the symbol that contains this symbol, e.g. the methodmap of a method.
+    pub container_name: Option<String>,
+    pub file_id: FileId,
+
+    /// Range of the name of the declaration, seen by the user.
+    pub range: lsp_types::Range,
+}
+
+pub(crate) fn document_symbols(db: &RootDatabase, file_id: FileId) -> Vec<DocumentSymbol> {
+    let sema = &Semantics::new(db);
+    let preprocessing_results = sema.preprocess_file(file_id);
+    let ctx = SymbolCtx {
+        sema,
+        tree: sema.parse(file_id),
+        source: db.preprocessed_text(file_id),
+        offsets: preprocessing_results.offsets(),
+    };
+
+    File::from(file_id)
+        .declarations(db)
+        .into_iter()
+        // Variants are declared at the top level, but belong to their enum.
+        .filter(|def| !matches!(def, FileDef::Variant(_)))
+        .filter_map(|def| ctx.symbol(DefResolution::from(def)))
+        .collect()
+}
+
+pub(crate) fn workspace_symbols(db: &RootDatabase, query: &str) -> Vec<WorkspaceSymbol> {
+    let sema = &Semantics::new(db);
+    db.known_files()
+        .into_iter()
+        .flat_map(|(file_id, _)| {
+            db.file_symbols(file_id)
+                .iter()
+                .filter_map(|symbol| {
+                    Some((fuzzy_score(query, &symbol.name)?, file_id, symbol.clone()))
+                })
+                .collect_vec()
+        })
+        .sorted_by(|(a_score, _, a), (b_score, _, b)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.name.len().cmp(&b.name.len()))
+                .then_with(|| a.name.cmp(&b.name))
+        })
+        .take(WORKSPACE_SYMBOLS_LIMIT)
+        // The ranges are only computed for the symbols that are returned, as they require the
+        // syntax tree of the file.
+        .filter_map(|(_, file_id, symbol)| {
+            let tree = sema.parse(file_id);
+            let preprocessing_results = sema.preprocess_file(file_id);
+            let node = symbol.def.source(db, &tree)?.value;
+
+            Some(WorkspaceSymbol {
+                name: symbol.name,
+                kind: symbol.kind,
+                container_name: symbol.container_name,
+                file_id,
+                range: u_range(preprocessing_results.offsets(), find_inner_name_range(&node)),
+            })
+        })
+        .collect()
+}
+
+struct SymbolCtx<'a> {
+    sema: &'a Semantics<'a, RootDatabase>,
+    tree: Tree,
+    source: Arc<str>,
+    offsets: &'a FxHashMap<u32, Vec<Offset>>,
+}
+
+impl SymbolCtx<'_> {
+    fn symbol(&self, def: DefResolution) -> Option<DocumentSymbol> {
+        let db = self.sema.db;
+        let kind = SymbolKind::from_def(db, &def)?;
+        let node = def.clone().source(db, &self.tree)?.value;
+        let name_range = find_inner_name_range(&node);
+        let name_node = node.descendant_for_point_range(
+            lsp_position_to_ts_point(&name_range.start),
+            lsp_position_to_ts_point(&name_range.end),
+        )?;
+        let name = name_node
+            .utf8_text(self.source.as_bytes())
+            .ok()?
+            .split_whitespace()
+            .join(" ");
+        let detail = match def {
+            DefResolution::Function(_)
+            | DefResolution::Global(_)
+            | DefResolution::Field(_)
+            | DefResolution::Property(_) => def.type_ref(db).map(|type_ref| type_ref.to_str()),
+            _ => None,
+        };
+        let children = symbol_index::children(db, &def)
+            .into_iter()
+            .filter_map(|child| self.symbol(child))
+            .sorted_by_key(|child| child.range.start)
+            .collect();
+
+        Some(DocumentSymbol {
+            name,
+            detail,
+            kind,
+            range: u_range(self.offsets, ts_range_to_lsp_range(&node.range())),
+            selection_range: u_range(self.offsets, name_range),
+            children,
+        })
+    }
+}
+
+/// Convert a range of the preprocessed text, which can span several lines, to a range seen by the
+/// user.
+fn u_range(offsets: &FxHashMap<u32, Vec<Offset>>, s_range: lsp_types::Range) -> lsp_types::Range {
+    let start =
+        s_range_to_u_range(offsets, lsp_types::Range::new(s_range.start, s_range.start)).start;
+    let end = s_range_to_u_range(offsets, lsp_types::Range::new(s_range.end, s_range.end)).end;
+
+    lsp_types::Range::new(start, end)
+}
+
+/// Score how well `name` matches `query`.
+///
+/// Returns [`None`] if the characters of `query` do not all appear in `name`, in order and
+/// ignoring case. Consecutive matches and matches at the start of a word score higher.
+fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let mut query = query.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut score = 0;
+    let mut prev_matched = false;
+    let mut prev_char: Option<char> = None;
+    for c in name.chars() {
+        let Some(&q) = query.peek() else {
+            break;
+        };
+        if c.to_lowercase().eq(q.to_lowercase()) {
+            score += 1;
+            if prev_matched {
+                score += 2;
+            }
+            let word_start = match prev_char {
+                None => true,
+                Some(prev) => prev == '_' || (prev.is_lowercase() && c.is_uppercase()),
+            };
+            if word_start {
+                score += 3;
+            }
+            prev_matched = true;
+            query.next();
+        } else {
+            prev_matched = false;
+        }
+        prev_char = Some(c);
+    }
+
+    query.peek().is_none().then_some(score)
+}
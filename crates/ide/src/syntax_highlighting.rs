@@ -1,11 +1,22 @@
-use std::fmt::{self, Debug, Write};
+use std::{
+    fmt::{self, Debug, Write},
+    sync::Arc,
+};
 
-use hir::Semantics;
+use base_db::{is_name_node, SourceDatabaseExt};
+use fxhash::FxHashMap;
+use hir::{DefResolution, HasSource, Semantics};
 use ide_db::{RootDatabase, SymbolKind};
 use itertools::Itertools;
-use syntax::utils::{intersect, ts_range_to_lsp_range};
+use preprocessor::PreprocessingResult;
+use syntax::{
+    utils::{deprecation_note, intersect, ts_range_to_lsp_range},
+    TSKind,
+};
 use vfs::FileId;
 
+use crate::references::s_range_to_user_range;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Highlight {
     pub tag: HlTag,
@@ -64,15 +75,37 @@ pub enum HlTag {
 #[repr(u8)]
 pub enum HlMod {
     Macro,
+    /// The name of a declaration.
+    Declaration,
+    /// A symbol declared with the `static` keyword.
+    Static,
+    /// A symbol declared with the `const` keyword.
+    Const,
+    /// A symbol preceded by a `#pragma deprecated` directive.
+    Deprecated,
+    /// A symbol declared in a file of an include directory.
+    DefaultLibrary,
 }
 
 impl HlMod {
-    const ALL: &'static [HlMod; HlMod::Macro as usize + 1] = &[HlMod::Macro];
+    const ALL: &'static [HlMod; HlMod::DefaultLibrary as usize + 1] = &[
+        HlMod::Macro,
+        HlMod::Declaration,
+        HlMod::Static,
+        HlMod::Const,
+        HlMod::Deprecated,
+        HlMod::DefaultLibrary,
+    ];
 
     #[allow(unused)]
     fn as_str(self) -> &'static str {
         match self {
             HlMod::Macro => "macro",
+            HlMod::Declaration => "declaration",
+            HlMod::Static => "static",
+            HlMod::Const => "const",
+            HlMod::Deprecated => "deprecated",
+            HlMod::DefaultLibrary => "default_library",
         }
     }
 
@@ -90,6 +123,10 @@ impl HlMods {
         self.0 & m.mask() == m.mask()
     }
 
+    pub fn insert(&mut self, m: HlMod) {
+        self.0 |= m.mask();
+    }
+
     pub fn iter(self) -> impl Iterator<Item = HlMod> {
         HlMod::ALL
             .iter()
@@ -127,8 +164,18 @@ pub(crate) fn highlight(
             None => (source_file, ts_range_to_lsp_range(&source_file.range())),
         }
     };
-    let mut res = Vec::new();
     let preprocessing_res = sema.preprocess_file(file_id);
+    let mut hl = Highlighter {
+        sema: &sema,
+        file_id,
+        text: db.file_text(file_id),
+        source: sema.preprocessed_text(file_id),
+        preprocessing_res: &preprocessing_res,
+        range_to_highlight,
+        defs: FxHashMap::default(),
+    };
+
+    let mut res = Vec::new();
     for (_, offsets) in preprocessing_res
         .offsets()
         .iter()
@@ -138,15 +185,143 @@ pub(crate) fn highlight(
             .iter()
             .filter(|offset| intersect(offset.range, range_to_highlight).is_some())
             .for_each(|offset| {
+                let mods = sema
+                    .find_macro_def(offset.file_id, offset.idx)
+                    .map(DefResolution::from)
+                    .and_then(|def| hl.def_highlight(def))
+                    .map(|(_, mods)| mods)
+                    .unwrap_or_default();
                 res.push(HlRange {
                     range: offset.range,
                     highlight: Highlight {
                         tag: HlTag::Symbol(SymbolKind::Macro),
-                        mods: HlMods::default(),
+                        mods,
                     },
                 });
             });
     }
 
+    let mut cursor = tree.root_node().walk();
+    'outer: loop {
+        let node = cursor.node();
+        // Skip the subtrees outside of the range. The preprocessor keeps the lines in sync with
+        // the user's text, unlike the columns, so only the lines can be compared.
+        let in_range = node.start_position().row as u32 <= range_to_highlight.end.line
+            && node.end_position().row as u32 >= range_to_highlight.start.line;
+        let highlighted = in_range
+            && matches!(
+                TSKind::from(node),
+                TSKind::identifier | TSKind::r#type | TSKind::this
+            )
+            && hl.highlight_name(node, &mut res).is_some();
+        if (in_range && !highlighted && cursor.goto_first_child()) || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+    res.sort_by_key(|hl_range| (hl_range.range.start.line, hl_range.range.start.character));
+
+    res
+}
+
+struct Highlighter<'a> {
+    sema: &'a Semantics<'a, RootDatabase>,
+    file_id: FileId,
+    text: Arc<str>,
+    source: Arc<str>,
+    preprocessing_res: &'a PreprocessingResult,
+    range_to_highlight: lsp_types::Range,
+
+    /// Highlights of the definitions already resolved in the file.
+    defs: FxHashMap<DefResolution, Option<(SymbolKind, HlMods)>>,
+}
+
+impl Highlighter<'_> {
+    /// Highlight a name that appears in the user's text, from the definition it refers to.
+    fn highlight_name(&mut self, node: tree_sitter::Node, acc: &mut Vec<HlRange>) -> Option<()> {
+        let name = node.utf8_text(self.source.as_bytes()).ok()?;
+        let range = s_range_to_user_range(
+            self.preprocessing_res,
+            &self.text,
+            ts_range_to_lsp_range(&node.range()),
+            name,
+        )?;
+        intersect(range, self.range_to_highlight)?;
+        let def = self.sema.find_def(self.file_id, &node)?;
+        let (kind, mut mods) = self.def_highlight(def)?;
+        if is_name_node(&node) {
+            mods.insert(HlMod::Declaration);
+        }
+        acc.push(HlRange {
+            range,
+            highlight: Highlight {
+                tag: HlTag::Symbol(kind),
+                mods,
+            },
+        });
+
+        Some(())
+    }
+
+    /// Get the kind of a definition and the modifiers of its declaration.
+    fn def_highlight(&mut self, def: DefResolution) -> Option<(SymbolKind, HlMods)> {
+        if let Some(res) = self.defs.get(&def) {
+            return *res;
+        }
+        let db = self.sema.db;
+        let res = SymbolKind::from_def(db, &def).map(|kind| {
+            let mut mods = HlMods::default();
+            let file_id = def.file_id(db);
+            if db.source_root(db.file_source_root(file_id)).is_include_dir {
+                mods.insert(HlMod::DefaultLibrary);
+            }
+            let tree = self.sema.parse(file_id);
+            let source = self.sema.preprocessed_text(file_id);
+            if let Some(node) = def.clone().source(db, &tree).map(|src| src.value) {
+                // The modifiers of a variable belong to the statement that declares it.
+                let decl = match TSKind::from(node) {
+                    TSKind::variable_declaration | TSKind::old_variable_declaration => {
+                        node.parent().unwrap_or(node)
+                    }
+                    _ => node,
+                };
+                for keyword in decl_keywords(decl) {
+                    match keyword {
+                        TSKind::anon_static => mods.insert(HlMod::Static),
+                        TSKind::anon_const => mods.insert(HlMod::Const),
+                        _ => (),
+                    }
+                }
+                if deprecation_note(decl, source.as_bytes()).is_some() {
+                    mods.insert(HlMod::Deprecated);
+                }
+            }
+            (kind, mods)
+        });
+        self.defs.insert(def, res);
+
+        res
+    }
+}
+
+/// Get the keywords of a declaration, including the ones of its visibility and storage class.
+fn decl_keywords(decl: tree_sitter::Node) -> Vec<TSKind> {
+    let mut res = Vec::new();
+    for child in decl.children(&mut decl.walk()) {
+        match TSKind::from(child) {
+            TSKind::visibility | TSKind::variable_storage_class => {
+                res.extend(child.children(&mut child.walk()).map(TSKind::from))
+            }
+            kind => res.push(kind),
+        }
+    }
+
     res
 }
@@ -171,11 +171,45 @@ impl fmt::Display for EvaluationError {
 
 impl error::Error for EvaluationError {}
 
+/// Error raised by a directive of the file itself, i.e. `#error`, `#warning` or a failed `#assert`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectiveError {
+    pub(super) text: String,
+    pub(super) range: Range,
+}
+
+impl PreprocessorError for DirectiveError {
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn range(&self) -> &Range {
+        &self.range
+    }
+}
+
+impl DirectiveError {
+    pub(super) fn new(text: String, range: Range) -> DirectiveError {
+        DirectiveError { text, range }
+    }
+}
+
+impl fmt::Display for DirectiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {:?}", self.text, self.range)
+    }
+}
+
+impl error::Error for DirectiveError {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct PreprocessorErrors {
     pub macro_not_found_errors: Vec<MacroNotFoundError>,
     pub evaluation_errors: Vec<EvaluationError>,
     pub unresolved_include_errors: Vec<UnresolvedIncludeError>,
+    pub error_directives: Vec<DirectiveError>,
+    pub warning_directives: Vec<DirectiveError>,
+    pub assertion_errors: Vec<DirectiveError>,
 }
 
 impl PreprocessorErrors {
@@ -183,5 +217,8 @@ impl PreprocessorErrors {
         self.macro_not_found_errors.shrink_to_fit();
         self.evaluation_errors.shrink_to_fit();
         self.unresolved_include_errors.shrink_to_fit();
+        self.error_directives.shrink_to_fit();
+        self.warning_directives.shrink_to_fit();
+        self.assertion_errors.shrink_to_fit();
     }
 }
@@ -11,7 +11,7 @@ use stdx::hashable_hash_map::HashableHashMap;
 use symbol::RangeLessSymbol;
 use vfs::FileId;
 
-use errors::{ExpansionError, PreprocessorErrors, UnresolvedIncludeError};
+use errors::{DirectiveError, ExpansionError, PreprocessorErrors, UnresolvedIncludeError};
 use evaluator::IfCondition;
 use macros::expand_identifier;
 use token::Token;
@@ -26,7 +26,7 @@ mod result;
 mod symbol;
 mod token;
 
-pub use errors::{EvaluationError, PreprocessorError};
+pub use errors::{DirectiveError, EvaluationError, PreprocessorError};
 pub use offset::Offset;
 pub use result::PreprocessingResult;
 
@@ -46,6 +46,17 @@ enum ConditionState {
     Active,
 }
 
+/// Macros defined by the compiler, which are not declared in any file.
+pub const BUILTIN_MACROS: &[&str] = &[
+    "__BINARY_NAME__",
+    "__BINARY_PATH__",
+    "__DATE__",
+    "__FILE__",
+    "__LINE__",
+    "__Pawn",
+    "__TIME__",
+];
+
 pub type MacrosMap = FxHashMap<SmolStr, Arc<Macro>>;
 pub type HMacrosMap = HashableHashMap<SmolStr, Arc<Macro>>;
 pub type ArgsMap = FxHashMap<u32, Vec<(Range, Range)>>;
@@ -106,6 +117,16 @@ impl Macro {
             body: vec![],
         }
     }
+
+    /// The [`file_id`](FileId) of the file the macro was defined in.
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    /// The index of the macro definition in its file.
+    pub fn idx(&self) -> u32 {
+        self.idx
+    }
 }
 
 /// Parse status of `using __intrinsics__.Handle;`.
@@ -420,6 +441,19 @@ where
     }
 
     fn process_if_directive(&mut self, symbol: &Symbol) {
+        if self.evaluate_condition(symbol).0 {
+            self.conditions_stack.push(ConditionState::Active);
+        } else {
+            self.skip_line_start_col = symbol.range.end.character;
+            self.conditions_stack.push(ConditionState::NotActivated);
+        }
+    }
+
+    /// Evaluate the condition following an `#if`, `#elseif` or `#assert` directive.
+    ///
+    /// Returns the result of the evaluation, defaulting to false if it failed, and the tokens of
+    /// the condition.
+    fn evaluate_condition(&mut self, symbol: &Symbol) -> (bool, Vec<Token>) {
         let line_nb = symbol.range.start.line;
         let mut if_condition = IfCondition::new(
             &mut self.macros,
@@ -443,12 +477,6 @@ where
             }
         };
 
-        if if_condition_eval {
-            self.conditions_stack.push(ConditionState::Active);
-        } else {
-            self.skip_line_start_col = symbol.range.end.character;
-            self.conditions_stack.push(ConditionState::NotActivated);
-        }
         self.errors
             .macro_not_found_errors
             .extend(if_condition.macro_not_found_errors);
@@ -458,6 +486,101 @@ where
                 self.out.push(String::new());
             }
         }
+
+        (if_condition_eval, if_condition.tokens)
+    }
+
+    fn process_assert_directive(&mut self, symbol: &Symbol) {
+        let (res, tokens) = self.evaluate_condition(symbol);
+        if res {
+            return;
+        }
+        let mut text = String::new();
+        let mut range = symbol.range;
+        for token in tokens
+            .iter()
+            .filter(|token| !matches!(token.token_kind(), TokenKind::Newline | TokenKind::Eof))
+        {
+            if !text.is_empty() {
+                text.push_str(&" ".repeat(token.delta().col.unsigned_abs() as usize));
+            }
+            text.push_str(&token.text());
+            range.end = token.range().end;
+        }
+        self.errors
+            .assertion_errors
+            .push(DirectiveError::new(text, range));
+    }
+
+    /// Record the message of an `#error` or `#warning` directive. The directive is kept as is in
+    /// the output.
+    fn process_message_directive(&mut self, symbol: &Symbol, is_error: bool) {
+        self.push_symbol(symbol);
+        let keyword = if is_error { "error" } else { "warning" };
+        let text = symbol.inline_text();
+        let mut message = text
+            .trim()
+            .trim_start_matches('#')
+            .trim_start()
+            .trim_start_matches(keyword)
+            .trim()
+            .to_string();
+        let mut range = symbol.range;
+        while self.lexer.in_preprocessor() {
+            let Some(symbol) = self.lexer.next() else {
+                break;
+            };
+            match symbol.token_kind {
+                TokenKind::Newline | TokenKind::Eof => {
+                    self.push_ws(&symbol);
+                    self.push_current_line();
+                    self.reset_current_line();
+                    break;
+                }
+                _ => {
+                    if !message.is_empty() {
+                        message.push_str(&" ".repeat(symbol.delta.col.unsigned_abs() as usize));
+                    }
+                    message.push_str(&symbol.text());
+                    range.end = symbol.range.end;
+                    self.push_symbol(&symbol);
+                }
+            }
+        }
+        let error = DirectiveError::new(message, range);
+        if is_error {
+            self.errors.error_directives.push(error);
+        } else {
+            self.errors.warning_directives.push(error);
+        }
+    }
+
+    /// Stop processing the file at an `#endinput` directive. The rest of the file is kept as
+    /// empty lines, to keep the line numbers in sync, and is marked as inactive.
+    fn process_endinput_directive(&mut self, symbol: &Symbol) {
+        self.push_symbol(symbol);
+        let directive_line = symbol.range.start.line;
+        while let Some(symbol) = self.lexer.next() {
+            match symbol.token_kind {
+                TokenKind::Newline | TokenKind::Eof => {
+                    if symbol.range.start.line != directive_line {
+                        self.skipped_lines.push(lsp_types::Range::new(
+                            Position::new(symbol.range.start.line, 0),
+                            Position::new(symbol.range.start.line, symbol.range.start.character),
+                        ));
+                    }
+                    self.push_current_line();
+                    self.reset_current_line();
+                    if symbol.token_kind == TokenKind::Eof {
+                        break;
+                    }
+                }
+                // Skip any token that is not a newline.
+                _ => (),
+            }
+        }
+        // The conditions the directive is nested in, e.g. an include guard, are never closed.
+        self.conditions_stack.clear();
     }
 
     fn process_else_directive(&mut self, symbol: &Symbol) -> anyhow::Result<()> {
@@ -614,6 +737,10 @@ where
             PreprocDir::MElse => self.process_else_directive(symbol)?,
             PreprocDir::MInclude => self.process_include_directive(symbol, false),
             PreprocDir::MTryinclude => self.process_include_directive(symbol, true),
+            PreprocDir::MError => self.process_message_directive(symbol, true),
+            PreprocDir::MWarning => self.process_message_directive(symbol, false),
+            PreprocDir::MAssert => self.process_assert_directive(symbol),
+            PreprocDir::MEndinput => self.process_endinput_directive(symbol),
             _ => self.push_symbol(symbol),
         }
 
@@ -630,3 +630,12 @@ void foo(){
 
     assert_preproc_eq!(input);
 }
+
+#[test]
+fn endinput_directive_1() {
+    let input = r#"int foo;
+#endinput
+int bar;"#;
+
+    assert_preproc_eq!(input);
+}
@@ -10,7 +10,7 @@
 // mod google_cpu_profiler;
 // mod hprof;
 mod memory_usage;
-// mod stop_watch;
+mod stop_watch;
 // mod tree;
 
 use std::cell::RefCell;
@@ -18,7 +18,7 @@ use std::cell::RefCell;
 pub use crate::{
     //     hprof::{heartbeat, heartbeat_span, init, init_from, span},
     memory_usage::{Bytes, MemoryUsage},
-    //     stop_watch::{StopWatch, StopWatchSpan},
+    stop_watch::{StopWatch, StopWatchSpan},
 };
 
 pub use countme;
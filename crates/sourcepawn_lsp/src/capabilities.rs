@@ -1,8 +1,13 @@
 use ide::WideEncoding;
 use lsp_types::{
-    ClientCapabilities, HoverProviderCapability, MarkupKind, OneOf, PositionEncodingKind,
-    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, ServerCapabilities,
-    TextDocumentSyncCapability, TextDocumentSyncKind,
+    CallHierarchyServerCapability, ClientCapabilities, CodeActionKind, CodeActionOptions,
+    CodeActionProviderCapability, CompletionOptions, CompletionOptionsCompletionItem,
+    DeclarationCapability, DiagnosticOptions, DiagnosticServerCapabilities,
+    DocumentOnTypeFormattingOptions, FoldingRangeProviderCapability, HoverProviderCapability,
+    ImplementationProviderCapability, MarkupKind, OneOf, PositionEncodingKind, RenameOptions,
+    SelectionRangeProviderCapability, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensOptions, ServerCapabilities, SignatureHelpOptions, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TypeDefinitionProviderCapability,
 };
 
 use crate::{
@@ -26,6 +31,14 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
         )),
         hover_provider: Some(HoverProviderCapability::Simple(true)),
         definition_provider: Some(OneOf::Left(true)),
+        declaration_provider: Some(DeclarationCapability::Simple(true)),
+        implementation_provider: Some(ImplementationProviderCapability::Simple(true)),
+        type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(true)),
+        references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: Default::default(),
+        })),
         semantic_tokens_provider: Some(
             SemanticTokensOptions {
                 legend: SemanticTokensLegend {
@@ -39,19 +52,13 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
             }
             .into(),
         ),
-        /*
         completion_provider: Some(CompletionOptions {
             trigger_characters: Some(vec![
                 "<".to_string(),
                 '"'.to_string(),
-                "'".to_string(),
                 "/".to_string(),
-                "\\".to_string(),
                 ".".to_string(),
                 ":".to_string(),
-                " ".to_string(),
-                "$".to_string(),
-                "*".to_string(),
             ]),
             resolve_provider: Some(true),
             completion_item: Some(CompletionOptionsCompletionItem {
@@ -59,17 +66,34 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
             }),
             ..Default::default()
         }),
-        hover_provider: Some(HoverProviderCapability::Simple(true)),
         signature_help_provider: Some(SignatureHelpOptions {
             trigger_characters: Some(vec![",".to_string(), "(".to_string()]),
             retrigger_characters: Some(vec![",".to_string(), "(".to_string()]),
             ..Default::default()
         }),
-        references_provider: Some(OneOf::Left(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+            ..Default::default()
+        })),
+        document_highlight_provider: Some(OneOf::Left(true)),
         document_symbol_provider: Some(OneOf::Left(true)),
-        rename_provider: Some(OneOf::Left(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+        inlay_hint_provider: Some(OneOf::Left(true)),
         call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
-        */
+        folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+        selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        document_range_formatting_provider: Some(OneOf::Left(true)),
+        document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+            first_trigger_character: "}".to_string(),
+            more_trigger_character: Some(vec![";".to_string()]),
+        }),
+        diagnostic_provider: Some(DiagnosticServerCapabilities::Options(DiagnosticOptions {
+            identifier: None,
+            inter_file_dependencies: true,
+            workspace_diagnostics: config.workspace_diagnostics(),
+            work_done_progress_options: Default::default(),
+        })),
         ..Default::default()
     }
 }
@@ -83,6 +107,10 @@ pub trait ClientCapabilitiesExt {
 
     fn has_hover_markdown_support(&self) -> bool;
 
+    fn has_line_folding_only_support(&self) -> bool;
+
+    fn has_pull_diagnostics_support(&self) -> bool;
+
     fn has_pull_configuration_support(&self) -> bool;
 
     fn has_push_configuration_support(&self) -> bool;
@@ -120,6 +148,21 @@ impl ClientCapabilitiesExt for ClientCapabilities {
             .is_some()
     }
 
+    fn has_line_folding_only_support(&self) -> bool {
+        self.text_document
+            .as_ref()
+            .and_then(|cap| cap.folding_range.as_ref())
+            .and_then(|cap| cap.line_folding_only)
+            == Some(true)
+    }
+
+    fn has_pull_diagnostics_support(&self) -> bool {
+        self.text_document
+            .as_ref()
+            .and_then(|cap| cap.diagnostic.as_ref())
+            .is_some()
+    }
+
     fn has_pull_configuration_support(&self) -> bool {
         self.workspace.as_ref().and_then(|cap| cap.configuration) == Some(true)
     }
@@ -145,7 +188,8 @@ impl ClientCapabilitiesExt for ClientCapabilities {
 mod tests {
     use super::*;
     use lsp_types::{
-        DocumentSymbolClientCapabilities, GotoCapability, HoverClientCapabilities,
+        DiagnosticClientCapabilities, DocumentSymbolClientCapabilities,
+        FoldingRangeClientCapabilities, GotoCapability, HoverClientCapabilities,
         TextDocumentClientCapabilities, WindowClientCapabilities,
     };
 
@@ -229,4 +273,43 @@ mod tests {
         let capabilities = ClientCapabilities::default();
         assert!(!capabilities.has_hover_markdown_support());
     }
+
+    #[test]
+    fn test_has_line_folding_only_support_true() {
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                folding_range: Some(FoldingRangeClientCapabilities {
+                    line_folding_only: Some(true),
+                    ..FoldingRangeClientCapabilities::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(capabilities.has_line_folding_only_support());
+    }
+
+    #[test]
+    fn test_has_line_folding_only_support_false() {
+        let capabilities = ClientCapabilities::default();
+        assert!(!capabilities.has_line_folding_only_support());
+    }
+
+    #[test]
+    fn test_has_pull_diagnostics_support_true() {
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                diagnostic: Some(DiagnosticClientCapabilities::default()),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(capabilities.has_pull_diagnostics_support());
+    }
+
+    #[test]
+    fn test_has_pull_diagnostics_support_false() {
+        let capabilities = ClientCapabilities::default();
+        assert!(!capabilities.has_pull_diagnostics_support());
+    }
 }
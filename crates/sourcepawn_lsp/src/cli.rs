@@ -0,0 +1,12 @@
+//! Subcommands of the binary, which run the analysis on a workspace without a client.
+
+mod analysis_stats;
+mod check;
+mod docgen;
+mod load;
+
+pub use self::{
+    analysis_stats::{analysis_stats, AnalysisStats, Phase, Stats},
+    check::{check, Check, CheckResult, OutputFormat},
+    docgen::{docgen, Docgen, Docs},
+};
@@ -0,0 +1,184 @@
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use hir::{DefWithBody, FileDef};
+use hir_def::{DefDatabase, InferenceDiagnostic};
+use ide_db::RootDatabase;
+use preprocessor::db::PreprocDatabase;
+use profile::{StopWatch, StopWatchSpan};
+use vfs::FileId;
+
+use crate::cli::load::{analysis_host, canonicalize, known_files, load_vfs};
+
+/// Arguments of the `analysis-stats` subcommand.
+#[derive(Debug, Clone, Args)]
+pub struct AnalysisStats {
+    /// Directory of the plugins to analyze
+    path: PathBuf,
+
+    /// Include directory, can be repeated
+    #[clap(short, long = "include", name = "INCLUDE_DIR")]
+    include_directories: Vec<PathBuf>,
+
+    /// Number of threads used to prime the caches, defaults to the number of CPUs
+    #[clap(long)]
+    num_threads: Option<u8>,
+
+    /// Print the unresolved identifiers
+    #[clap(long)]
+    print_unresolved: bool,
+}
+
+/// A phase of the analysis, with the time and memory spent in it.
+pub struct Phase {
+    pub name: &'static str,
+    pub span: StopWatchSpan,
+
+    /// What the phase processed, e.g. `3 files`.
+    pub detail: Option<String>,
+}
+
+/// The phases of the analysis of a workspace.
+pub struct Stats {
+    pub phases: Vec<Phase>,
+
+    /// The unresolved identifiers, with the name of the function they are used in.
+    pub unresolved: Vec<(String, String)>,
+}
+
+impl AnalysisStats {
+    /// Load the workspace and report the time and memory spent in each phase of the analysis.
+    pub fn run(self) -> anyhow::Result<()> {
+        let num_threads = self
+            .num_threads
+            .unwrap_or_else(|| num_cpus::get().try_into().unwrap_or(u8::MAX));
+        let stats = analysis_stats(&self.path, &self.include_directories, num_threads)?;
+        for phase in &stats.phases {
+            match &phase.detail {
+                Some(detail) => println!("{}: {} ({detail})", phase.name, phase.span),
+                None => println!("{}: {}", phase.name, phase.span),
+            }
+        }
+        if self.print_unresolved {
+            for (name, def_name) in &stats.unresolved {
+                println!("  unresolved identifier `{name}` in `{def_name}`");
+            }
+        }
+        println!("Total memory: {}", profile::MemoryUsage::now());
+
+        Ok(())
+    }
+}
+
+/// Load the workspace of the plugins of a directory and run each phase of the analysis on it.
+pub fn analysis_stats(
+    path: &Path,
+    include_directories: &[PathBuf],
+    num_threads: u8,
+) -> anyhow::Result<Stats> {
+    let root = canonicalize(path)?;
+    let include_directories = include_directories
+        .iter()
+        .map(|path| canonicalize(path))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut phases = vec![];
+    let mut phase = |name, mut sw: StopWatch, detail| {
+        phases.push(Phase {
+            name,
+            span: sw.elapsed(),
+            detail,
+        })
+    };
+
+    let sw = StopWatch::start();
+    let vfs = load_vfs(&root, &include_directories);
+    let host = analysis_host(&vfs, &root, &include_directories);
+    let files = known_files(&vfs)
+        .into_iter()
+        .map(|(file_id, _)| file_id)
+        .collect::<Vec<_>>();
+    phase("Vfs load", sw, Some(format!("{} files", files.len())));
+
+    let paths = vfs.get_url_map();
+    let sw = StopWatch::start();
+    host.analysis().parallel_prime_caches(
+        num_threads,
+        |_| (),
+        |id| {
+            paths.get(&id).and_then(|path| {
+                path.name_and_extension()
+                    .map(|(name, ext)| format!("{}.{}", name, ext.unwrap_or_default()))
+            })
+        },
+    )?;
+    phase("Prime caches", sw, Some(format!("{num_threads} threads")));
+
+    // The phases are measured on a fresh database, as priming the caches already ran most of
+    // them.
+    let host = analysis_host(&vfs, &root, &include_directories);
+    let db = host.raw_database();
+
+    let sw = StopWatch::start();
+    for &file_id in &files {
+        db.preprocess_file(file_id);
+    }
+    phase("Preprocess", sw, None);
+
+    let sw = StopWatch::start();
+    for &file_id in &files {
+        db.parse(file_id);
+    }
+    phase("Parse", sw, None);
+
+    let sw = StopWatch::start();
+    for &file_id in &files {
+        db.file_item_tree(file_id);
+    }
+    phase("Item tree", sw, None);
+
+    let sw = StopWatch::start();
+    for &file_id in &files {
+        db.file_def_map(file_id);
+    }
+    phase("Def map", sw, None);
+
+    let bodies = files
+        .iter()
+        .flat_map(|&file_id| defs_with_body(db, file_id))
+        .collect::<Vec<_>>();
+
+    let sw = StopWatch::start();
+    for &def in &bodies {
+        db.body_with_source_map(def.into());
+    }
+    phase("Body lowering", sw, Some(format!("{} bodies", bodies.len())));
+
+    let sw = StopWatch::start();
+    let mut unresolved = vec![];
+    for &def in &bodies {
+        for diagnostic in db.infer(def.into()).diagnostics.iter() {
+            if let InferenceDiagnostic::UnresolvedIdent { name, .. } = diagnostic {
+                let def_name = def.name(db).map(|it| it.to_string()).unwrap_or_default();
+                unresolved.push((name.to_string(), def_name));
+            }
+        }
+    }
+    phase("Inference", sw, Some(format!("{} unresolved identifiers", unresolved.len())));
+
+    Ok(Stats { phases, unresolved })
+}
+
+/// The functions, typedefs and functags declared in the file, including the methods of its
+/// enum structs and methodmaps.
+fn defs_with_body(db: &RootDatabase, file_id: FileId) -> Vec<DefWithBody> {
+    let mut res = vec![];
+    for def in hir::File::from(file_id).declarations(db) {
+        match def {
+            FileDef::EnumStruct(it) => res.extend(it.methods(db).into_iter().map(Into::into)),
+            FileDef::Methodmap(it) => res.extend(it.own_methods(db).into_iter().map(Into::into)),
+            _ => res.extend(def.as_def_with_body()),
+        }
+    }
+
+    res
+}
@@ -0,0 +1,257 @@
+use std::{
+    env,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use clap::{Args, ValueEnum};
+use ide::{DiagnosticsConfig, Severity};
+use paths::AbsPathBuf;
+use serde::Serialize;
+use serde_json::json;
+use stdx::format_to;
+use vfs::VfsPath;
+
+use crate::{
+    cli::load::{canonicalize, load_workspace},
+    lsp::to_proto::url_from_abs_path,
+    version::version,
+};
+
+/// Arguments of the `check` subcommand.
+#[derive(Debug, Clone, Args)]
+pub struct Check {
+    /// Directory of the plugins to check, or path of a single plugin
+    path: PathBuf,
+
+    /// Include directory, can be repeated
+    #[clap(short, long = "include", name = "INCLUDE_DIR")]
+    include_directories: Vec<PathBuf>,
+
+    /// Format of the output
+    #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One line per diagnostic
+    Text,
+    /// An array of diagnostics
+    Json,
+    /// A SARIF 2.1.0 log, as consumed by code scanning tools
+    Sarif,
+}
+
+/// A diagnostic of one of the checked plugins.
+#[derive(Debug, Serialize)]
+struct CheckDiagnostic {
+    #[serde(serialize_with = "serialize_path")]
+    path: AbsPathBuf,
+    range: lsp_types::Range,
+    #[serde(serialize_with = "serialize_severity")]
+    severity: Severity,
+    code: &'static str,
+    message: String,
+}
+
+/// The diagnostics of the checked plugins.
+#[derive(Debug)]
+pub struct CheckResult {
+    diagnostics: Vec<CheckDiagnostic>,
+
+    /// Whether any of the diagnostics is an error.
+    pub errored: bool,
+}
+
+impl Check {
+    /// Check the plugins and print their diagnostics.
+    ///
+    /// Returns whether any of the diagnostics is an error.
+    pub fn run(self) -> anyhow::Result<bool> {
+        let result = check(&self.path, &self.include_directories)?;
+        let current_dir = env::current_dir().unwrap_or_default();
+        writeln!(io::stdout().lock(), "{}", result.render(self.format, &current_dir)?)?;
+
+        Ok(result.errored)
+    }
+}
+
+/// Compute the diagnostics of the plugins of a directory, or of a single plugin.
+pub fn check(path: &Path, include_directories: &[PathBuf]) -> anyhow::Result<CheckResult> {
+    let path = canonicalize(path)?;
+    let (root, plugin) = if Path::is_file(path.as_ref()) {
+        let root = path
+            .parent()
+            .context("expected the plugin to be in a directory")?
+            .to_path_buf();
+        (root, Some(VfsPath::from(path)))
+    } else {
+        (path, None)
+    };
+    let include_directories = include_directories
+        .iter()
+        .map(|path| canonicalize(path))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let (host, vfs) = load_workspace(&root, &include_directories);
+    let analysis = host.analysis();
+    let config = DiagnosticsConfig {
+        enabled: true,
+        disable_experimental: false,
+        disabled: Default::default(),
+    };
+
+    let mut roots = analysis
+        .project_roots()?
+        .into_iter()
+        .map(|file_id| (vfs.file_path(file_id), file_id))
+        .filter(|(path, _)| plugin.as_ref().map_or(true, |plugin| plugin == path))
+        .collect::<Vec<_>>();
+    roots.sort();
+
+    let mut diagnostics = vec![];
+    for (path, file_id) in roots {
+        let Some(path) = path.as_path() else {
+            continue;
+        };
+        diagnostics.extend(
+            analysis
+                .diagnostics(&config, file_id)?
+                .into_iter()
+                // Inactive code is only reported to be greyed out by the client.
+                .filter(|diagnostic| diagnostic.code.as_str() != "inactive-code")
+                .map(|diagnostic| CheckDiagnostic {
+                    path: path.to_path_buf(),
+                    range: diagnostic.range,
+                    severity: diagnostic.severity,
+                    code: diagnostic.code.as_str(),
+                    message: diagnostic.message,
+                }),
+        );
+    }
+    let errored = diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error);
+
+    Ok(CheckResult {
+        diagnostics,
+        errored,
+    })
+}
+
+impl CheckResult {
+    /// Render the diagnostics in the given format. The paths of the text format are relative to
+    /// `base` when they are inside of it.
+    pub fn render(&self, format: OutputFormat, base: &Path) -> anyhow::Result<String> {
+        let res = match format {
+            OutputFormat::Text => text(&self.diagnostics, base),
+            OutputFormat::Json => serde_json::to_string_pretty(&self.diagnostics)?,
+            OutputFormat::Sarif => serde_json::to_string_pretty(&sarif(&self.diagnostics))?,
+        };
+
+        Ok(res)
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::WeakWarning => "hint",
+    }
+}
+
+fn serialize_path<S: serde::Serializer>(
+    path: &AbsPathBuf,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(path)
+}
+
+fn serialize_severity<S: serde::Serializer>(
+    severity: &Severity,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(severity_name(*severity))
+}
+
+/// Format the diagnostics as `path:line:column: severity[code]: message`, followed by a summary.
+fn text(diagnostics: &[CheckDiagnostic], base: &Path) -> String {
+    let mut res = String::new();
+    for diagnostic in diagnostics {
+        let path: &Path = diagnostic.path.as_ref();
+        format_to!(
+            res,
+            "{}:{}:{}: {}[{}]: {}\n",
+            path.strip_prefix(base).unwrap_or(path).display(),
+            diagnostic.range.start.line + 1,
+            diagnostic.range.start.character + 1,
+            severity_name(diagnostic.severity),
+            diagnostic.code,
+            diagnostic.message
+        );
+    }
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    };
+    format_to!(
+        res,
+        "{} error(s), {} warning(s), {} hint(s)",
+        count(Severity::Error),
+        count(Severity::Warning),
+        count(Severity::WeakWarning)
+    );
+
+    res
+}
+
+/// Build a SARIF 2.1.0 log of the diagnostics.
+fn sarif(diagnostics: &[CheckDiagnostic]) -> serde_json::Value {
+    let results = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let level = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::WeakWarning => "note",
+            };
+            // SARIF lines and columns are 1-based.
+            json!({
+                "ruleId": diagnostic.code,
+                "level": level,
+                "message": { "text": diagnostic.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": url_from_abs_path(&diagnostic.path) },
+                        "region": {
+                            "startLine": diagnostic.range.start.line + 1,
+                            "startColumn": diagnostic.range.start.character + 1,
+                            "endLine": diagnostic.range.end.line + 1,
+                            "endColumn": diagnostic.range.end.character + 1,
+                        }
+                    }
+                }]
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "sourcepawn_lsp",
+                    "version": version(),
+                    "informationUri": "https://github.com/Sarrus1/sourcepawn-vscode",
+                }
+            },
+            "results": results,
+        }]
+    })
+}
@@ -0,0 +1,325 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use clap::Args;
+use ide::ItemDoc;
+use ide_db::SymbolKind;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::json;
+use stdx::format_to;
+
+use crate::{
+    cli::load::{canonicalize, load_workspace},
+    version::version,
+};
+
+/// Arguments of the `docgen` subcommand.
+#[derive(Debug, Clone, Args)]
+pub struct Docgen {
+    /// Directory of the include files to document, or path of a single include file
+    path: PathBuf,
+
+    /// Include directory, can be repeated
+    #[clap(short, long = "include", name = "INCLUDE_DIR")]
+    include_directories: Vec<PathBuf>,
+
+    /// Directory where the documentation is written
+    #[clap(short, long, default_value = "docs")]
+    output: PathBuf,
+}
+
+/// The documentation of an include file.
+struct FileDocs {
+    /// Path of the include file, relative to the documented directory.
+    path: PathBuf,
+    items: Vec<ItemDoc>,
+}
+
+impl FileDocs {
+    /// Path of the page of the file, relative to the output directory.
+    fn page(&self) -> PathBuf {
+        self.path.with_extension("html")
+    }
+}
+
+/// The generated documentation.
+#[derive(Debug)]
+pub struct Docs {
+    /// Number of documented include files.
+    pub files: usize,
+
+    /// Contents of the HTML page of each include file, of the HTML index of the pages and of the
+    /// JSON index of the documented items, by path relative to the output directory.
+    pub pages: Vec<(PathBuf, String)>,
+}
+
+impl Docgen {
+    /// Write an HTML page per include file, an HTML index of the pages and a JSON index of the
+    /// documented items.
+    pub fn run(self) -> anyhow::Result<()> {
+        let docs = docgen(&self.path, &self.include_directories)?;
+        for (path, contents) in &docs.pages {
+            let path = self.output.join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, contents)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
+
+        println!("Documented {} file(s) in {}", docs.files, self.output.display());
+
+        Ok(())
+    }
+}
+
+/// Generate the documentation of the include files of a directory, or of a single include file.
+pub fn docgen(path: &Path, include_directories: &[PathBuf]) -> anyhow::Result<Docs> {
+    let path = canonicalize(path)?;
+    let root = if Path::is_file(path.as_ref()) {
+        path.parent()
+            .context("expected the include file to be in a directory")?
+            .to_path_buf()
+    } else {
+        path.clone()
+    };
+    let include_directories = include_directories
+        .iter()
+        .map(|path| canonicalize(path))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let (host, vfs) = load_workspace(&root, &include_directories);
+    let analysis = host.analysis();
+
+    let mut files = vec![];
+    for (file_id, vfs_path) in vfs.iter() {
+        let Some(file_path) = vfs_path.as_path() else {
+            continue;
+        };
+        let file_path: &Path = file_path.as_ref();
+        let is_include = file_path.extension().map_or(false, |ext| ext == "inc");
+        if !is_include || !file_path.starts_with(&path) {
+            continue;
+        }
+        files.push(FileDocs {
+            path: file_path.strip_prefix(&root)?.to_path_buf(),
+            items: analysis.file_docs(file_id)?,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut pages = files
+        .iter()
+        .map(|file| (file.page(), file_page(file)))
+        .collect::<Vec<_>>();
+    pages.push((PathBuf::from("index.html"), index_page(&files)));
+    pages.push((
+        PathBuf::from("index.json"),
+        serde_json::to_string_pretty(&json_index(&files))?,
+    ));
+
+    Ok(Docs {
+        files: files.len(),
+        pages,
+    })
+}
+
+fn kind_name(kind: SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::Macro => "define",
+        SymbolKind::Function => "function",
+        SymbolKind::Forward => "forward",
+        SymbolKind::Native => "native",
+        SymbolKind::Method => "method",
+        SymbolKind::Global => "global",
+        SymbolKind::Local => "local",
+        SymbolKind::Param => "param",
+        SymbolKind::Enum => "enum",
+        SymbolKind::Variant => "variant",
+        SymbolKind::EnumStruct => "enum struct",
+        SymbolKind::Field => "field",
+        SymbolKind::Methodmap => "methodmap",
+        SymbolKind::Property => "property",
+        SymbolKind::Typedef => "typedef",
+        SymbolKind::Typeset => "typeset",
+        SymbolKind::Functag => "functag",
+        SymbolKind::Funcenum => "funcenum",
+    }
+}
+
+/// Build the JSON index of the documented files and their items.
+fn json_index(files: &[FileDocs]) -> serde_json::Value {
+    fn item_json(item: &ItemDoc) -> serde_json::Value {
+        json!({
+            "name": item.name,
+            "kind": kind_name(item.kind),
+            "signature": item.signature,
+            "description": item.description,
+            "params": item
+                .params
+                .iter()
+                .map(|(name, description)| json!({ "name": name, "description": description }))
+                .collect::<Vec<_>>(),
+            "returns": item.returns,
+            "deprecated": item.deprecated,
+            "children": item.children.iter().map(item_json).collect::<Vec<_>>(),
+        })
+    }
+
+    json!({
+        "version": version(),
+        "files": files
+            .iter()
+            .map(|file| json!({
+                "path": path_to_url(&file.path),
+                "page": path_to_url(&file.page()),
+                "items": file.items.iter().map(item_json).collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: auto; padding: 1em; }
+pre { background: #f4f4f4; padding: 0.5em; overflow-x: auto; }
+code { font-family: monospace; }
+.kind { color: #666; font-weight: normal; }
+.deprecated { color: #a00; }
+.children { margin-left: 1.5em; }";
+
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{title}</title>
+<style>
+{STYLE}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+",
+        title = escape(title),
+    )
+}
+
+fn index_page(files: &[FileDocs]) -> String {
+    let mut body = String::from("<h1>API reference</h1>\n<ul>\n");
+    for file in files {
+        format_to!(
+            body,
+            "<li><a href=\"{}\">{}</a> ({} items)</li>\n",
+            escape(&path_to_url(&file.page())),
+            escape(&path_to_url(&file.path)),
+            file.items.len()
+        );
+    }
+    body.push_str("</ul>\n");
+
+    html_page("API reference", &body)
+}
+
+fn file_page(file: &FileDocs) -> String {
+    let title = path_to_url(&file.path);
+    let depth = file.path.components().count().saturating_sub(1);
+    let mut body = format!(
+        "<p><a href=\"{}index.html\">Index</a></p>\n<h1>{}</h1>\n<ul>\n",
+        "../".repeat(depth),
+        escape(&title)
+    );
+    for item in &file.items {
+        format_to!(body, "<li><a href=\"#{0}\">{0}</a></li>\n", escape(&item.name));
+    }
+    body.push_str("</ul>\n");
+    for item in &file.items {
+        item_html(&mut body, item, "", 2);
+    }
+
+    html_page(&title, &body)
+}
+
+/// Write the section of an item, whose anchor is the name of the item prefixed by the anchors of
+/// the items that contain it.
+fn item_html(out: &mut String, item: &ItemDoc, anchor_prefix: &str, level: usize) {
+    let anchor = format!("{anchor_prefix}{}", item.name);
+    format_to!(
+        out,
+        "<section id=\"{}\">\n<h{level}><span class=\"kind\">{}</span> {}</h{level}>\n",
+        escape(&anchor),
+        kind_name(item.kind),
+        escape(&item.name),
+    );
+    format_to!(out, "<pre><code>{}</code></pre>\n", escape(&item.signature));
+    if let Some(note) = &item.deprecated {
+        format_to!(
+            out,
+            "<p class=\"deprecated\"><strong>Deprecated</strong> {}</p>\n",
+            escape(note)
+        );
+    }
+    out.push_str(&markdown_to_html(&item.description));
+    if !item.params.is_empty() {
+        out.push_str("<h4>Parameters</h4>\n<dl>\n");
+        for (name, description) in &item.params {
+            format_to!(
+                out,
+                "<dt><code>{}</code></dt>\n<dd>{}</dd>\n",
+                escape(name),
+                markdown_to_html(description)
+            );
+        }
+        out.push_str("</dl>\n");
+    }
+    if let Some(returns) = &item.returns {
+        format_to!(out, "<h4>Returns</h4>\n{}\n", markdown_to_html(returns));
+    }
+    if !item.children.is_empty() {
+        out.push_str("<div class=\"children\">\n");
+        for child in &item.children {
+            item_html(out, child, &format!("{anchor}."), (level + 1).min(6));
+        }
+        out.push_str("</div>\n");
+    }
+    out.push_str("</section>\n");
+}
+
+/// Render the markdown produced by [`Documentation::to_markdown`](ide_db::Documentation), which
+/// only uses paragraphs, code spans, emphasis and strong emphasis.
+fn markdown_to_html(text: &str) -> String {
+    lazy_static! {
+        static ref CODE_RE: Regex = Regex::new(r"`([^`]+)`").unwrap();
+        static ref STRONG_RE: Regex = Regex::new(r"\*\*([^*]+)\*\*").unwrap();
+        static ref EM_RE: Regex = Regex::new(r"\b_([^_]+)_\b").unwrap();
+    }
+    let mut res = String::new();
+    for paragraph in text.split("\n\n").map(str::trim).filter(|it| !it.is_empty()) {
+        let paragraph = escape(&paragraph.replace("\\<", "<").replace("\\>", ">"));
+        let paragraph = CODE_RE.replace_all(&paragraph, "<code>${1}</code>");
+        let paragraph = STRONG_RE.replace_all(&paragraph, "<strong>${1}</strong>");
+        let paragraph = EM_RE.replace_all(&paragraph, "<em>${1}</em>");
+        format_to!(res, "<p>{paragraph}</p>\n");
+    }
+
+    res
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Join the components of a relative path with `/`, as expected in URLs.
+fn path_to_url(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
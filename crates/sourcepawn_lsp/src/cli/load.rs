@@ -0,0 +1,113 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::Context;
+use base_db::{Change, FileExtension, SourceRootConfig};
+use crossbeam::channel::unbounded;
+use ide::AnalysisHost;
+use itertools::Itertools;
+use paths::AbsPathBuf;
+use vfs::{
+    loader::{self, Handle},
+    FileId, Vfs, VfsPath,
+};
+
+use crate::line_index::LineEndings;
+
+/// Load the `.sp` and `.inc` files of `root` and of the include directories, and build an
+/// [`AnalysisHost`] from them, the same way the server does once the workspace has been scanned.
+pub(crate) fn load_workspace(
+    root: &AbsPathBuf,
+    include_directories: &[AbsPathBuf],
+) -> (AnalysisHost, Vfs) {
+    let vfs = load_vfs(root, include_directories);
+    let host = analysis_host(&vfs, root, include_directories);
+
+    (host, vfs)
+}
+
+/// Load the `.sp` and `.inc` files of `root` and of the include directories.
+pub(crate) fn load_vfs(root: &AbsPathBuf, include_directories: &[AbsPathBuf]) -> Vfs {
+    let (sender, receiver) = unbounded::<loader::Message>();
+    let mut loader: vfs_notify::NotifyHandle =
+        Handle::spawn(Box::new(move |msg| sender.send(msg).unwrap()));
+    let mut load = include_directories
+        .iter()
+        .cloned()
+        .map(loader::Entry::sp_files_recursively)
+        .collect_vec();
+    load.push(loader::Entry::sp_files_recursively(root.clone()));
+    loader.set_config(loader::Config {
+        load,
+        watch: vec![],
+        version: 0,
+    });
+
+    let mut vfs = Vfs::default();
+    for message in receiver {
+        match message {
+            loader::Message::Progress {
+                n_total, n_done, ..
+            } => {
+                if n_done == n_total {
+                    break;
+                }
+            }
+            loader::Message::Loaded { files } => {
+                for (path, contents) in files {
+                    vfs.set_file_contents(VfsPath::from(path), contents);
+                }
+            }
+        }
+    }
+
+    vfs
+}
+
+/// Build a new [`AnalysisHost`] from the files of the [`Vfs`].
+pub(crate) fn analysis_host(
+    vfs: &Vfs,
+    root: &AbsPathBuf,
+    include_directories: &[AbsPathBuf],
+) -> AnalysisHost {
+    let mut change = Change::new();
+    for (file_id, _) in vfs.iter() {
+        let text = String::from_utf8(vfs.file_contents(file_id).to_vec())
+            .ok()
+            .map(|text| Arc::from(LineEndings::normalize(text).0));
+        change.change_file(file_id, text);
+    }
+
+    // The first root is the local one, see `SourceRootConfig::partition`.
+    let mut source_root_config = SourceRootConfig::default();
+    let mut roots = vec![VfsPath::from(root.clone())];
+    roots.extend(include_directories.iter().cloned().map(VfsPath::from));
+    source_root_config.fsc.set_roots(roots);
+    change.set_roots(source_root_config.partition(vfs));
+
+    let mut host = AnalysisHost::new(None);
+    host.apply_change(change);
+    host.set_known_files(known_files(vfs));
+
+    host
+}
+
+/// The files of the [`Vfs`] with a SourcePawn extension, sorted by id.
+pub(crate) fn known_files(vfs: &Vfs) -> Vec<(FileId, FileExtension)> {
+    let mut files = vfs
+        .iter()
+        .flat_map(|(id, path)| {
+            let (_, ext) = path.name_and_extension()?;
+            FileExtension::try_from(ext?).ok().map(|ext| (id, ext))
+        })
+        .collect_vec();
+    files.sort();
+
+    files
+}
+
+/// Canonicalize a path given on the command line.
+pub(crate) fn canonicalize(path: &Path) -> anyhow::Result<AbsPathBuf> {
+    dunce::canonicalize(path)
+        .map(AbsPathBuf::assert)
+        .with_context(|| format!("failed to find {}", path.display()))
+}
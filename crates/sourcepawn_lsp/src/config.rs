@@ -4,13 +4,16 @@
 //! We currently get this config from `initialize` LSP request, which is not the
 //! best way to do it, but was the simplest thing we could implement.
 
-use ide::{DiagnosticsConfig, HoverConfig, HoverDocFormat};
+use fxhash::FxHashSet;
+use ide::{
+    BraceStyle, DiagnosticsConfig, FormattingConfig, HoverConfig, HoverDocFormat, InlayHintsConfig,
+};
 use itertools::Itertools;
 use lsp_types::{ClientCapabilities, MarkupKind};
 use paths::AbsPathBuf;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Deserialize};
 use std::iter;
-use std::{collections::HashSet, fmt, path::PathBuf};
+use std::{fmt, path::PathBuf};
 
 use crate::{line_index::PositionEncoding, lsp::ext::negotiated_encoding};
 
@@ -46,8 +49,29 @@ config_data! {
         compiler_onSave: bool = "true",
         /// Path to the SourcePawn compiler (spcomp).
         compiler_path: Option<String> = "null",
+        /// List of native lints to disable, e.g. `unused-variable`. The errors and warnings that mirror the compiler's cannot be disabled, as they share the `E0000` and `W0000` codes.
+        diagnostics_disabled: FxHashSet<String> = "[]",
+        /// Whether to compute the diagnostics of every `.sp` file of the workspace that is not included by another file, even if it is not opened.
+        /// This requires the client to pull diagnostics.
+        diagnostics_workspace_enable: bool = "false",
+        /// Whether to align the values of enum variants and the names of methodmap members on consecutive lines.
+        formatting_alignBodies_enable: bool = "false",
+        /// Placement of the opening brace of bodies.
+        formatting_braceStyle: BraceStyleDef = "\"nextLine\"",
+        /// Number of columns of one level of indentation. The default `null` means to use the editor's settings.
+        formatting_indentWidth: Option<usize> = "null",
+        /// Whether to surround binary and assignment operators with spaces.
+        formatting_operatorSpacing_enable: bool = "true",
         /// Include directories paths for the compiler and the linter.
         includeDirectories: Vec<PathBuf> = "[]",
+        /// Whether to show the values of `#define` constants where they are used.
+        inlayHints_defineValueHints_enable: bool = "true",
+        /// Whether to show the values of enum variants declared without a value.
+        inlayHints_enumValueHints_enable: bool = "true",
+        /// Whether to show the names of the parameters at call sites.
+        inlayHints_parameterHints_enable: bool = "true",
+        /// Whether to show the inferred types of variables declared without a type.
+        inlayHints_typeHints_enable: bool = "true",
         /// Disable the language server's syntax linter. This is independant from spcomp.
         linter_disable: bool = "false",
         /// How many worker threads in the main loop. The default `null` means to pick automatically.
@@ -159,7 +183,36 @@ impl Config {
         DiagnosticsConfig {
             enabled: true,
             disable_experimental: false,
-            disabled: HashSet::default(),
+            disabled: self.data.diagnostics_disabled.clone(),
+        }
+    }
+
+    pub fn workspace_diagnostics(&self) -> bool {
+        self.data.diagnostics_workspace_enable
+    }
+
+    pub fn inlay_hints(&self) -> InlayHintsConfig {
+        InlayHintsConfig {
+            parameter_hints: self.data.inlayHints_parameterHints_enable,
+            type_hints: self.data.inlayHints_typeHints_enable,
+            enum_value_hints: self.data.inlayHints_enumValueHints_enable,
+            define_value_hints: self.data.inlayHints_defineValueHints_enable,
+        }
+    }
+
+    pub fn formatting(&self, options: &lsp_types::FormattingOptions) -> FormattingConfig {
+        FormattingConfig {
+            brace_style: match self.data.formatting_braceStyle {
+                BraceStyleDef::SameLine => BraceStyle::SameLine,
+                BraceStyleDef::NextLine => BraceStyle::NextLine,
+            },
+            indent_width: self
+                .data
+                .formatting_indentWidth
+                .map_or(options.tab_size, |width| width as u32),
+            insert_spaces: options.insert_spaces,
+            operator_spacing: self.data.formatting_operatorSpacing_enable,
+            align_bodies: self.data.formatting_alignBodies_enable,
         }
     }
 
@@ -200,6 +253,17 @@ impl Config {
         )
     }
 
+    pub fn workspace_diagnostics_refresh(&self) -> bool {
+        try_or_def!(
+            self.caps
+                .workspace
+                .as_ref()?
+                .diagnostic
+                .as_ref()?
+                .refresh_support?
+        )
+    }
+
     #[allow(unused)]
     pub fn semantics_tokens_augments_syntax_tokens(&self) -> bool {
         try_!(
@@ -213,6 +277,21 @@ impl Config {
         .unwrap_or(false)
     }
 
+    pub fn signature_help_label_offsets(&self) -> bool {
+        try_or_def!(
+            self.caps
+                .text_document
+                .as_ref()?
+                .signature_help
+                .as_ref()?
+                .signature_information
+                .as_ref()?
+                .parameter_information
+                .as_ref()?
+                .label_offset_support?
+        )
+    }
+
     fn experimental(&self, index: &'static str) -> bool {
         try_or_def!(self.caps.experimental.as_ref()?.get(index)?.as_bool()?)
     }
@@ -265,6 +344,13 @@ impl Config {
 
 type ParallelCachePrimingNumThreads = u8;
 
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+enum BraceStyleDef {
+    SameLine,
+    NextLine,
+}
+
 macro_rules! _config_data {
     (struct $name:ident {
         $(
@@ -436,6 +522,14 @@ fn field_props(field: &str, ty: &str, doc: &[&str], default: &str) -> serde_json
             "type": ["null", "array"],
             "items": { "type": "string" },
         },
+        "BraceStyleDef" => set! {
+            "type": "string",
+            "enum": ["sameLine", "nextLine"],
+            "enumDescriptions": [
+                "Put the opening brace at the end of the line of the declaration or statement.",
+                "Put the opening brace on its own line."
+            ],
+        },
         "ParallelCachePrimingNumThreads" => set! {
             "type": "number",
             "minimum": 0,
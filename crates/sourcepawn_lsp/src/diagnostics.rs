@@ -2,6 +2,7 @@
 use std::mem;
 
 use fxhash::{FxHashMap, FxHashSet};
+use ide::Cancellable;
 use nohash_hasher::{IntMap, IntSet};
 use vfs::FileId;
 
@@ -148,31 +149,46 @@ pub(crate) fn fetch_native_diagnostics(
     subscriptions
         .into_iter()
         .filter_map(|file_id| {
-            let diagnostics = snapshot
-                .analysis
-                .diagnostics(&snapshot.config.diagnostics(), file_id)
-                .ok()?
-                .into_iter()
-                .map(move |d| lsp_types::Diagnostic {
-                    range: d.range,
-                    severity: Some(lsp::to_proto::diagnostic_severity(d.severity)),
-                    code: Some(lsp_types::NumberOrString::String(
-                        d.code.as_str().to_string(),
-                    )),
-                    // code_description: Some(lsp_types::CodeDescription {
-                    //     href: lsp_types::Url::parse(&d.code.url()).unwrap(),
-                    // }),
-                    code_description: None,
-                    source: Some("sourcepawn-vscode".to_string()),
-                    message: d.message,
-                    related_information: None,
-                    tags: d
-                        .unused
-                        .then(|| vec![lsp_types::DiagnosticTag::UNNECESSARY]),
-                    data: None,
-                })
-                .collect::<Vec<_>>();
+            let diagnostics = native_diagnostics(&snapshot, file_id).ok()?;
             Some((file_id, diagnostics))
         })
         .collect()
 }
+
+pub(crate) fn native_diagnostics(
+    snapshot: &GlobalStateSnapshot,
+    file_id: FileId,
+) -> Cancellable<Vec<lsp_types::Diagnostic>> {
+    let diagnostics = snapshot
+        .analysis
+        .diagnostics(&snapshot.config.diagnostics(), file_id)?
+        .into_iter()
+        .map(move |d| {
+            let mut tags = Vec::new();
+            if d.unused {
+                tags.push(lsp_types::DiagnosticTag::UNNECESSARY);
+            }
+            if d.deprecated {
+                tags.push(lsp_types::DiagnosticTag::DEPRECATED);
+            }
+            lsp_types::Diagnostic {
+                range: d.range,
+                severity: Some(lsp::to_proto::diagnostic_severity(d.severity)),
+                code: Some(lsp_types::NumberOrString::String(
+                    d.code.as_str().to_string(),
+                )),
+                // code_description: Some(lsp_types::CodeDescription {
+                //     href: lsp_types::Url::parse(&d.code.url()).unwrap(),
+                // }),
+                code_description: None,
+                source: Some("sourcepawn-vscode".to_string()),
+                message: d.message,
+                related_information: None,
+                tags: (!tags.is_empty()).then_some(tags),
+                data: None,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(diagnostics)
+}
@@ -11,6 +11,7 @@ use lsp_types::{
     TextDocumentItem, TextDocumentPositionParams, Url, WorkspaceFolder,
 };
 use std::{
+    collections::BTreeMap,
     env,
     fs::File,
     io,
@@ -22,7 +23,10 @@ use std::{
 use tempfile::{tempdir, TempDir};
 use zip::ZipArchive;
 
-use crate::config::ConfigData;
+use crate::{
+    cli::{self, OutputFormat},
+    config::ConfigData,
+};
 
 use super::{GlobalState, LspClient};
 
@@ -165,6 +169,16 @@ impl Drop for TestBed {
 
 impl TestBed {
     pub fn new(fixture: &str, add_sourcemod: bool) -> Result<Self> {
+        Self::new_with_config(fixture, add_sourcemod, serde_json::json!({}))
+    }
+
+    /// Create a test bed whose configuration is overridden by the settings of `config`, as sent
+    /// by a client, e.g. `{ "diagnostics": { "disabled": ["unused-variable"] } }`.
+    pub fn new_with_config(
+        fixture: &str,
+        add_sourcemod: bool,
+        config: serde_json::Value,
+    ) -> Result<Self> {
         LOGGER.call_once(|| {
             if option_env!("TEST_LOG") == Some("1") {
                 fern::Dispatch::new()
@@ -205,6 +219,7 @@ impl TestBed {
             std::thread::spawn(move || GlobalState::new(server_conn, false).run().unwrap());
         let client_thread = {
             let client = client.clone();
+            let overrides = config;
             std::thread::spawn(move || {
                 let destination = temp_sm_dir_path_;
                 for message in &client_conn.receiver {
@@ -227,6 +242,12 @@ impl TestBed {
                                         .includeDirectories
                                         .push(destination.clone().join("include/"));
                                 }
+                                let mut config = serde_json::to_value(config).unwrap();
+                                if let (Some(config), Some(overrides)) =
+                                    (config.as_object_mut(), overrides.as_object())
+                                {
+                                    config.extend(overrides.clone());
+                                }
                                 client
                                     .send_response(Response::new_ok(request.id, vec![config]))
                                     .unwrap();
@@ -311,7 +332,6 @@ impl TestBed {
         &self.temp_dir_path
     }
 
-    #[allow(unused)]
     pub fn documents(&self) -> &[Document] {
         &self.fixture.documents
     }
@@ -328,35 +348,67 @@ impl TestBed {
     }
 }
 
+/// Initialize the server with the client capabilities, and send it a request.
+///
+/// The capabilities to get the configuration and the workspace folders are always added.
+fn request<R: lsp_types::request::Request>(
+    test_bed: &TestBed,
+    mut capabilities: serde_json::Value,
+    params: R::Params,
+) -> Result<R::Result> {
+    capabilities["workspace"] = serde_json::json!({
+        "configuration": true,
+        "workspace_folders": true
+    });
+    test_bed.initialize(serde_json::from_value(capabilities)?)?;
+
+    test_bed.client().send_request::<R>(params)
+}
+
+/// Get the identifier of the first document of the fixture.
+fn first_document(test_bed: &TestBed) -> TextDocumentIdentifier {
+    let path = &test_bed.documents().first().unwrap().path;
+    TextDocumentIdentifier::new(Url::from_file_path(test_bed.directory().join(path)).unwrap())
+}
+
 pub fn goto_definition(fixture: &str) -> Vec<LocationLink> {
+    goto::<lsp_types::request::GotoDefinition>(fixture)
+}
+
+pub fn goto_declaration(fixture: &str) -> Vec<LocationLink> {
+    goto::<lsp_types::request::GotoDeclaration>(fixture)
+}
+
+pub fn goto_implementation(fixture: &str) -> Vec<LocationLink> {
+    goto::<lsp_types::request::GotoImplementation>(fixture)
+}
+
+pub fn goto_type_definition(fixture: &str) -> Vec<LocationLink> {
+    goto::<lsp_types::request::GotoTypeDefinition>(fixture)
+}
+
+/// Send a request that navigates from the cursor, and return the links of the response.
+fn goto<R>(fixture: &str) -> Vec<LocationLink>
+where
+    R: lsp_types::request::Request<
+        Params = lsp_types::GotoDefinitionParams,
+        Result = Option<lsp_types::GotoDefinitionResponse>,
+    >,
+{
     let test_bed = TestBed::new(fixture, true).unwrap();
-    test_bed
-        .initialize(
-            serde_json::from_value(serde_json::json!({
-                "textDocument": {
-                    "definition": {
-                        "linkSupport": true
-                    }
-                },
-                "workspace": {
-                    "configuration": true,
-                    "workspace_folders": true
-                }
-            }))
-            .unwrap(),
-        )
-        .unwrap();
-    let text_document_position = test_bed.cursor().unwrap();
-    let params = lsp_types::request::GotoTypeDefinitionParams {
-        text_document_position_params: text_document_position,
+    let params = lsp_types::GotoDefinitionParams {
+        text_document_position_params: test_bed.cursor().unwrap(),
         work_done_progress_params: Default::default(),
         partial_result_params: Default::default(),
     };
-    let mut locations = match test_bed
-        .client()
-        .send_request::<lsp_types::request::GotoDefinition>(params)
-        .unwrap()
-    {
+    let capabilities = serde_json::json!({
+        "textDocument": {
+            "definition": {
+                "linkSupport": true
+            }
+        }
+    });
+    let mut locations = match request::<R>(&test_bed, capabilities, params).unwrap() {
         Some(lsp_types::GotoDefinitionResponse::Link(locations)) => locations,
         _ => unreachable!("Expected a link response."),
     };
@@ -368,40 +420,407 @@ pub fn goto_definition(fixture: &str) -> Vec<LocationLink> {
     locations
 }
 
-pub fn complete(fixture: &str, trigger_character: Option<String>) -> Vec<CompletionItem> {
+pub fn references(fixture: &str) -> Vec<Location> {
     let test_bed = TestBed::new(fixture, true).unwrap();
-    test_bed
-        .initialize(
-            serde_json::from_value(serde_json::json!({
-                "textDocument": {
-                    "completion": {
-                        "completionItem": {
-                            "documentationFormat": ["plaintext", "markdown"]
-                        }
+    let params = lsp_types::ReferenceParams {
+        text_document_position: test_bed.cursor().unwrap(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+        context: lsp_types::ReferenceContext {
+            include_declaration: true,
+        },
+    };
+    let mut locations =
+        request::<lsp_types::request::References>(&test_bed, serde_json::json!({}), params)
+            .unwrap()
+            .unwrap_or_default();
+
+    locations.iter_mut().for_each(|location| {
+        test_bed.anonymize_uri(&mut location.uri);
+    });
+    locations.sort_by(|a, b| {
+        (a.uri.as_str(), a.range.start, a.range.end).cmp(&(
+            b.uri.as_str(),
+            b.range.start,
+            b.range.end,
+        ))
+    });
+
+    locations
+}
+
+pub fn signature_help(fixture: &str) -> Option<lsp_types::SignatureHelp> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let params = lsp_types::SignatureHelpParams {
+        context: None,
+        text_document_position_params: test_bed.cursor().unwrap(),
+        work_done_progress_params: Default::default(),
+    };
+    let capabilities = serde_json::json!({
+        "textDocument": {
+            "signatureHelp": {
+                "signatureInformation": {
+                    "parameterInformation": {
+                        "labelOffsetSupport": true
                     }
-                },
-                "workspace": {
-                    "configuration": true,
-                    "workspace_folders": true
                 }
-            }))
-            .unwrap(),
-        )
-        .unwrap();
-    let text_document_position = test_bed.cursor().unwrap();
-    let mut items = match test_bed
-        .client()
-        .send_request::<Completion>(CompletionParams {
-            text_document_position,
-            partial_result_params: Default::default(),
-            work_done_progress_params: Default::default(),
-            context: Some(CompletionContext {
-                trigger_kind: CompletionTriggerKind::TRIGGER_CHARACTER,
-                trigger_character,
-            }),
+            }
+        }
+    });
+
+    request::<lsp_types::request::SignatureHelpRequest>(&test_bed, capabilities, params).unwrap()
+}
+
+pub fn document_symbols(fixture: &str) -> Vec<lsp_types::DocumentSymbol> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let params = lsp_types::DocumentSymbolParams {
+        text_document: first_document(&test_bed),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let capabilities = serde_json::json!({
+        "textDocument": {
+            "documentSymbol": {
+                "hierarchicalDocumentSymbolSupport": true
+            }
+        }
+    });
+
+    match request::<lsp_types::request::DocumentSymbolRequest>(&test_bed, capabilities, params)
+        .unwrap()
+    {
+        Some(lsp_types::DocumentSymbolResponse::Nested(symbols)) => symbols,
+        _ => Vec::new(),
+    }
+}
+
+pub fn workspace_symbols(fixture: &str, query: &str) -> Vec<lsp_types::SymbolInformation> {
+    let test_bed = TestBed::new(fixture, false).unwrap();
+    let params = lsp_types::WorkspaceSymbolParams {
+        query: query.to_string(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    match request::<lsp_types::request::WorkspaceSymbolRequest>(
+        &test_bed,
+        serde_json::json!({}),
+        params,
+    )
+    .unwrap()
+    {
+        Some(lsp_types::WorkspaceSymbolResponse::Flat(mut symbols)) => {
+            for symbol in symbols.iter_mut() {
+                test_bed.anonymize_uri(&mut symbol.location.uri);
+            }
+            symbols
+        }
+        _ => Vec::new(),
+    }
+}
+
+pub fn code_actions(fixture: &str) -> Vec<lsp_types::CodeAction> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let TextDocumentPositionParams {
+        text_document,
+        position,
+    } = test_bed.cursor().unwrap();
+    let params = lsp_types::CodeActionParams {
+        text_document,
+        range: Range::new(position, position),
+        context: Default::default(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    request::<lsp_types::request::CodeActionRequest>(&test_bed, serde_json::json!({}), params)
+        .unwrap()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|action| match action {
+            lsp_types::CodeActionOrCommand::CodeAction(mut action) => {
+                if let Some(changes) = action.edit.as_mut().and_then(|edit| edit.changes.as_mut()) {
+                    *changes = changes
+                        .drain()
+                        .map(|(mut uri, edits)| {
+                            test_bed.anonymize_uri(&mut uri);
+                            (uri, edits)
+                        })
+                        .collect();
+                }
+                Some(action)
+            }
+            lsp_types::CodeActionOrCommand::Command(_) => None,
         })
+        .collect()
+}
+
+pub fn document_diagnostics(fixture: &str) -> Vec<lsp_types::Diagnostic> {
+    document_diagnostics_with_config(fixture, serde_json::json!({}))
+}
+
+/// Same as [`document_diagnostics`], with the configuration overridden by `config`.
+pub fn document_diagnostics_with_config(
+    fixture: &str,
+    config: serde_json::Value,
+) -> Vec<lsp_types::Diagnostic> {
+    let test_bed = TestBed::new_with_config(fixture, true, config).unwrap();
+    let params = lsp_types::DocumentDiagnosticParams {
+        text_document: first_document(&test_bed),
+        identifier: None,
+        previous_result_id: None,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let capabilities = serde_json::json!({
+        "textDocument": {
+            "diagnostic": {}
+        }
+    });
+
+    match request::<lsp_types::request::DocumentDiagnosticRequest>(
+        &test_bed,
+        capabilities,
+        params,
+    )
+    .unwrap()
+    {
+        lsp_types::DocumentDiagnosticReportResult::Report(
+            lsp_types::DocumentDiagnosticReport::Full(report),
+        ) => report.full_document_diagnostic_report.items,
+        _ => Vec::new(),
+    }
+}
+
+pub fn document_highlight(fixture: &str) -> Vec<lsp_types::DocumentHighlight> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let params = lsp_types::DocumentHighlightParams {
+        text_document_position_params: test_bed.cursor().unwrap(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    request::<lsp_types::request::DocumentHighlightRequest>(
+        &test_bed,
+        serde_json::json!({}),
+        params,
+    )
+    .unwrap()
+    .unwrap_or_default()
+}
+
+pub fn selection_ranges(fixture: &str) -> Vec<lsp_types::SelectionRange> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let cursor = test_bed.cursor().unwrap();
+    let params = lsp_types::SelectionRangeParams {
+        text_document: cursor.text_document,
+        positions: vec![cursor.position],
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    request::<lsp_types::request::SelectionRangeRequest>(&test_bed, serde_json::json!({}), params)
+        .unwrap()
+        .unwrap_or_default()
+}
+
+pub fn folding_ranges(fixture: &str) -> Vec<lsp_types::FoldingRange> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let params = lsp_types::FoldingRangeParams {
+        text_document: first_document(&test_bed),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    request::<lsp_types::request::FoldingRangeRequest>(&test_bed, serde_json::json!({}), params)
+        .unwrap()
+        .unwrap_or_default()
+}
+
+pub fn inlay_hints(fixture: &str) -> Vec<lsp_types::InlayHint> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let params = lsp_types::InlayHintParams {
+        text_document: first_document(&test_bed),
+        range: Range::new(Position::new(0, 0), Position::new(u32::MAX, 0)),
+        work_done_progress_params: Default::default(),
+    };
+
+    request::<lsp_types::request::InlayHintRequest>(&test_bed, serde_json::json!({}), params)
         .unwrap()
+        .unwrap_or_default()
+}
+
+pub fn semantic_tokens(fixture: &str) -> lsp_types::SemanticTokens {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let params = lsp_types::SemanticTokensParams {
+        text_document: first_document(&test_bed),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+
+    match request::<lsp_types::request::SemanticTokensFullRequest>(
+        &test_bed,
+        serde_json::json!({}),
+        params,
+    )
+    .unwrap()
     {
+        // The result id is a global counter, which depends on the order of the tests.
+        Some(lsp_types::SemanticTokensResult::Tokens(tokens)) => lsp_types::SemanticTokens {
+            result_id: None,
+            data: tokens.data,
+        },
+        _ => Default::default(),
+    }
+}
+
+/// Format the first document of the fixture, and return its formatted text.
+pub fn formatting(fixture: &str) -> String {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let params = lsp_types::DocumentFormattingParams {
+        text_document: first_document(&test_bed),
+        options: lsp_types::FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
+        },
+        work_done_progress_params: Default::default(),
+    };
+    let edits = request::<lsp_types::request::Formatting>(&test_bed, serde_json::json!({}), params)
+        .unwrap()
+        .unwrap_or_default();
+
+    let document = test_bed.documents().first().unwrap();
+    let line_starts = std::iter::once(0)
+        .chain(document.text.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect_vec();
+    // The client does not negotiate the position encoding, so the columns are UTF-16 offsets.
+    let offset = |pos: Position| {
+        let line_start = line_starts[pos.line as usize];
+        let mut utf16_len = 0;
+        let len = document.text[line_start..]
+            .chars()
+            .take_while(|c| {
+                let take = utf16_len < pos.character as usize;
+                utf16_len += c.len_utf16();
+                take
+            })
+            .map(char::len_utf8)
+            .sum::<usize>();
+        line_start + len
+    };
+    let mut text = document.text.clone();
+    for edit in edits.into_iter().sorted_by_key(|edit| edit.range.start).rev() {
+        text.replace_range(offset(edit.range.start)..offset(edit.range.end), &edit.new_text);
+    }
+
+    text
+}
+
+pub fn call_hierarchy_incoming(fixture: &str) -> Vec<lsp_types::CallHierarchyIncomingCall> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let Some(item) = prepare_call_hierarchy(&test_bed) else {
+        return Vec::new();
+    };
+    let params = lsp_types::CallHierarchyIncomingCallsParams {
+        item,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let mut calls = test_bed
+        .client()
+        .send_request::<lsp_types::request::CallHierarchyIncomingCalls>(params)
+        .unwrap()
+        .unwrap_or_default();
+
+    calls
+        .iter_mut()
+        .for_each(|call| test_bed.anonymize_uri(&mut call.from.uri));
+    calls
+}
+
+pub fn call_hierarchy_outgoing(fixture: &str) -> Vec<lsp_types::CallHierarchyOutgoingCall> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let Some(item) = prepare_call_hierarchy(&test_bed) else {
+        return Vec::new();
+    };
+    let params = lsp_types::CallHierarchyOutgoingCallsParams {
+        item,
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let mut calls = test_bed
+        .client()
+        .send_request::<lsp_types::request::CallHierarchyOutgoingCalls>(params)
+        .unwrap()
+        .unwrap_or_default();
+
+    calls
+        .iter_mut()
+        .for_each(|call| test_bed.anonymize_uri(&mut call.to.uri));
+    calls
+}
+
+/// Prepare the call hierarchy at the cursor, and return the first item.
+fn prepare_call_hierarchy(test_bed: &TestBed) -> Option<lsp_types::CallHierarchyItem> {
+    let params = lsp_types::CallHierarchyPrepareParams {
+        text_document_position_params: test_bed.cursor().unwrap(),
+        work_done_progress_params: Default::default(),
+    };
+
+    request::<lsp_types::request::CallHierarchyPrepare>(test_bed, serde_json::json!({}), params)
+        .unwrap()?
+        .into_iter()
+        .next()
+}
+
+pub fn rename(
+    fixture: &str,
+    new_name: &str,
+) -> Result<BTreeMap<Url, Vec<lsp_types::TextEdit>>, String> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let params = lsp_types::RenameParams {
+        text_document_position: test_bed.cursor().unwrap(),
+        new_name: new_name.to_string(),
+        work_done_progress_params: Default::default(),
+    };
+    let edit = request::<lsp_types::request::Rename>(&test_bed, serde_json::json!({}), params)
+        .map_err(|err| err.to_string())?
+        .unwrap_or_default();
+
+    Ok(edit
+        .changes
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(mut uri, mut edits)| {
+            test_bed.anonymize_uri(&mut uri);
+            edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+            (uri, edits)
+        })
+        .collect())
+}
+
+pub fn complete(fixture: &str, trigger_character: Option<String>) -> Vec<CompletionItem> {
+    let test_bed = TestBed::new(fixture, true).unwrap();
+    let params = CompletionParams {
+        text_document_position: test_bed.cursor().unwrap(),
+        partial_result_params: Default::default(),
+        work_done_progress_params: Default::default(),
+        context: Some(CompletionContext {
+            trigger_kind: CompletionTriggerKind::TRIGGER_CHARACTER,
+            trigger_character,
+        }),
+    };
+    let capabilities = serde_json::json!({
+        "textDocument": {
+            "completion": {
+                "completionItem": {
+                    "documentationFormat": ["plaintext", "markdown"]
+                }
+            }
+        }
+    });
+    let mut items = match request::<Completion>(&test_bed, capabilities, params).unwrap() {
         Some(CompletionResponse::Array(items)) => items,
         Some(CompletionResponse::List(list)) => list.items,
         None => Vec::new(),
@@ -474,34 +893,84 @@ pub fn unzip_file(zip_file_path: &Path, destination: &Path) -> Result<(), io::Er
 
 pub fn hover(fixture: &str) -> Hover {
     let test_bed = TestBed::new(fixture, true).unwrap();
-    test_bed
-        .initialize(
-            serde_json::from_value(serde_json::json!({
-                "textDocument": {
-                    "hover": {
-                        "contentFormat": [
-                            "plaintext",
-                            "markdown"
-                        ]
-                    }
-                },
-                "workspace": {
-                    "configuration": true,
-                    "workspace_folders": true
-                }
-            }))
-            .unwrap(),
-        )
-        .unwrap();
-    let text_document_position = test_bed.cursor().unwrap();
     let params = lsp_types::HoverParams {
-        text_document_position_params: text_document_position,
+        text_document_position_params: test_bed.cursor().unwrap(),
         work_done_progress_params: Default::default(),
     };
+    let capabilities = serde_json::json!({
+        "textDocument": {
+            "hover": {
+                "contentFormat": [
+                    "plaintext",
+                    "markdown"
+                ]
+            }
+        }
+    });
 
-    test_bed
-        .client()
-        .send_request::<lsp_types::request::HoverRequest>(params)
+    request::<lsp_types::request::HoverRequest>(&test_bed, capabilities, params)
         .unwrap()
         .expect("Expected a hover response.")
 }
+
+/// Write the files of the fixture in a temporary directory, for the subcommands which load the
+/// workspace themselves instead of running a server.
+fn write_fixture(fixture: &str) -> (TempDir, PathBuf) {
+    let temp_dir = tempdir().unwrap();
+    let path = dunce::canonicalize(temp_dir.path()).unwrap();
+    Fixture::parse(fixture).write_files(&path);
+
+    (temp_dir, path)
+}
+
+/// Replace the path of the temporary directory by `/workspace`, so that the tests are not
+/// dependent on the tempdir.
+fn anonymize_paths(output: &str, dir: &Path) -> String {
+    let url = Url::from_directory_path(dir).unwrap();
+    output
+        .replace(url.as_str(), "file:///workspace/")
+        .replace(&*dir.to_string_lossy(), "/workspace")
+}
+
+/// Check the plugins of the fixture and render their diagnostics.
+///
+/// Returns whether any of the diagnostics is an error, and the rendered diagnostics.
+pub fn check(fixture: &str, format: OutputFormat) -> (bool, String) {
+    let (_temp_dir, dir) = write_fixture(fixture);
+    let result = cli::check(&dir, &[]).unwrap();
+    let output = result.render(format, &dir).unwrap();
+
+    (result.errored, anonymize_paths(&output, &dir))
+}
+
+/// Generate the documentation of the include files of the fixture.
+///
+/// Returns the contents of the generated pages, by path relative to the output directory.
+pub fn docgen(fixture: &str) -> BTreeMap<String, String> {
+    let (_temp_dir, dir) = write_fixture(fixture);
+    cli::docgen(&dir, &[])
+        .unwrap()
+        .pages
+        .into_iter()
+        .map(|(path, contents)| (path.to_string_lossy().replace('\\', "/"), contents))
+        .collect()
+}
+
+/// Run the phases of the analysis on the fixture.
+///
+/// Returns a line per phase with what it processed, and a line per unresolved identifier, as the
+/// time and memory spent in each phase are not deterministic.
+pub fn analysis_stats(fixture: &str) -> String {
+    let (_temp_dir, dir) = write_fixture(fixture);
+    let stats = cli::analysis_stats(&dir, &[], 1).unwrap();
+    let phases = stats.phases.iter().map(|phase| match &phase.detail {
+        Some(detail) => format!("{} ({detail})", phase.name),
+        None => phase.name.to_string(),
+    });
+    let unresolved = stats
+        .unresolved
+        .iter()
+        .map(|(name, def_name)| format!("unresolved identifier `{name}` in `{def_name}`"));
+
+    phases.chain(unresolved).join("\n")
+}
@@ -2,7 +2,7 @@ use base_db::{Change, FileExtension, SourceRootConfig};
 use crossbeam::channel::{unbounded, Receiver, Sender};
 use flycheck::FlycheckHandle;
 use fxhash::FxHashMap;
-use ide::{Analysis, AnalysisHost};
+use ide::{Analysis, AnalysisHost, Cancellable};
 
 use itertools::Itertools;
 use lsp_server::{Connection, ErrorCode, RequestId};
@@ -18,7 +18,7 @@ use crate::{
     client::LspClient,
     config::{Config, ConfigError},
     diagnostics::DiagnosticCollection,
-    line_index::LineEndings,
+    line_index::{LineEndings, LineIndex},
     lsp::{from_proto, to_proto::url_from_abs_path},
     lsp_ext,
     main_loop::Task,
@@ -390,6 +390,16 @@ impl GlobalStateSnapshot {
         file_id_to_url(&self.vfs_read(), id)
     }
 
+    pub(crate) fn file_line_index(&self, file_id: FileId) -> Cancellable<LineIndex> {
+        let text = self.analysis.file_text(file_id)?;
+        Ok(LineIndex {
+            index: Arc::new(ide::LineIndex::new(&text)),
+            // The VFS normalizes the end of lines to `\n`.
+            endings: LineEndings::Unix,
+            encoding: self.config.position_encoding(),
+        })
+    }
+
     #[allow(unused)]
     pub(crate) fn url_file_version(&self, uri: &Url) -> Option<i32> {
         let path = from_proto::vfs_path(uri).ok()?;
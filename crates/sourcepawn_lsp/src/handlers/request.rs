@@ -1,17 +1,21 @@
 use anyhow::Context;
-use base_db::FileRange;
+use base_db::{FilePosition, FileRange};
+use fxhash::FxHashMap;
 use lsp_types::{
     SemanticTokensDeltaParams, SemanticTokensFullDeltaResult, SemanticTokensParams,
     SemanticTokensRangeParams, SemanticTokensRangeResult, SemanticTokensResult, Url,
 };
 use stdx::format_to;
+use vfs::FileId;
 
 use crate::{
+    capabilities::ClientCapabilitiesExt,
+    diagnostics::native_diagnostics,
     global_state::GlobalStateSnapshot,
     lsp::{from_proto, to_proto},
     lsp_ext::{
-        AnalyzerStatusParams, ItemTreeParams, PreprocessedDocumentParams, ProjectMainPathParams,
-        ProjectsGraphvizParams, SyntaxTreeParams,
+        AnalyzerStatusParams, CompletionResolveData, ItemTreeParams, PreprocessedDocumentParams,
+        ProjectMainPathParams, ProjectsGraphvizParams, SyntaxTreeParams,
     },
 };
 
@@ -38,6 +42,72 @@ pub(crate) fn handle_goto_definition(
     )?))
 }
 
+pub(crate) fn handle_goto_declaration(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::request::GotoDeclarationParams,
+) -> anyhow::Result<Option<lsp_types::request::GotoDeclarationResponse>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position_params)?;
+
+    let targets = match snap.analysis.goto_declaration(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let src = FileRange {
+        file_id: pos.file_id,
+        range: targets.range,
+    };
+
+    Ok(Some(to_proto::goto_definition_response(
+        &snap,
+        Some(src),
+        targets.info,
+    )?))
+}
+
+pub(crate) fn handle_goto_implementation(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::request::GotoImplementationParams,
+) -> anyhow::Result<Option<lsp_types::request::GotoImplementationResponse>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position_params)?;
+
+    let targets = match snap.analysis.goto_implementation(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let src = FileRange {
+        file_id: pos.file_id,
+        range: targets.range,
+    };
+
+    Ok(Some(to_proto::goto_definition_response(
+        &snap,
+        Some(src),
+        targets.info,
+    )?))
+}
+
+pub(crate) fn handle_goto_type_definition(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::request::GotoTypeDefinitionParams,
+) -> anyhow::Result<Option<lsp_types::request::GotoTypeDefinitionResponse>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position_params)?;
+
+    let targets = match snap.analysis.goto_type_definition(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let src = FileRange {
+        file_id: pos.file_id,
+        range: targets.range,
+    };
+
+    Ok(Some(to_proto::goto_definition_response(
+        &snap,
+        Some(src),
+        targets.info,
+    )?))
+}
+
 pub(crate) fn handle_hover(
     snap: GlobalStateSnapshot,
     params: lsp_types::HoverParams,
@@ -60,6 +130,483 @@ pub(crate) fn handle_hover(
     Ok(res.into())
 }
 
+pub(crate) fn handle_references(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::ReferenceParams,
+) -> anyhow::Result<Option<Vec<lsp_types::Location>>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position)?;
+
+    let refs = match snap.analysis.find_all_refs(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let locations = refs
+        .into_file_ranges(params.context.include_declaration)
+        .into_iter()
+        .map(|frange| to_proto::location(&snap, frange))
+        .collect();
+
+    Ok(Some(locations))
+}
+
+pub(crate) fn handle_document_highlight(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentHighlightParams,
+) -> anyhow::Result<Option<Vec<lsp_types::DocumentHighlight>>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position_params)?;
+
+    let refs = match snap.analysis.highlight_related(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let res = refs
+        .into_iter()
+        .map(|highlight| lsp_types::DocumentHighlight {
+            range: highlight.range,
+            kind: highlight.category.map(to_proto::document_highlight_kind),
+        })
+        .collect();
+
+    Ok(Some(res))
+}
+
+pub(crate) fn handle_document_symbol(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentSymbolParams,
+) -> anyhow::Result<Option<lsp_types::DocumentSymbolResponse>> {
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+
+    let symbols = snap.analysis.document_symbols(file_id)?;
+    if snap.config.caps().has_hierarchical_document_symbol_support() {
+        return Ok(Some(lsp_types::DocumentSymbolResponse::Nested(
+            symbols.into_iter().map(to_proto::document_symbol).collect(),
+        )));
+    }
+    let url = to_proto::url(&snap, file_id);
+    let mut acc = Vec::new();
+    for symbol in symbols {
+        to_proto::flat_document_symbol(&url, symbol, None, &mut acc);
+    }
+
+    Ok(Some(lsp_types::DocumentSymbolResponse::Flat(acc)))
+}
+
+pub(crate) fn handle_folding_range(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::FoldingRangeParams,
+) -> anyhow::Result<Option<Vec<lsp_types::FoldingRange>>> {
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let line_folding_only = snap.config.caps().has_line_folding_only_support();
+
+    Ok(Some(
+        snap.analysis
+            .folding_ranges(file_id)?
+            .into_iter()
+            .map(|fold| to_proto::folding_range(fold, line_folding_only))
+            .collect(),
+    ))
+}
+
+pub(crate) fn handle_selection_range(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::SelectionRangeParams,
+) -> anyhow::Result<Option<Vec<lsp_types::SelectionRange>>> {
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+
+    let ranges = snap
+        .analysis
+        .selection_ranges(file_id, params.positions.clone())?;
+    let res = params
+        .positions
+        .into_iter()
+        .zip(ranges)
+        .map(|(position, ranges)| {
+            ranges
+                .into_iter()
+                .rev()
+                .fold(None, |parent, range| {
+                    Some(lsp_types::SelectionRange {
+                        range,
+                        parent: parent.map(Box::new),
+                    })
+                })
+                .unwrap_or_else(|| lsp_types::SelectionRange {
+                    range: lsp_types::Range::new(position, position),
+                    parent: None,
+                })
+        })
+        .collect();
+
+    Ok(Some(res))
+}
+
+pub(crate) fn handle_workspace_symbol(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::WorkspaceSymbolParams,
+) -> anyhow::Result<Option<lsp_types::WorkspaceSymbolResponse>> {
+    let symbols = snap
+        .analysis
+        .workspace_symbols(&params.query)?
+        .into_iter()
+        .map(|symbol| to_proto::workspace_symbol(&snap, symbol))
+        .collect();
+
+    Ok(Some(lsp_types::WorkspaceSymbolResponse::Flat(symbols)))
+}
+
+pub(crate) fn handle_call_hierarchy_prepare(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::CallHierarchyPrepareParams,
+) -> anyhow::Result<Option<Vec<lsp_types::CallHierarchyItem>>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position_params)?;
+
+    let item = match snap.analysis.call_hierarchy(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+
+    Ok(Some(vec![to_proto::call_hierarchy_item(&snap, item)]))
+}
+
+pub(crate) fn handle_call_hierarchy_incoming(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::CallHierarchyIncomingCallsParams,
+) -> anyhow::Result<Option<Vec<lsp_types::CallHierarchyIncomingCall>>> {
+    let pos = call_hierarchy_item_position(&snap, &params.item)?;
+
+    let calls = match snap.analysis.incoming_calls(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let res = calls
+        .into_iter()
+        .map(|call| lsp_types::CallHierarchyIncomingCall {
+            from: to_proto::call_hierarchy_item(&snap, call.target),
+            from_ranges: call.ranges,
+        })
+        .collect();
+
+    Ok(Some(res))
+}
+
+pub(crate) fn handle_call_hierarchy_outgoing(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::CallHierarchyOutgoingCallsParams,
+) -> anyhow::Result<Option<Vec<lsp_types::CallHierarchyOutgoingCall>>> {
+    let pos = call_hierarchy_item_position(&snap, &params.item)?;
+
+    let calls = match snap.analysis.outgoing_calls(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let res = calls
+        .into_iter()
+        .map(|call| lsp_types::CallHierarchyOutgoingCall {
+            to: to_proto::call_hierarchy_item(&snap, call.target),
+            from_ranges: call.ranges,
+        })
+        .collect();
+
+    Ok(Some(res))
+}
+
+/// The items of the call hierarchy are resolved again from the position of their name.
+fn call_hierarchy_item_position(
+    snap: &GlobalStateSnapshot,
+    item: &lsp_types::CallHierarchyItem,
+) -> anyhow::Result<FilePosition> {
+    Ok(FilePosition {
+        file_id: from_proto::file_id(snap, &item.uri)?,
+        position: item.selection_range.start,
+    })
+}
+
+pub(crate) fn handle_signature_help(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::SignatureHelpParams,
+) -> anyhow::Result<Option<lsp_types::SignatureHelp>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position_params)?;
+
+    let help = match snap.analysis.signature_help(pos)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+
+    Ok(Some(to_proto::signature_help(
+        help,
+        snap.config.signature_help_label_offsets(),
+    )))
+}
+
+pub(crate) fn handle_inlay_hints(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::InlayHintParams,
+) -> anyhow::Result<Option<Vec<lsp_types::InlayHint>>> {
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+
+    Ok(Some(
+        snap.analysis
+            .inlay_hints(&snap.config.inlay_hints(), file_id, Some(params.range))?
+            .into_iter()
+            .map(to_proto::inlay_hint)
+            .collect(),
+    ))
+}
+
+pub(crate) fn handle_formatting(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentFormattingParams,
+) -> anyhow::Result<Option<Vec<lsp_types::TextEdit>>> {
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let edits = snap
+        .analysis
+        .format(file_id, &snap.config.formatting(&params.options), None)?;
+
+    text_edits(&snap, file_id, edits)
+}
+
+pub(crate) fn handle_range_formatting(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentRangeFormattingParams,
+) -> anyhow::Result<Option<Vec<lsp_types::TextEdit>>> {
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let edits = snap.analysis.format(
+        file_id,
+        &snap.config.formatting(&params.options),
+        Some(params.range),
+    )?;
+
+    text_edits(&snap, file_id, edits)
+}
+
+pub(crate) fn handle_on_type_formatting(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentOnTypeFormattingParams,
+) -> anyhow::Result<Option<Vec<lsp_types::TextEdit>>> {
+    let Some(ch) = params.ch.chars().next() else {
+        return Ok(None);
+    };
+    let mut pos = from_proto::file_position(&snap, params.text_document_position)?;
+    let line_index = snap.file_line_index(pos.file_id)?;
+    let line_col = from_proto::line_col(&line_index, pos.position)?;
+    pos.position = lsp_types::Position::new(line_col.line, line_col.col);
+    let edits = snap
+        .analysis
+        .format_on_type(pos, ch, &snap.config.formatting(&params.options))?;
+
+    text_edits(&snap, pos.file_id, edits)
+}
+
+/// Convert the edits of the formatter, whose columns are UTF-8 offsets, to the negotiated
+/// encoding.
+fn text_edits(
+    snap: &GlobalStateSnapshot,
+    file_id: FileId,
+    edits: Option<Vec<lsp_types::TextEdit>>,
+) -> anyhow::Result<Option<Vec<lsp_types::TextEdit>>> {
+    let Some(edits) = edits else {
+        return Ok(None);
+    };
+    let line_index = snap.file_line_index(file_id)?;
+
+    Ok(Some(
+        edits
+            .into_iter()
+            .map(|edit| to_proto::text_edit(&line_index, edit))
+            .collect(),
+    ))
+}
+
+pub(crate) fn handle_prepare_rename(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::TextDocumentPositionParams,
+) -> anyhow::Result<Option<lsp_types::PrepareRenameResponse>> {
+    let pos = from_proto::file_position(&snap, params)?;
+
+    let target = snap.analysis.prepare_rename(pos)?.map_err(to_proto::rename_error)?;
+
+    Ok(Some(lsp_types::PrepareRenameResponse::Range(target.range)))
+}
+
+pub(crate) fn handle_rename(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::RenameParams,
+) -> anyhow::Result<Option<lsp_types::WorkspaceEdit>> {
+    let pos = from_proto::file_position(&snap, params.text_document_position)?;
+
+    let source_change = snap
+        .analysis
+        .rename(pos, &params.new_name)?
+        .map_err(to_proto::rename_error)?;
+
+    Ok(Some(to_proto::workspace_edit(&snap, source_change)))
+}
+
+pub(crate) fn handle_code_action(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::CodeActionParams,
+) -> anyhow::Result<Option<Vec<lsp_types::CodeActionOrCommand>>> {
+    if let Some(only) = &params.context.only {
+        if !only
+            .iter()
+            .any(|kind| kind.as_str().starts_with(lsp_types::CodeActionKind::QUICKFIX.as_str()))
+        {
+            return Ok(None);
+        }
+    }
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let range = params.range;
+
+    let mut res = Vec::new();
+    for diagnostic in snap
+        .analysis
+        .diagnostics(&snap.config.diagnostics(), file_id)?
+        .into_iter()
+        .filter(|it| it.range.start <= range.end && range.start <= it.range.end)
+    {
+        let client_diagnostics = params
+            .context
+            .diagnostics
+            .iter()
+            .filter(|it| it.range == diagnostic.range && it.message == diagnostic.message)
+            .cloned()
+            .collect::<Vec<_>>();
+        for fix in diagnostic.fixes.into_iter().flatten() {
+            res.push(lsp_types::CodeActionOrCommand::CodeAction(
+                to_proto::code_action(&snap, fix, client_diagnostics.clone()),
+            ));
+        }
+    }
+
+    Ok(Some(res))
+}
+
+pub(crate) fn handle_document_diagnostic(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentDiagnosticParams,
+) -> anyhow::Result<lsp_types::DocumentDiagnosticReportResult> {
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let result_id = snap.analysis.revision()?;
+
+    // Nothing changed since the previous request, the client can keep its diagnostics.
+    let report = if params.previous_result_id.as_ref() == Some(&result_id) {
+        lsp_types::DocumentDiagnosticReport::Unchanged(
+            lsp_types::RelatedUnchangedDocumentDiagnosticReport {
+                related_documents: None,
+                unchanged_document_diagnostic_report:
+                    lsp_types::UnchangedDocumentDiagnosticReport { result_id },
+            },
+        )
+    } else {
+        lsp_types::DocumentDiagnosticReport::Full(lsp_types::RelatedFullDocumentDiagnosticReport {
+            related_documents: None,
+            full_document_diagnostic_report: lsp_types::FullDocumentDiagnosticReport {
+                result_id: Some(result_id),
+                items: native_diagnostics(&snap, file_id)?,
+            },
+        })
+    };
+
+    Ok(lsp_types::DocumentDiagnosticReportResult::Report(report))
+}
+
+pub(crate) fn handle_workspace_diagnostic(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::WorkspaceDiagnosticParams,
+) -> anyhow::Result<lsp_types::WorkspaceDiagnosticReportResult> {
+    let mut items = Vec::new();
+    if snap.config.workspace_diagnostics() {
+        let result_id = snap.analysis.revision()?;
+        let previous_result_ids: FxHashMap<Url, String> = params
+            .previous_result_ids
+            .into_iter()
+            .map(|it| (it.uri, it.value))
+            .collect();
+        for file_id in snap.analysis.project_roots()? {
+            let uri = snap.file_id_to_url(file_id);
+            // Opened files are pulled by the client with `textDocument/diagnostic`.
+            if snap.mem_docs.contains(&from_proto::vfs_path(&uri)?) {
+                continue;
+            }
+            let report = if previous_result_ids.get(&uri) == Some(&result_id) {
+                lsp_types::WorkspaceDocumentDiagnosticReport::Unchanged(
+                    lsp_types::WorkspaceUnchangedDocumentDiagnosticReport {
+                        uri,
+                        version: None,
+                        unchanged_document_diagnostic_report:
+                            lsp_types::UnchangedDocumentDiagnosticReport {
+                                result_id: result_id.clone(),
+                            },
+                    },
+                )
+            } else {
+                lsp_types::WorkspaceDocumentDiagnosticReport::Full(
+                    lsp_types::WorkspaceFullDocumentDiagnosticReport {
+                        uri,
+                        version: None,
+                        full_document_diagnostic_report: lsp_types::FullDocumentDiagnosticReport {
+                            result_id: Some(result_id.clone()),
+                            items: native_diagnostics(&snap, file_id)?,
+                        },
+                    },
+                )
+            };
+            items.push(report);
+        }
+    }
+
+    Ok(lsp_types::WorkspaceDiagnosticReportResult::Report(
+        lsp_types::WorkspaceDiagnosticReport { items },
+    ))
+}
+
+pub(crate) fn handle_completion(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::CompletionParams,
+) -> anyhow::Result<Option<lsp_types::CompletionResponse>> {
+    let mut pos = from_proto::file_position(&snap, params.text_document_position)?;
+    let line_index = snap.file_line_index(pos.file_id)?;
+    let line_col = from_proto::line_col(&line_index, pos.position)?;
+    pos.position = lsp_types::Position::new(line_col.line, line_col.col);
+    let trigger_character = params
+        .context
+        .and_then(|ctx| ctx.trigger_character)
+        .and_then(|it| it.chars().next());
+
+    let items = match snap.analysis.completions(pos, trigger_character)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let items: Vec<_> = items
+        .into_iter()
+        .map(|item| to_proto::completion_item(&snap, item))
+        .collect();
+
+    Ok(Some(items.into()))
+}
+
+pub(crate) fn handle_completion_resolve(
+    snap: GlobalStateSnapshot,
+    mut original_completion: lsp_types::CompletionItem,
+) -> anyhow::Result<lsp_types::CompletionItem> {
+    let Some(data) = original_completion.data.clone() else {
+        return Ok(original_completion);
+    };
+    let data: CompletionResolveData = serde_json::from_value(data)?;
+    let frange = from_proto::file_range_uri(&snap, &data.uri, data.range)?;
+
+    if let Some(docs) = snap.analysis.resolve_completion(frange)? {
+        original_completion.documentation = Some(lsp_types::Documentation::MarkupContent(
+            lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: docs.to_markdown(),
+            },
+        ));
+    }
+
+    Ok(original_completion)
+}
+
 pub(crate) fn handle_semantic_tokens_full(
     snap: GlobalStateSnapshot,
     params: SemanticTokensParams,
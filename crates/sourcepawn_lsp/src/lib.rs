@@ -1,4 +1,5 @@
 mod capabilities;
+pub mod cli;
 mod client;
 mod diagnostics;
 mod dispatch;
@@ -15,7 +15,6 @@ pub(crate) struct LspError {
 }
 
 impl LspError {
-    #[allow(unused)]
     pub(crate) fn new(code: i32, message: String) -> LspError {
         LspError { code, message }
     }
@@ -22,10 +22,11 @@ pub(crate) fn vfs_path(url: &lsp_types::Url) -> anyhow::Result<vfs::VfsPath> {
     abs_path(url).map(vfs::VfsPath::from)
 }
 
-pub(crate) fn offset(
+/// Convert a position in the negotiated encoding to a line and a UTF-8 column.
+pub(crate) fn line_col(
     line_index: &LineIndex,
     position: lsp_types::Position,
-) -> anyhow::Result<TextSize> {
+) -> anyhow::Result<LineCol> {
     let line_col = match line_index.encoding {
         PositionEncoding::Utf8 => LineCol {
             line: position.line,
@@ -42,6 +43,14 @@ pub(crate) fn offset(
                 .ok_or_else(|| format_err!("Invalid wide col offset"))?
         }
     };
+    Ok(line_col)
+}
+
+pub(crate) fn offset(
+    line_index: &LineIndex,
+    position: lsp_types::Position,
+) -> anyhow::Result<TextSize> {
+    let line_col = line_col(line_index, position)?;
     let text_size = line_index
         .index
         .offset(line_col)
@@ -4,16 +4,55 @@ use std::{
 };
 
 use base_db::FileRange;
-use ide::{Cancellable, Highlight, HlMod, HlRange, HlTag, Markup, NavigationTarget, Severity};
+use ide::{
+    Assist, AssistKind, CallHierarchyItem, Cancellable, CompletionItem, CompletionKind,
+    DocumentSymbol, Fold, FoldKind, Highlight, HlMod, HlRange, HlTag, InlayHint, InlayKind,
+    LineCol, Markup, NavigationTarget, ReferenceCategory, RenameError, Severity, SignatureHelp,
+    SourceChange, WorkspaceSymbol,
+};
 use ide_db::SymbolKind;
 use itertools::Itertools;
 use paths::AbsPath;
 use vfs::FileId;
 
-use crate::global_state::GlobalStateSnapshot;
+use crate::{
+    global_state::GlobalStateSnapshot,
+    line_index::{LineIndex, PositionEncoding},
+    lsp::LspError,
+    lsp_ext::CompletionResolveData,
+};
 
 use super::semantic_tokens;
 
+/// Convert a position with a UTF-8 column to the negotiated encoding.
+pub(crate) fn position(
+    line_index: &LineIndex,
+    position: lsp_types::Position,
+) -> lsp_types::Position {
+    let line_col = LineCol {
+        line: position.line,
+        col: position.character,
+    };
+    match line_index.encoding {
+        PositionEncoding::Utf8 => position,
+        PositionEncoding::Wide(enc) => match line_index.index.to_wide(enc, line_col) {
+            Some(line_col) => lsp_types::Position::new(line_col.line, line_col.col),
+            None => position,
+        },
+    }
+}
+
+/// Convert an edit whose positions have UTF-8 columns to the negotiated encoding.
+pub(crate) fn text_edit(line_index: &LineIndex, edit: lsp_types::TextEdit) -> lsp_types::TextEdit {
+    lsp_types::TextEdit {
+        range: lsp_types::Range::new(
+            position(line_index, edit.range.start),
+            position(line_index, edit.range.end),
+        ),
+        new_text: edit.new_text,
+    }
+}
+
 pub(crate) fn goto_definition_response(
     snap: &GlobalStateSnapshot,
     src: Option<FileRange>,
@@ -52,6 +91,159 @@ fn location_info(
     Ok((target_uri, target_range, target_selection_range))
 }
 
+pub(crate) fn location(snap: &GlobalStateSnapshot, frange: FileRange) -> lsp_types::Location {
+    lsp_types::Location::new(url(snap, frange.file_id), frange.range)
+}
+
+pub(crate) fn workspace_edit(
+    snap: &GlobalStateSnapshot,
+    source_change: SourceChange,
+) -> lsp_types::WorkspaceEdit {
+    let changes = source_change
+        .source_file_edits
+        .into_iter()
+        .map(|(file_id, edits)| (url(snap, file_id), edits))
+        .collect();
+
+    lsp_types::WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn code_action_kind(kind: AssistKind) -> lsp_types::CodeActionKind {
+    match kind {
+        AssistKind::QuickFix => lsp_types::CodeActionKind::QUICKFIX,
+        AssistKind::Refactor => lsp_types::CodeActionKind::REFACTOR,
+    }
+}
+
+pub(crate) fn code_action(
+    snap: &GlobalStateSnapshot,
+    assist: Assist,
+    diagnostics: Vec<lsp_types::Diagnostic>,
+) -> lsp_types::CodeAction {
+    lsp_types::CodeAction {
+        title: assist.label,
+        kind: Some(code_action_kind(assist.id.1)),
+        diagnostics: (!diagnostics.is_empty()).then_some(diagnostics),
+        edit: Some(workspace_edit(snap, assist.source_change)),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn signature_help(
+    help: SignatureHelp,
+    label_offsets: bool,
+) -> lsp_types::SignatureHelp {
+    let parameters = if label_offsets {
+        help.parameter_ranges()
+            .iter()
+            .map(|range| {
+                // Offsets are expressed in UTF-16 code units.
+                let start = help.signature[..range.start].encode_utf16().count() as u32;
+                let len = help.signature[range.clone()].encode_utf16().count() as u32;
+                lsp_types::ParameterInformation {
+                    label: lsp_types::ParameterLabel::LabelOffsets([start, start + len]),
+                    documentation: None,
+                }
+            })
+            .collect()
+    } else {
+        help.parameter_labels()
+            .map(|label| lsp_types::ParameterInformation {
+                label: lsp_types::ParameterLabel::Simple(label.to_string()),
+                documentation: None,
+            })
+            .collect()
+    };
+    let documentation = help.doc.map(|doc| {
+        lsp_types::Documentation::MarkupContent(lsp_types::MarkupContent {
+            kind: lsp_types::MarkupKind::Markdown,
+            value: doc.to_markdown(),
+        })
+    });
+    let active_parameter = help.active_parameter.map(|it| it as u32);
+
+    lsp_types::SignatureHelp {
+        signatures: vec![lsp_types::SignatureInformation {
+            label: help.signature,
+            documentation,
+            parameters: Some(parameters),
+            active_parameter,
+        }],
+        active_signature: Some(0),
+        active_parameter,
+    }
+}
+
+pub(crate) fn document_highlight_kind(
+    category: ReferenceCategory,
+) -> lsp_types::DocumentHighlightKind {
+    match category {
+        ReferenceCategory::Read => lsp_types::DocumentHighlightKind::READ,
+        ReferenceCategory::Write => lsp_types::DocumentHighlightKind::WRITE,
+    }
+}
+
+pub(crate) fn folding_range(fold: Fold, line_folding_only: bool) -> lsp_types::FoldingRange {
+    let kind = match fold.kind {
+        FoldKind::Block => None,
+        FoldKind::Comment => Some(lsp_types::FoldingRangeKind::Comment),
+        FoldKind::Includes => Some(lsp_types::FoldingRangeKind::Imports),
+        FoldKind::Region | FoldKind::Conditional | FoldKind::InactiveCode => {
+            Some(lsp_types::FoldingRangeKind::Region)
+        }
+    };
+    let range = fold.range;
+    if !line_folding_only {
+        return lsp_types::FoldingRange {
+            start_line: range.start.line,
+            start_character: Some(range.start.character),
+            end_line: range.end.line,
+            end_character: Some(range.end.character),
+            kind,
+            collapsed_text: None,
+        };
+    }
+
+    // Keep the line of the closing brace of a block visible.
+    let end_line = match fold.kind {
+        FoldKind::Block => range.end.line.saturating_sub(1).max(range.start.line),
+        _ => range.end.line,
+    };
+    lsp_types::FoldingRange {
+        start_line: range.start.line,
+        start_character: None,
+        end_line,
+        end_character: None,
+        kind,
+        collapsed_text: None,
+    }
+}
+
+pub(crate) fn inlay_hint(hint: InlayHint) -> lsp_types::InlayHint {
+    let (kind, padding_left, padding_right) = match hint.kind {
+        InlayKind::Parameter => (Some(lsp_types::InlayHintKind::PARAMETER), false, true),
+        InlayKind::Type => (Some(lsp_types::InlayHintKind::TYPE), false, false),
+        InlayKind::EnumValue | InlayKind::DefineValue => (None, true, false),
+    };
+    lsp_types::InlayHint {
+        position: hint.position,
+        label: lsp_types::InlayHintLabel::String(hint.label),
+        kind,
+        text_edits: None,
+        tooltip: None,
+        padding_left: Some(padding_left),
+        padding_right: Some(padding_right),
+        data: None,
+    }
+}
+
+pub(crate) fn rename_error(err: RenameError) -> LspError {
+    LspError::new(lsp_server::ErrorCode::InvalidParams as i32, err.to_string())
+}
+
 pub(crate) fn markup_content(
     markup: Markup,
     kind: ide::HoverDocFormat,
@@ -65,6 +257,156 @@ pub(crate) fn markup_content(
     lsp_types::MarkupContent { kind, value }
 }
 
+pub(crate) fn completion_item(
+    snap: &GlobalStateSnapshot,
+    item: CompletionItem,
+) -> lsp_types::CompletionItem {
+    let data = item.data.and_then(|frange| {
+        serde_json::to_value(CompletionResolveData {
+            uri: url(snap, frange.file_id),
+            range: frange.range,
+        })
+        .ok()
+    });
+
+    lsp_types::CompletionItem {
+        label: item.label,
+        label_details: item
+            .description
+            .map(|description| lsp_types::CompletionItemLabelDetails {
+                detail: None,
+                description: Some(description),
+            }),
+        kind: Some(completion_item_kind(item.kind)),
+        detail: item.detail,
+        insert_text: item.insert_text,
+        data,
+        ..Default::default()
+    }
+}
+
+pub(crate) fn completion_item_kind(kind: CompletionKind) -> lsp_types::CompletionItemKind {
+    match kind {
+        CompletionKind::SymbolKind(symbol) => match symbol {
+            SymbolKind::Macro => lsp_types::CompletionItemKind::CONSTANT,
+            SymbolKind::Function | SymbolKind::Forward | SymbolKind::Native => {
+                lsp_types::CompletionItemKind::FUNCTION
+            }
+            SymbolKind::Method => lsp_types::CompletionItemKind::METHOD,
+            SymbolKind::Global | SymbolKind::Local | SymbolKind::Param => {
+                lsp_types::CompletionItemKind::VARIABLE
+            }
+            SymbolKind::Enum => lsp_types::CompletionItemKind::ENUM,
+            SymbolKind::Variant => lsp_types::CompletionItemKind::ENUM_MEMBER,
+            SymbolKind::EnumStruct => lsp_types::CompletionItemKind::STRUCT,
+            SymbolKind::Field => lsp_types::CompletionItemKind::FIELD,
+            SymbolKind::Methodmap => lsp_types::CompletionItemKind::CLASS,
+            SymbolKind::Property => lsp_types::CompletionItemKind::PROPERTY,
+            SymbolKind::Typedef
+            | SymbolKind::Typeset
+            | SymbolKind::Functag
+            | SymbolKind::Funcenum => lsp_types::CompletionItemKind::INTERFACE,
+        },
+        CompletionKind::Keyword => lsp_types::CompletionItemKind::KEYWORD,
+        CompletionKind::File => lsp_types::CompletionItemKind::FILE,
+        CompletionKind::Directory => lsp_types::CompletionItemKind::FOLDER,
+    }
+}
+
+pub(crate) fn symbol_kind(kind: SymbolKind) -> lsp_types::SymbolKind {
+    match kind {
+        SymbolKind::Macro => lsp_types::SymbolKind::CONSTANT,
+        SymbolKind::Function | SymbolKind::Forward | SymbolKind::Native => {
+            lsp_types::SymbolKind::FUNCTION
+        }
+        SymbolKind::Method => lsp_types::SymbolKind::METHOD,
+        SymbolKind::Global | SymbolKind::Local | SymbolKind::Param => {
+            lsp_types::SymbolKind::VARIABLE
+        }
+        SymbolKind::Enum => lsp_types::SymbolKind::ENUM,
+        SymbolKind::Variant => lsp_types::SymbolKind::ENUM_MEMBER,
+        SymbolKind::EnumStruct => lsp_types::SymbolKind::STRUCT,
+        SymbolKind::Field => lsp_types::SymbolKind::FIELD,
+        SymbolKind::Methodmap => lsp_types::SymbolKind::CLASS,
+        SymbolKind::Property => lsp_types::SymbolKind::PROPERTY,
+        SymbolKind::Typedef | SymbolKind::Typeset | SymbolKind::Functag | SymbolKind::Funcenum => {
+            lsp_types::SymbolKind::INTERFACE
+        }
+    }
+}
+
+pub(crate) fn document_symbol(symbol: DocumentSymbol) -> lsp_types::DocumentSymbol {
+    #[allow(deprecated)]
+    lsp_types::DocumentSymbol {
+        name: symbol.name,
+        detail: symbol.detail,
+        kind: symbol_kind(symbol.kind),
+        tags: None,
+        deprecated: None,
+        range: symbol.range,
+        selection_range: symbol.selection_range,
+        children: Some(
+            symbol
+                .children
+                .into_iter()
+                .map(document_symbol)
+                .collect(),
+        ),
+    }
+}
+
+/// Flatten a [`DocumentSymbol`] and its children, for clients that do not support nested symbols.
+pub(crate) fn flat_document_symbol(
+    url: &lsp_types::Url,
+    symbol: DocumentSymbol,
+    container_name: Option<String>,
+    acc: &mut Vec<lsp_types::SymbolInformation>,
+) {
+    for child in symbol.children {
+        flat_document_symbol(url, child, Some(symbol.name.clone()), acc);
+    }
+    #[allow(deprecated)]
+    acc.push(lsp_types::SymbolInformation {
+        name: symbol.name,
+        kind: symbol_kind(symbol.kind),
+        tags: None,
+        deprecated: None,
+        location: lsp_types::Location::new(url.clone(), symbol.range),
+        container_name,
+    });
+}
+
+pub(crate) fn workspace_symbol(
+    snap: &GlobalStateSnapshot,
+    symbol: WorkspaceSymbol,
+) -> lsp_types::SymbolInformation {
+    #[allow(deprecated)]
+    lsp_types::SymbolInformation {
+        name: symbol.name,
+        kind: symbol_kind(symbol.kind),
+        tags: None,
+        deprecated: None,
+        location: lsp_types::Location::new(url(snap, symbol.file_id), symbol.range),
+        container_name: symbol.container_name,
+    }
+}
+
+pub(crate) fn call_hierarchy_item(
+    snap: &GlobalStateSnapshot,
+    item: CallHierarchyItem,
+) -> lsp_types::CallHierarchyItem {
+    lsp_types::CallHierarchyItem {
+        name: item.name,
+        kind: symbol_kind(item.kind),
+        tags: None,
+        detail: item.detail,
+        uri: url(snap, item.file_id),
+        range: item.range,
+        selection_range: item.selection_range,
+        data: None,
+    }
+}
+
 pub(crate) fn url(snap: &GlobalStateSnapshot, file_id: FileId) -> lsp_types::Url {
     snap.file_id_to_url(file_id)
 }
@@ -108,6 +450,21 @@ fn semantic_token_type_and_modifiers(
     let type_ = match highlight.tag {
         HlTag::Symbol(symbol) => match symbol {
             SymbolKind::Macro => semantic_tokens::MACRO,
+            SymbolKind::Function | SymbolKind::Forward | SymbolKind::Native => {
+                semantic_tokens::FUNCTION
+            }
+            SymbolKind::Method => semantic_tokens::METHOD,
+            SymbolKind::Global | SymbolKind::Local => semantic_tokens::VARIABLE,
+            SymbolKind::Param => semantic_tokens::PARAMETER,
+            SymbolKind::Enum => semantic_tokens::ENUM,
+            SymbolKind::Variant => semantic_tokens::ENUM_MEMBER,
+            SymbolKind::EnumStruct => semantic_tokens::STRUCT,
+            SymbolKind::Field | SymbolKind::Property => semantic_tokens::PROPERTY,
+            SymbolKind::Methodmap => semantic_tokens::INTERFACE,
+            SymbolKind::Typedef
+            | SymbolKind::Typeset
+            | SymbolKind::Functag
+            | SymbolKind::Funcenum => semantic_tokens::TYPE_ALIAS,
         },
         HlTag::None => semantic_tokens::GENERIC,
     };
@@ -115,6 +472,11 @@ fn semantic_token_type_and_modifiers(
     for modifier in highlight.mods.iter() {
         let modifier = match modifier {
             HlMod::Macro => semantic_tokens::MACRO_MODIFIER,
+            HlMod::Declaration => semantic_tokens::DECLARATION,
+            HlMod::Static => semantic_tokens::STATIC,
+            HlMod::Const => semantic_tokens::CONSTANT,
+            HlMod::Deprecated => semantic_tokens::DEPRECATED,
+            HlMod::DefaultLibrary => semantic_tokens::DEFAULT_LIBRARY,
         };
         mods |= modifier;
     }
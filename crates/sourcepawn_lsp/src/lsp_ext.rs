@@ -118,3 +118,11 @@ impl Notification for SpcompStatusNotification {
 pub struct SpcompStatusParams {
     pub quiescent: bool,
 }
+
+/// Data attached to a completion item, used to resolve its documentation.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionResolveData {
+    pub uri: Url,
+    pub range: lsp_types::Range,
+}
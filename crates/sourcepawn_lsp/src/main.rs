@@ -1,5 +1,6 @@
 use clap::ArgAction;
 use clap::Parser;
+use clap::Subcommand;
 use log::LevelFilter;
 use lsp_server::Connection;
 use std::env;
@@ -7,9 +8,10 @@ use std::error::Error;
 use std::fs::OpenOptions;
 use std::io;
 use std::path::PathBuf;
+use std::process;
 use std::time::SystemTime;
 
-use sourcepawn_lsp::GlobalState;
+use sourcepawn_lsp::{cli, GlobalState};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -36,11 +38,43 @@ pub struct Opts {
     /// Write the logging output to FILE
     #[clap(long, name = "FILE", value_parser)]
     log_file: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+/// Commands to run instead of the language server
+#[derive(Debug, Subcommand, Clone)]
+enum Command {
+    /// Print the diagnostics of the plugins of a directory, and exit with a nonzero status if any
+    /// of them is an error
+    Check(cli::Check),
+    /// Load a workspace and report the time and memory spent in each phase of the analysis
+    AnalysisStats(cli::AnalysisStats),
+    /// Generate an HTML and JSON reference of the API declared in include files
+    Docgen(cli::Docgen),
+}
+
+fn run_command(command: Command) -> Result<(), Box<dyn Error + Sync + Send>> {
+    match command {
+        Command::Check(check) => {
+            if check.run()? {
+                process::exit(1);
+            }
+        }
+        Command::AnalysisStats(analysis_stats) => analysis_stats.run()?,
+        Command::Docgen(docgen) => docgen.run()?,
+    }
+
+    Ok(())
 }
 
 fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
     let opts = Opts::parse();
     setup_logger(opts.clone());
+    if let Some(command) = opts.command {
+        return run_command(command);
+    }
 
     let _guard = if !opts.disable_telemetry {
         log::info!("Telemetry is enabled. To disable it, use the --disable-telemetry flag.");
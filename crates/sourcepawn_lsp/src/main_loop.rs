@@ -385,7 +385,35 @@ impl GlobalState {
                 handlers::handle_semantic_tokens_range,
             )
             .on::<lsp_request::GotoDefinition>(handlers::handle_goto_definition)
+            .on::<lsp_request::GotoDeclaration>(handlers::handle_goto_declaration)
+            .on::<lsp_request::GotoImplementation>(handlers::handle_goto_implementation)
+            .on::<lsp_request::GotoTypeDefinition>(handlers::handle_goto_type_definition)
             .on::<lsp_request::HoverRequest>(handlers::handle_hover)
+            .on::<lsp_request::References>(handlers::handle_references)
+            .on::<lsp_request::CodeActionRequest>(handlers::handle_code_action)
+            .on::<lsp_request::DocumentHighlightRequest>(handlers::handle_document_highlight)
+            .on::<lsp_request::DocumentSymbolRequest>(handlers::handle_document_symbol)
+            .on::<lsp_request::FoldingRangeRequest>(handlers::handle_folding_range)
+            .on::<lsp_request::DocumentDiagnosticRequest>(handlers::handle_document_diagnostic)
+            .on::<lsp_request::WorkspaceDiagnosticRequest>(handlers::handle_workspace_diagnostic)
+            .on::<lsp_request::SelectionRangeRequest>(handlers::handle_selection_range)
+            .on::<lsp_request::WorkspaceSymbolRequest>(handlers::handle_workspace_symbol)
+            .on::<lsp_request::SignatureHelpRequest>(handlers::handle_signature_help)
+            .on::<lsp_request::InlayHintRequest>(handlers::handle_inlay_hints)
+            .on::<lsp_request::CallHierarchyPrepare>(handlers::handle_call_hierarchy_prepare)
+            .on::<lsp_request::CallHierarchyIncomingCalls>(
+                handlers::handle_call_hierarchy_incoming,
+            )
+            .on::<lsp_request::CallHierarchyOutgoingCalls>(
+                handlers::handle_call_hierarchy_outgoing,
+            )
+            .on::<lsp_request::Formatting>(handlers::handle_formatting)
+            .on::<lsp_request::RangeFormatting>(handlers::handle_range_formatting)
+            .on::<lsp_request::OnTypeFormatting>(handlers::handle_on_type_formatting)
+            .on::<lsp_request::PrepareRenameRequest>(handlers::handle_prepare_rename)
+            .on::<lsp_request::Rename>(handlers::handle_rename)
+            .on::<lsp_request::Completion>(handlers::handle_completion)
+            .on::<lsp_request::ResolveCompletionItem>(handlers::handle_completion_resolve)
             .on::<lsp_ext::SyntaxTree>(handlers::handle_syntax_tree)
             .on::<lsp_ext::ProjectsGraphviz>(handlers::handle_projects_graphviz)
             .on::<lsp_ext::PreprocessedDocument>(handlers::handle_preprocessed_document)
@@ -678,6 +706,17 @@ impl GlobalState {
                     self.send_request::<lsp_types::request::SemanticTokensRefresh>((), |_, _| ());
                 }
 
+                // Diagnostics depend on the includes of a file, make the clients that pull them
+                // request them again, for open files as well as for the workspace.
+                if self.config.caps().has_pull_diagnostics_support()
+                    && self.config.workspace_diagnostics_refresh()
+                {
+                    self.send_request::<lsp_types::request::WorkspaceDiagnosticRefresh>(
+                        (),
+                        |_, _| (),
+                    );
+                }
+
                 // Refresh code lens if the client supports it.
                 // if self.config.code_lens_refresh() {
                 //     self.send_request::<lsp_types::request::CodeLensRefresh>((), |_, _| ());
@@ -692,8 +731,10 @@ impl GlobalState {
                 // }
             }
 
+            // Clients that pull diagnostics only get the ones of spcomp pushed.
             let update_diagnostics = (!was_quiescent || state_changed || memdocs_added_or_removed)
-                && self.config.publish_diagnostics();
+                && self.config.publish_diagnostics()
+                && !self.config.caps().has_pull_diagnostics_support();
             if update_diagnostics {
                 self.update_diagnostics()
             }
@@ -0,0 +1,31 @@
+use insta::assert_snapshot;
+use sourcepawn_lsp::fixture::analysis_stats;
+
+#[test]
+fn analysis_stats_1() {
+    assert_snapshot!(analysis_stats(
+        r#"
+%! main.sp
+#include "foo"
+
+enum struct Point {
+    int x;
+    int Get() { return 1; }
+}
+
+methodmap Bar < Foo {
+    public Bar() { return view_as<Bar>(1); }
+    public void Qux() {}
+}
+
+public void OnPluginStart() {
+    int a = baz;
+}
+%! foo.inc
+methodmap Foo {
+    public void Baz() {}
+}
+
+typedef Callback = function void ();"#,
+    ));
+}
@@ -0,0 +1,53 @@
+use insta::{assert_json_snapshot, assert_snapshot};
+use sourcepawn_lsp::{cli::OutputFormat, fixture::check};
+
+const FIXTURE: &str = r#"
+%! main.sp
+#include "foo"
+
+public void OnPluginStart() {
+    int a = Bar();
+    a = baz;
+}
+%! foo.inc
+float Bar() {
+    return 1.0;
+}
+%! other.sp
+stock void Unused() {}"#;
+
+#[test]
+fn text_1() {
+    let (errored, output) = check(FIXTURE, OutputFormat::Text);
+    assert!(errored);
+    assert_snapshot!(output);
+}
+
+#[test]
+fn json_1() {
+    let (errored, output) = check(FIXTURE, OutputFormat::Json);
+    assert!(errored);
+    assert_snapshot!(output);
+}
+
+#[test]
+fn sarif_1() {
+    let (errored, output) = check(FIXTURE, OutputFormat::Sarif);
+    assert!(errored);
+    let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_json_snapshot!(sarif, {
+        ".runs[].tool.driver.version" => "[version]",
+    });
+}
+
+#[test]
+fn no_errors_1() {
+    let (errored, output) = check(
+        r#"
+%! main.sp
+public void OnPluginStart() {}"#,
+        OutputFormat::Text,
+    );
+    assert!(!errored);
+    assert_snapshot!(output);
+}
@@ -0,0 +1,44 @@
+use insta::{assert_json_snapshot, assert_snapshot};
+use sourcepawn_lsp::fixture::docgen;
+
+const FIXTURE: &str = r#"
+%! foo.inc
+// Maximum length of a name.
+#define MAX_NAME 32
+
+/**
+ * Get the name of a client.
+ *
+ * @param client    Index of the client.
+ * @param name      Buffer to store the name in.
+ * @return          True on success.
+ */
+native bool GetName(int client, char[] name, int maxlen = MAX_NAME);
+
+methodmap Player {
+    // Index of the player.
+    property int Index {
+        public get() { return view_as<int>(this); }
+    }
+}"#;
+
+#[test]
+fn json_index_1() {
+    let pages = docgen(FIXTURE);
+    let index: serde_json::Value = serde_json::from_str(&pages["index.json"]).unwrap();
+    assert_json_snapshot!(index, {
+        ".version" => "[version]",
+    });
+}
+
+#[test]
+fn html_index_1() {
+    let pages = docgen(FIXTURE);
+    assert_snapshot!(pages["index.html"]);
+}
+
+#[test]
+fn html_page_1() {
+    let pages = docgen(FIXTURE);
+    assert_snapshot!(pages["foo.html"]);
+}
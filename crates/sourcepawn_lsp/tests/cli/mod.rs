@@ -0,0 +1,3 @@
+mod analysis_stats;
+mod check;
+mod docgen;
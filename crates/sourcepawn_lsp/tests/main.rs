@@ -1 +1,2 @@
+mod cli;
 mod text_document;
@@ -0,0 +1,45 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::{call_hierarchy_incoming, call_hierarchy_outgoing};
+
+#[test]
+fn incoming_1() {
+    assert_json_snapshot!(call_hierarchy_incoming(
+        r#"
+%! main.sp
+void foo() {}
+      |
+      ^
+void bar() {
+    foo();
+    foo();
+}"#,
+    ));
+}
+
+#[test]
+fn outgoing_1() {
+    assert_json_snapshot!(call_hierarchy_outgoing(
+        r#"
+%! main.sp
+void foo() {}
+void bar() {
+      |
+      ^
+    foo();
+}"#,
+    ));
+}
+
+#[test]
+fn forward_1() {
+    assert_json_snapshot!(call_hierarchy_outgoing(
+        r#"
+%! include/foo.inc
+forward void OnFoo();
+              |
+              ^
+%! main.sp
+#include <foo>
+public void OnFoo() {}"#,
+    ));
+}
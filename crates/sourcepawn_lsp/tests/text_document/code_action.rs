@@ -0,0 +1,74 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::code_actions;
+
+#[test]
+fn unresolved_include_1() {
+    assert_json_snapshot!(code_actions(
+        r#"
+%! main.sp
+#include "foo"
+           |
+           ^
+int x;"#,
+    ));
+}
+
+#[test]
+fn unresolved_field_1() {
+    assert_json_snapshot!(code_actions(
+        r#"
+%! main.sp
+enum struct Foo {
+    int count;
+}
+void bar() {
+    Foo foo;
+    foo.cont = 1;
+        |
+        ^
+}"#,
+    ));
+}
+
+#[test]
+fn incorrect_number_of_arguments_1() {
+    assert_json_snapshot!(code_actions(
+        r#"
+%! main.sp
+void foo(int a, int b) {}
+void bar() {
+    foo(1);
+        |
+        ^
+}"#,
+    ));
+}
+
+#[test]
+fn incorrect_number_of_arguments_2() {
+    assert_json_snapshot!(code_actions(
+        r#"
+%! main.sp
+methodmap Foo {}
+void foo(int a, float b, const char[] c, int d = 5, Foo e, bool f = true) {}
+void bar() {
+    foo(1);
+        |
+        ^
+}"#,
+    ));
+}
+
+#[test]
+fn incorrect_number_of_arguments_3() {
+    assert_json_snapshot!(code_actions(
+        r#"
+%! main.sp
+void foo(int a, int &b) {}
+void bar() {
+    foo(1);
+        |
+        ^
+}"#,
+    ));
+}
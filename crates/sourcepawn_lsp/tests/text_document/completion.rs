@@ -143,3 +143,68 @@ int bar;"#,
         Some("/".to_string())
     ));
 }
+
+#[test]
+fn methodmap_member_1() {
+    assert_json_snapshot!(complete(
+        r#"
+%! main.sp
+methodmap Foo {
+    public void Bar() {}
+    property int Baz {
+        public get() { return 1; }
+    }
+}
+
+methodmap Qux < Foo {
+    public void Quux() {}
+}
+
+void main() {
+    Qux qux;
+    qux.
+        |
+        ^
+}"#,
+        Some('.'.to_string())
+    ));
+}
+
+#[test]
+fn enum_struct_member_1() {
+    assert_json_snapshot!(complete(
+        r#"
+%! main.sp
+enum struct Foo {
+    int bar;
+    void Baz() {}
+}
+
+void main() {
+    Foo foo;
+    foo.
+        |
+        ^
+}"#,
+        Some('.'.to_string())
+    ));
+}
+
+#[test]
+fn enum_variant_1() {
+    assert_json_snapshot!(complete(
+        r#"
+%! main.sp
+enum Foo {
+    Bar,
+    Baz
+}
+
+void main() {
+    Foo::
+         |
+         ^
+}"#,
+        Some(':'.to_string())
+    ));
+}
@@ -0,0 +1,378 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::{document_diagnostics, document_diagnostics_with_config};
+
+#[test]
+fn unresolved_ident_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+int foo() {
+    int bar = baz;
+    return bar;
+}"#,
+    ));
+}
+
+#[test]
+fn unresolved_ident_builtin_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+int a;
+char b[64];
+
+public void foo() {
+    a = cellmax + __LINE__;
+    b = __BINARY_NAME__;
+    a = baz;
+}"#,
+    ));
+}
+
+#[test]
+fn unresolved_ident_unresolved_include_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+#include <bar>
+
+int a;
+
+public void foo() {
+    a = baz;
+}"#,
+    ));
+}
+
+#[test]
+fn unused_variable_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+int foo() {
+    int bar = 1;
+    int baz = 2;
+    return baz;
+}"#,
+    ));
+}
+
+#[test]
+fn unused_parameter_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+int foo(int a, int b) {
+    return a;
+}"#,
+    ));
+}
+
+#[test]
+fn unused_global_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+static int foo;
+static int bar;
+
+void baz() {
+    bar = 1;
+}"#,
+    ));
+}
+
+#[test]
+fn unused_global_shadowed_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+static int foo;
+
+void bar() {
+    int foo;
+    foo = 1;
+}"#,
+    ));
+}
+
+#[test]
+fn unused_function_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+stock void foo() {}
+stock void bar() {}
+
+public void OnPluginStart() {
+    bar();
+}"#,
+    ));
+}
+
+#[test]
+fn unused_include_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+#include "foo"
+#include "bar"
+
+public void OnPluginStart() {
+    bar();
+}
+%! foo.inc
+void foo() {}
+%! bar.inc
+void bar() {}"#,
+    ));
+}
+
+#[test]
+fn disabled_lint_1() {
+    assert_json_snapshot!(document_diagnostics_with_config(
+        r#"
+%! main.sp
+int foo(int a) {
+    int b;
+    return 0;
+}"#,
+        serde_json::json!({ "diagnostics": { "disabled": ["unused-variable"] } }),
+    ));
+}
+
+#[test]
+fn deprecated_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+#pragma deprecated Use bar() instead.
+void foo() {}
+
+void bar() {
+    foo();
+}"#,
+    ));
+}
+
+#[test]
+fn deprecated_forward_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+public void OnFoo() {}
+
+#pragma deprecated Use OnBar() instead.
+forward void OnFoo();
+
+void bar() {
+    OnFoo();
+}"#,
+    ));
+}
+
+#[test]
+fn deprecated_blank_line_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+#pragma deprecated Use bar() instead.
+
+// Kept for compatibility.
+void foo() {}
+
+void bar() {
+    foo();
+}"#,
+    ));
+}
+
+#[test]
+fn directives_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+#warning Not ready yet
+#assert 1 == 2"#,
+    ));
+}
+
+#[test]
+fn error_directive_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+#error Not supported
+int foo;"#,
+    ));
+}
+
+#[test]
+fn endinput_directive_guard_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+#define _foo_included
+#if defined _foo_included
+#endinput
+#endif
+int foo = bar;"#,
+    ));
+}
+
+#[test]
+fn mismatched_assignment_type_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+int a;
+float b;
+
+public void foo() {
+    a = b;
+    b = a;
+    bool c = a;
+    c = b;
+}"#,
+    ));
+}
+
+#[test]
+fn mismatched_arg_type_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+void foo(int a, float b) {
+    foo(b, a);
+}"#,
+    ));
+}
+
+#[test]
+fn mismatched_return_type_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+float foo() {
+    return 1;
+}
+
+int bar() {
+    return foo();
+}
+
+bool baz() {
+    return bar();
+}"#,
+    ));
+}
+
+#[test]
+fn mismatched_type_weak_tags_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+enum Color { Color_Red }
+
+int a;
+Color c;
+float d;
+
+public void foo() {
+    bool b = 1;
+    a = b + 1;
+    c = c + 1;
+    c = 1 + c;
+    d = 1 + 2.0;
+    a = 2.0 + 1;
+}"#,
+    ));
+}
+
+#[test]
+fn mismatched_type_legacy_tags_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+new Float:a;
+new bool:b;
+
+public foo(String:c) {
+    a = 1;
+    a = 2.0;
+    b = a;
+    a = Float:b;
+    c = 'd';
+    c = 3.0;
+}"#,
+    ));
+}
+
+#[test]
+fn mismatched_type_view_as_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+methodmap Foo {}
+methodmap Bar {}
+
+public void foo(Foo a, Bar b) {
+    a = b;
+    a = view_as<Foo>(b);
+    int c = a;
+    c = view_as<int>(a);
+}"#,
+    ));
+}
+
+#[test]
+fn mismatched_type_null_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+methodmap Foo {}
+
+public void foo(Foo a) {
+    a = null;
+    a = 1;
+}"#,
+    ));
+}
+
+#[test]
+fn mismatched_type_inheritance_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+methodmap Foo {}
+methodmap Bar < Foo {}
+methodmap Baz {}
+
+public void foo(Foo a) {}
+
+public void bar(Bar b, Baz c) {
+    foo(b);
+    foo(c);
+    Bar d = view_as<Foo>(b);
+    foo(d);
+}"#,
+    ));
+}
+
+#[test]
+fn mismatched_type_function_reference_1() {
+    assert_json_snapshot!(document_diagnostics(
+        r#"
+%! main.sp
+int foo() {
+    return 1;
+}
+
+float a;
+
+public void bar() {
+    a = foo;
+    a = foo();
+}"#,
+    ));
+}
@@ -0,0 +1,76 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::document_highlight;
+
+#[test]
+fn local_1() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+void foo() {
+    int bar = 1;
+    bar = bar + 1;
+    |
+    ^
+    bar++;
+}"#,
+    ));
+}
+
+#[test]
+fn macro_argument_1() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+#define ADD(%1) %1 + 1
+void foo() {
+    int bar;
+        |
+        ^
+    bar = ADD(bar);
+}"#,
+    ));
+}
+
+#[test]
+fn compound_assignment_1() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+void foo() {
+    int a;
+    a += 1;
+    |
+    ^
+    a = a * 2;
+}"#,
+    ));
+}
+
+#[test]
+fn array_assignment_1() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+void foo(int i, int x) {
+    int arr[4];
+    arr[i] = x;
+    |
+    ^
+    x = arr[i];
+}"#,
+    ));
+}
+
+#[test]
+fn array_assignment_2() {
+    assert_json_snapshot!(document_highlight(
+        r#"
+%! main.sp
+void foo(int i, int x) {
+    int arr[4];
+    arr[i] = x;
+        |
+        ^
+}"#,
+    ));
+}
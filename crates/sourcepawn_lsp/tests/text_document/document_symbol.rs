@@ -0,0 +1,42 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::{document_symbols, workspace_symbols};
+
+#[test]
+fn enum_1() {
+    assert_json_snapshot!(document_symbols(
+        r#"
+%! main.sp
+enum Foo {
+    Foo_A,
+    Foo_B
+}"#,
+    ));
+}
+
+#[test]
+fn methodmap_1() {
+    assert_json_snapshot!(document_symbols(
+        r#"
+%! main.sp
+methodmap Foo {
+    public void Bar() {}
+    property int Baz {
+        public get() { return 1; }
+    }
+}"#,
+    ));
+}
+
+#[test]
+fn workspace_symbol_1() {
+    assert_json_snapshot!(workspace_symbols(
+        r#"
+%! main.sp
+enum struct FooBar {
+    int fooCount;
+}
+void PrintFoo() {}
+void Bar() {}"#,
+        "foo",
+    ));
+}
@@ -0,0 +1,37 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::folding_ranges;
+
+#[test]
+fn folding_range_1() {
+    assert_json_snapshot!(folding_ranges(
+        r#"
+%! main.sp
+#include <sourcemod>
+#include <sdktools>
+#if 0
+int a;
+#endif
+// #pragma region Foo
+void OnPluginStart()
+{
+    int b;
+}
+// #pragma endregion"#,
+    ));
+}
+
+#[test]
+fn comment_1() {
+    assert_json_snapshot!(folding_ranges(
+        r#"
+%! main.sp
+/**
+ * Foo
+ */
+#if 1
+int a;
+#else
+int b;
+#endif"#,
+    ));
+}
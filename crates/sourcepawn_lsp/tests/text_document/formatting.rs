@@ -0,0 +1,44 @@
+use insta::assert_snapshot;
+use sourcepawn_lsp::fixture::formatting;
+
+#[test]
+fn formatting_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+void OnPluginStart() {
+int a=1;
+}"#,
+    ));
+}
+
+#[test]
+fn preprocessor_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+#define FOO 1
+#if 0
+  int  b;
+#endif
+void OnPluginStart()
+{
+      int a = FOO;
+}"#,
+    ));
+}
+
+#[test]
+fn non_ascii_1() {
+    assert_snapshot!(formatting(
+        r#"
+%! main.sp
+void OnPluginStart()
+{
+    char s[8] = "é";   
+    // déjà vu   
+    PrintToServer("%s",s);
+    int a=1; /* ü */ int b=2;   
+}"#,
+    ));
+}
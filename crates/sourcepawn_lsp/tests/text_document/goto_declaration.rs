@@ -0,0 +1,30 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::goto_declaration;
+
+#[test]
+fn forward_1() {
+    assert_json_snapshot!(goto_declaration(
+        r#"
+%! include/foo.inc
+forward void OnFoo();
+%! main.sp
+#include <foo>
+public void OnFoo() {}
+             |
+             ^"#,
+    ));
+}
+
+#[test]
+fn definition_1() {
+    assert_json_snapshot!(goto_declaration(
+        r#"
+%! main.sp
+void Foo() {}
+void Bar() {
+    Foo();
+    |
+    ^
+}"#,
+    ));
+}
@@ -0,0 +1,32 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::goto_implementation;
+
+#[test]
+fn forward_1() {
+    assert_json_snapshot!(goto_implementation(
+        r#"
+%! include/foo.inc
+forward void OnFoo();
+              |
+              ^
+%! main.sp
+#include <foo>
+public void OnFoo() {}"#,
+    ));
+}
+
+#[test]
+fn methodmap_native_1() {
+    assert_json_snapshot!(goto_implementation(
+        r#"
+%! main.sp
+methodmap Foo {
+    public native void Bar();
+                        |
+                        ^
+}
+void baz(Foo foo) {
+    foo.Bar();
+}"#,
+    ));
+}
@@ -0,0 +1,38 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::goto_type_definition;
+
+#[test]
+fn property_1() {
+    assert_json_snapshot!(goto_type_definition(
+        r#"
+%! main.sp
+methodmap Bar {}
+methodmap Foo {
+    property Bar bar {
+        public get() { return view_as<Bar>(0); }
+    }
+}
+void baz(Foo foo) {
+    foo.bar;
+         |
+         ^
+}"#,
+    ));
+}
+
+#[test]
+fn array_1() {
+    assert_json_snapshot!(goto_type_definition(
+        r#"
+%! main.sp
+enum struct Foo {
+    int a;
+}
+void bar() {
+    Foo foo[2];
+    foo;
+    |
+    ^
+}"#,
+    ));
+}
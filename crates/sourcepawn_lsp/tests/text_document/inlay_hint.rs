@@ -0,0 +1,28 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::inlay_hints;
+
+#[test]
+fn parameter_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+void Foo(int a, int b) {}
+void Bar() {
+    int b;
+    Foo(1, b);
+}"#,
+    ));
+}
+
+#[test]
+fn enum_value_1() {
+    assert_json_snapshot!(inlay_hints(
+        r#"
+%! main.sp
+enum Foo {
+    Foo_A,
+    Foo_B = 5,
+    Foo_C
+}"#,
+    ));
+}
@@ -1,4 +1,20 @@
-// mod completion;
+mod call_hierarchy;
+mod code_action;
+mod completion;
+mod document_diagnostic;
+mod document_highlight;
+mod document_symbol;
+mod folding_range;
+mod formatting;
+mod goto_declaration;
 
 mod goto_definition;
+mod goto_implementation;
+mod goto_type_definition;
 mod hover;
+mod inlay_hint;
+mod references;
+mod rename;
+mod selection_range;
+mod semantic_tokens;
+mod signature_help;
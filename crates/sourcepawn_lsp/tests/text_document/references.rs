@@ -0,0 +1,59 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::references;
+
+#[test]
+fn global_variable_1() {
+    assert_json_snapshot!(references(
+        r#"
+%! main.sp
+int foo;
+     |
+     ^
+void bar() {
+    foo = 1;
+    int baz = foo;
+}"#,
+    ));
+}
+
+#[test]
+fn function_include_1() {
+    assert_json_snapshot!(references(
+        r#"
+%! main.sp
+#include "foo.sp"
+void bar() {
+    foo();
+}
+%! foo.sp
+void foo() {}
+      |
+      ^"#,
+    ));
+}
+
+#[test]
+fn macro_argument_1() {
+    assert_json_snapshot!(references(
+        r#"
+%! main.sp
+#define FOO(%1) %1
+int foo;
+     |
+     ^
+int bar = FOO(foo);"#,
+    ));
+}
+
+#[test]
+fn define_1() {
+    assert_json_snapshot!(references(
+        r#"
+%! main.sp
+#define FOO 1
+         |
+         ^
+int foo = FOO;
+int bar = FOO;"#,
+    ));
+}
@@ -0,0 +1,62 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::rename;
+
+#[test]
+fn local_1() {
+    assert_json_snapshot!(rename(
+        r#"
+%! main.sp
+void bar() {
+    int foo;
+         |
+         ^
+    foo = 1;
+}"#,
+        "baz"
+    ));
+}
+
+#[test]
+fn function_include_1() {
+    assert_json_snapshot!(rename(
+        r#"
+%! main.sp
+#include "foo.sp"
+void bar() {
+    foo();
+    |
+    ^
+}
+%! foo.sp
+void foo() {}"#,
+        "baz"
+    ));
+}
+
+#[test]
+fn macro_body_1() {
+    assert_json_snapshot!(rename(
+        r#"
+%! main.sp
+#define INC foo++
+int foo;
+     |
+     ^
+void bar() {
+    INC;
+}"#,
+        "baz"
+    ));
+}
+
+#[test]
+fn invalid_name_1() {
+    assert_json_snapshot!(rename(
+        r#"
+%! main.sp
+int foo;
+     |
+     ^"#,
+        "1baz"
+    ));
+}
@@ -0,0 +1,15 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::selection_ranges;
+
+#[test]
+fn call_1() {
+    assert_json_snapshot!(selection_ranges(
+        r#"
+%! main.sp
+void foo() {
+    bar(1);
+     |
+     ^
+}"#,
+    ));
+}
@@ -0,0 +1,23 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::semantic_tokens;
+
+#[test]
+fn param_1() {
+    assert_json_snapshot!(semantic_tokens(
+        r#"
+%! main.sp
+void Foo(int a) {
+    int b = a;
+}"#,
+    ));
+}
+
+#[test]
+fn static_const_1() {
+    assert_json_snapshot!(semantic_tokens(
+        r#"
+%! main.sp
+static const int foo = 1;
+int bar = foo;"#,
+    ));
+}
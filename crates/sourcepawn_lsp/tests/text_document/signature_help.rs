@@ -0,0 +1,94 @@
+use insta::assert_json_snapshot;
+use sourcepawn_lsp::fixture::signature_help;
+
+#[test]
+fn function_1() {
+    assert_json_snapshot!(signature_help(
+        r#"
+%! main.sp
+void foo(int a, float b) {}
+void bar() {
+    foo(1, 2.0);
+           |
+           ^
+}"#,
+    ));
+}
+
+#[test]
+fn named_arg_1() {
+    assert_json_snapshot!(signature_help(
+        r#"
+%! main.sp
+void foo(int a, int b = 0, int c = 1) {}
+void bar() {
+    foo(1, .c = 2);
+            |
+            ^
+}"#,
+    ));
+}
+
+#[test]
+fn typedef_1() {
+    assert_json_snapshot!(signature_help(
+        r#"
+%! main.sp
+typedef Callback = function void (int client);
+void foo(Callback cb) {
+    cb(1);
+       |
+       ^
+}"#,
+    ));
+}
+
+#[test]
+fn method_1() {
+    assert_json_snapshot!(signature_help(
+        r#"
+%! main.sp
+methodmap Foo {
+    public void Bar(int a, float b) {}
+}
+void baz(Foo foo) {
+    foo.Bar(1, 2.0);
+               |
+               ^
+}"#,
+    ));
+}
+
+#[test]
+fn enum_struct_method_1() {
+    assert_json_snapshot!(signature_help(
+        r#"
+%! main.sp
+enum struct Foo {
+    int x;
+    void Bar(int a, float b) {}
+}
+void baz() {
+    Foo foo;
+    foo.Bar(1, 2.0);
+            |
+            ^
+}"#,
+    ));
+}
+
+#[test]
+fn constructor_1() {
+    assert_json_snapshot!(signature_help(
+        r#"
+%! main.sp
+methodmap Foo {
+    public Foo(int a, float b) {}
+}
+void baz() {
+    Foo foo = new Foo(1, 2.0);
+                         |
+                         ^
+}"#,
+    ));
+}
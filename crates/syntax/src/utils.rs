@@ -1,5 +1,7 @@
 use lsp_types::Position;
 
+use crate::TSKind;
+
 /// Convert a Tree-sitter [Range](tree_sitter::Range) to an LSP [Range](lsp_types::Range).
 ///
 /// # Arguments
@@ -175,3 +177,33 @@ fn intersect_3() {
         })
     );
 }
+
+/// Get the note of the `#pragma deprecated` directive that precedes a declaration, if any.
+///
+/// The note is empty if the directive does not have one. Like the compiler, which applies the
+/// directive to the next declaration, the comments, blank lines and other `#pragma` directives
+/// between the directive and the declaration are skipped.
+pub fn deprecation_note(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut prev_node = node.prev_sibling();
+    while let Some(node) = prev_node {
+        match TSKind::from(node) {
+            TSKind::preproc_pragma => {
+                let pragma = node
+                    .utf8_text(source)
+                    .ok()?
+                    .trim_start_matches('#')
+                    .trim_start()
+                    .strip_prefix("pragma")?
+                    .trim_start();
+                if let Some(note) = pragma.strip_prefix("deprecated") {
+                    return Some(note.trim().to_string());
+                }
+            }
+            TSKind::comment => (),
+            _ => return None,
+        }
+        prev_node = node.prev_sibling();
+    }
+
+    None
+}